@@ -0,0 +1,142 @@
+//! Generates Realm files for the benchmarks, so they don't depend on real
+//! `.realm` files. The files are built using the same [`RealmBuilder`] as the
+//! unit tests, which requires the `test-utils` feature.
+
+use realm_db_reader::test_utils::{RealmBuilder, tagged};
+
+/// The name of the table in the generated files.
+pub const TABLE: &str = "class_Person";
+
+/// The number of rows in each subtable of the generated files.
+pub const TAGS_PER_ROW: usize = 4;
+
+/// The name of the table in the files generated by [`deep_tree`].
+pub const DEEP_TABLE: &str = "class_Reading";
+
+/// The number of children of every inner node in the files generated by
+/// [`deep_tree`], and the number of values in every leaf.
+const DEEP_TREE_FANOUT: usize = 4;
+
+/// The maximum number of values in a B+Tree leaf, as written by Realm. Longer
+/// columns are split over several leaves.
+const MAX_LEAF_SIZE: usize = 1000;
+
+/// Generate a Realm file holding a single table, [`TABLE`], with the given
+/// number of rows and the following columns:
+///
+/// - `id`: an indexed integer column, holding the row number.
+/// - `name`: a string column.
+/// - `tags`: a subtable column, where every row holds a subtable of
+///   [`TAGS_PER_ROW`] rows with a single string column, `tag`.
+pub fn people(rows: usize) -> Vec<u8> {
+    let mut builder = RealmBuilder::new();
+
+    let ids = (0..rows as u64).collect::<Vec<_>>();
+    let id_data = column(&mut builder, &ids, RealmBuilder::integers);
+    let id_index = int_index(&mut builder, &ids);
+
+    let names = (0..rows)
+        .map(|row| format!("person {row}"))
+        .collect::<Vec<_>>();
+    let names = column(&mut builder, &names, RealmBuilder::short_strings);
+
+    let tags_spec = builder.table_spec(&[2], &["tag"], &[0], &[]);
+    let tags = (0..rows)
+        .map(|row| {
+            let tags = (0..TAGS_PER_ROW)
+                .map(|tag| format!("tag {}", (row + tag) % 16))
+                .collect::<Vec<_>>();
+            let tags = builder.short_strings(&tags);
+            builder.refs(&[tags])
+        })
+        .collect::<Vec<_>>();
+    let tags = column(&mut builder, &tags, RealmBuilder::refs);
+
+    let table = builder.table(
+        &[0, 2, 5],
+        &["id", "name", "tags"],
+        &[1, 0, 0],
+        &[tags_spec],
+        &[id_data, id_index, names, tags],
+    );
+    let top = builder.group(&[(TABLE, table)]);
+
+    builder.into_bytes(top)
+}
+
+/// Generate a Realm file holding a single table, [`DEEP_TABLE`], with the
+/// given number of rows and a single integer column, `value`, holding the row
+/// number. The column is a B+Tree with only [`DEEP_TREE_FANOUT`] children per
+/// node, so reading a single value parses the headers of many inner nodes.
+pub fn deep_tree(rows: usize) -> Vec<u8> {
+    let mut builder = RealmBuilder::new();
+
+    let values = (0..rows as u64).collect::<Vec<_>>();
+    let mut nodes = values
+        .chunks(DEEP_TREE_FANOUT)
+        .map(|leaf| (builder.integers(leaf), leaf.len()))
+        .collect::<Vec<_>>();
+    // Every child but the last is full, so the inner nodes can use the compact
+    // form, storing the number of values per child rather than offsets.
+    let mut per_child = DEEP_TREE_FANOUT;
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks(DEEP_TREE_FANOUT)
+            .map(|children| {
+                let size = children.iter().map(|(_, size)| size).sum::<usize>();
+                let refs = children.iter().map(|(ref_, _)| *ref_).collect::<Vec<_>>();
+
+                let node = builder.inner_node(tagged(per_child as u64), &refs, size as u64);
+                (node, size)
+            })
+            .collect();
+        per_child *= DEEP_TREE_FANOUT;
+    }
+    let column = nodes
+        .first()
+        .map_or_else(|| builder.integers(&[]), |(ref_, _)| *ref_);
+
+    let table = builder.table(&[0], &["value"], &[0], &[], &[column]);
+    let top = builder.group(&[(DEEP_TABLE, table)]);
+
+    builder.into_bytes(top)
+}
+
+/// Append a column holding the given values, using `leaf` to append each
+/// leaf of at most [`MAX_LEAF_SIZE`] values. If there is more than one leaf,
+/// the leaves are joined under an inner node, whose ref is returned.
+fn column<T>(
+    builder: &mut RealmBuilder,
+    values: &[T],
+    leaf: impl Fn(&mut RealmBuilder, &[T]) -> u64,
+) -> u64 {
+    if values.len() <= MAX_LEAF_SIZE {
+        return leaf(builder, values);
+    }
+
+    let leaves = values
+        .chunks(MAX_LEAF_SIZE)
+        .map(|values| leaf(builder, values))
+        .collect::<Vec<_>>();
+
+    builder.inner_node(tagged(MAX_LEAF_SIZE as u64), &leaves, values.len() as u64)
+}
+
+/// Append a search index with a single leaf, for an integer column holding
+/// the given distinct values (each less than 2^32). The key of an integer is
+/// its first four bytes in little-endian order, and keys are ordered as
+/// big-endian numbers.
+fn int_index(builder: &mut RealmBuilder, values: &[u64]) -> u64 {
+    let mut entries = values
+        .iter()
+        .enumerate()
+        .map(|(row, value)| ((*value as u32).to_le_bytes(), tagged(row as u64)))
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|(key, _)| u32::from_be_bytes(*key));
+
+    let entries = entries
+        .iter()
+        .map(|(key, row)| (key, *row))
+        .collect::<Vec<_>>();
+    builder.index_leaf(&entries)
+}
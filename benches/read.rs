@@ -0,0 +1,137 @@
+//! Benchmarks for the core read paths, using generated files of various
+//! sizes, see [`fixture::people`].
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use realm_db_reader::{Realm, Value};
+
+mod fixture;
+
+/// The numbers of rows in the generated tables.
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+fn open(c: &mut Criterion) {
+    let mut benches = c.benchmark_group("open");
+    for rows in SIZES {
+        let bytes = fixture::people(rows);
+        benches.bench_with_input(BenchmarkId::from_parameter(rows), &bytes, |b, bytes| {
+            b.iter(|| {
+                let group = Realm::from_bytes(bytes.clone())
+                    .unwrap()
+                    .into_group()
+                    .unwrap();
+                black_box(group.get_table_by_name(fixture::TABLE).unwrap())
+            });
+        });
+    }
+    benches.finish();
+}
+
+fn full_scan(c: &mut Criterion) {
+    let mut benches = c.benchmark_group("get_rows");
+    for rows in SIZES {
+        let group = Realm::from_bytes(fixture::people(rows))
+            .unwrap()
+            .into_group()
+            .unwrap();
+        let table = group.get_table_by_name(fixture::TABLE).unwrap();
+
+        benches.throughput(Throughput::Elements(rows as u64));
+        benches.bench_function(BenchmarkId::from_parameter(rows), |b| {
+            b.iter(|| black_box(table.get_rows().unwrap()));
+        });
+    }
+    benches.finish();
+}
+
+fn indexed_lookup(c: &mut Criterion) {
+    let mut benches = c.benchmark_group("find_row_from_indexed_column");
+    for rows in SIZES {
+        let group = Realm::from_bytes(fixture::people(rows))
+            .unwrap()
+            .into_group()
+            .unwrap();
+        let table = group.get_table_by_name(fixture::TABLE).unwrap();
+        let id = Value::Int(rows as i64 / 2);
+        let row = table.find_row_from_indexed_column("id", &id).unwrap();
+        assert_eq!(
+            row.unwrap().get("id"),
+            Some(&id),
+            "index of fixture is broken"
+        );
+
+        benches.bench_function(BenchmarkId::from_parameter(rows), |b| {
+            b.iter(|| {
+                black_box(
+                    table
+                        .find_row_from_indexed_column("id", black_box(&id))
+                        .unwrap()
+                        .unwrap(),
+                )
+            });
+        });
+    }
+    benches.finish();
+}
+
+fn subtable_row(c: &mut Criterion) {
+    let mut benches = c.benchmark_group("get_row_with_subtable");
+    for rows in SIZES {
+        let group = Realm::from_bytes(fixture::people(rows))
+            .unwrap()
+            .into_group()
+            .unwrap();
+        let table = group.get_table_by_name(fixture::TABLE).unwrap();
+        let row_number = rows / 2;
+
+        benches.throughput(Throughput::Elements(fixture::TAGS_PER_ROW as u64));
+        benches.bench_function(BenchmarkId::from_parameter(rows), |b| {
+            b.iter(|| black_box(table.get_row(black_box(row_number)).unwrap()));
+        });
+    }
+    benches.finish();
+}
+
+/// Read every row of a deep B+Tree, with and without
+/// [`header_cache`](realm_db_reader::RealmOptions::header_cache). Prints the
+/// number of node headers parsed for a single scan, as the cache trades those
+/// for locking and hashing.
+fn deep_tree_scan(c: &mut Criterion) {
+    const ROWS: usize = 10_000;
+
+    let mut benches = c.benchmark_group("deep_tree_get_rows");
+    let bytes = fixture::deep_tree(ROWS);
+    for capacity in [0, 1_024] {
+        let group = Realm::options()
+            .header_cache(capacity)
+            .from_bytes(bytes.clone())
+            .unwrap()
+            .into_group()
+            .unwrap();
+        let table = group.get_table_by_name(fixture::DEEP_TABLE).unwrap();
+
+        let parses = group.header_parses();
+        table.get_rows().unwrap();
+        println!(
+            "deep_tree_get_rows/{capacity}: {} node headers parsed per scan",
+            group.header_parses() - parses
+        );
+
+        benches.throughput(Throughput::Elements(ROWS as u64));
+        benches.bench_function(BenchmarkId::new("header_cache", capacity), |b| {
+            b.iter(|| black_box(table.get_rows().unwrap()));
+        });
+    }
+    benches.finish();
+}
+
+criterion_group!(
+    benches,
+    open,
+    full_scan,
+    indexed_lookup,
+    subtable_row,
+    deep_tree_scan
+);
+criterion_main!(benches);
@@ -36,8 +36,10 @@ where
         let header = realm.header(ref_)?;
         let inner = Self::get_inner(&header, realm, ref_)?;
 
+        // For leaves holding longer strings, the header size is the number of
+        // child arrays rather than the number of strings.
         Ok(Self {
-            size: header.size as usize,
+            size: inner.size(),
             inner,
         })
     }
@@ -117,6 +119,13 @@ where
         realm: Arc<Realm>,
         ref_: RealmRef,
     ) -> crate::RealmResult<Box<dyn ArrayLike<T>>> {
+        // An empty leaf holds no strings, whatever its layout. Leaves of
+        // longer strings always have child arrays, even if they hold no
+        // strings, so read an empty leaf as short strings instead.
+        if header.size == 0 {
+            return Ok(Box::new(ArrayStringShort::from_ref(realm, ref_)?));
+        }
+
         Ok(match (header.has_refs(), header.context_flag()) {
             (false, _) => Box::new(ArrayStringShort::from_ref(realm, ref_)?),
             (true, false) => Box::new(SmallBlobsArray::from_ref(realm, ref_)?),
@@ -124,3 +133,29 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RealmBuilder;
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut builder = RealmBuilder::new();
+        let short = builder.short_strings(&["a", "b"]);
+        let medium = builder.blob_strings(&["a".repeat(70), "b".repeat(70)]);
+        let realm = Arc::new(builder.build(0));
+
+        for ref_ in [short, medium] {
+            let ref_ = RealmRef::new(ref_ as usize);
+            let array: ArrayString<String> =
+                ArrayString::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+            assert_eq!(array.size(), 2);
+            assert!(array.get(1).unwrap().starts_with('b'));
+            assert!(array.get(2).is_err());
+            assert!(array.is_null(2).is_err());
+            assert!(ArrayString::<String>::get_direct(Arc::clone(&realm), ref_, 2, ()).is_err());
+        }
+    }
+}
@@ -1,10 +1,11 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::array::RealmRef;
 use crate::realm::{Realm, RealmNode};
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use std::str;
+use crate::utils::check_index;
 use tracing::{debug, instrument};
 
 #[derive(Debug, Clone)]
@@ -26,7 +27,12 @@ impl NodeWithContext<()> for ArrayStringShort {
 impl ArrayLike<Option<String>> for ArrayStringShort {
     #[instrument(level = "debug")]
     fn get(&self, index: usize) -> crate::RealmResult<Option<String>> {
-        Ok(Self::get_static(&self.node, index).map(|s| s.to_string()))
+        check_index(index, self.node.header.size as usize)?;
+
+        Ok(
+            Self::get_static(self.node.header.width(), self.node.payload(), index)
+                .map(Cow::into_owned),
+        )
     }
 
     fn get_direct(
@@ -36,11 +42,14 @@ impl ArrayLike<Option<String>> for ArrayStringShort {
         _: (),
     ) -> crate::RealmResult<Option<String>> {
         let node = RealmNode::from_ref(realm, ref_)?;
+        check_index(index, node.header.size as usize)?;
 
-        Ok(Self::get_static(&node, index).map(|s| s.to_string()))
+        Ok(Self::get_static(node.header.width(), node.payload(), index).map(Cow::into_owned))
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.node.header.size as usize)?;
+
         let width = self.node.header.width();
         if width == 0 {
             return Ok(true);
@@ -75,7 +84,9 @@ impl ArrayLike<String> for ArrayStringShort {
             .map(|s| s.unwrap_or_default())
     }
 
-    fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.node.header.size as usize)?;
+
         // Implementing for `String`, so we always return false.
         Ok(false)
     }
@@ -86,15 +97,32 @@ impl ArrayLike<String> for ArrayStringShort {
 }
 
 impl ArrayStringShort {
-    #[instrument(level = "debug")]
-    fn get_static(node: &RealmNode, index: usize) -> Option<&str> {
-        let width = node.header.width() as usize;
+    /// Get the string at the given index in the array at `ref_`, borrowing it
+    /// from the file when it is valid UTF-8, rather than copying it.
+    pub(crate) fn get_borrowed(
+        realm: &Realm,
+        ref_: RealmRef,
+        index: usize,
+    ) -> crate::RealmResult<Option<Cow<'_, str>>> {
+        let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
+
+        let payload = realm.payload(ref_, header.payload_len());
+        Ok(Self::get_static(header.width(), payload, index))
+    }
+
+    /// Get the string at the given index, which must be in bounds. Invalid
+    /// UTF-8 is replaced with U+FFFD, as files may be corrupt.
+    #[instrument(level = "debug", skip(payload))]
+    fn get_static(width: u8, payload: &[u8], index: usize) -> Option<Cow<'_, str>> {
+        // An array with width 0 has no payload, and only holds nulls.
+        let width = width as usize;
         if width == 0 {
             debug!("get: width is 0, returning None");
             return None;
         }
 
-        let element_data = &node.payload()[index * width..(index + 1) * width];
+        let element_data = &payload[index * width..(index + 1) * width];
         let zeroes = element_data[width - 1] as usize;
         if zeroes == width {
             return None;
@@ -106,6 +134,57 @@ impl ArrayStringShort {
         );
 
         // e.g. width = 4, zeroes = 1, element_data = [xx, xx, 00, 01]
-        Some(unsafe { str::from_utf8_unchecked(&element_data[..width - 1 - zeroes]) })
+        Some(String::from_utf8_lossy(&element_data[..width - 1 - zeroes]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_node;
+
+    #[test]
+    fn test_strings() {
+        // Width 4 (width index 3): "ab", null, "".
+        let (realm, ref_) = single_node(0x0B, 3, &[b'a', b'b', 0, 1, 0, 0, 0, 4, 0, 0, 0, 3]);
+        let array = ArrayStringShort::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(
+            ArrayLike::<Option<String>>::get_all(&array).unwrap(),
+            vec![Some("ab".to_string()), None, Some(String::new())]
+        );
+        assert!(ArrayLike::<Option<String>>::is_null(&array, 1).unwrap());
+        assert!(!ArrayLike::<Option<String>>::is_null(&array, 2).unwrap());
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let (realm, ref_) = single_node(0x08, 2, &[]);
+        let array = ArrayStringShort::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(
+            ArrayLike::<Option<String>>::get_all(&array).unwrap(),
+            vec![None, None]
+        );
+        assert!(ArrayLike::<Option<String>>::is_null(&array, 0).unwrap());
+        assert_eq!(
+            <ArrayStringShort as ArrayLike<Option<String>>>::get_direct(realm, ref_, 1, ())
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let (realm, ref_) = single_node(0x0B, 0, &[]);
+        let array = ArrayStringShort::from_ref(realm, ref_).unwrap();
+
+        assert!(
+            ArrayLike::<Option<String>>::get_all(&array)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(ArrayLike::<Option<String>>::get(&array, 0).is_err());
+        assert!(ArrayLike::<Option<String>>::is_null(&array, 0).is_err());
     }
 }
@@ -2,9 +2,9 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::array::{Array, RealmRef};
-use crate::realm::Realm;
+use crate::realm::{NodeHeader, Realm};
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
 
 pub(crate) trait FromU64 {
     fn from_u64(value: u64) -> Self;
@@ -28,7 +28,7 @@ impl NodeWithContext<()> for IntegerArray {
 
 impl ArrayLike<u64> for IntegerArray {
     fn get(&self, index: usize) -> crate::RealmResult<u64> {
-        Ok(self.array.get(index))
+        self.array.get(index)
     }
 
     fn get_direct(
@@ -38,13 +38,9 @@ impl ArrayLike<u64> for IntegerArray {
         _: (),
     ) -> crate::RealmResult<u64> {
         let header = realm.header(ref_)?;
-        let width = header.width();
+        check_index(index, header.size as usize)?;
 
-        Ok(read_array_value(
-            realm.payload(ref_, header.payload_len()),
-            width,
-            index,
-        ))
+        header.read_element(realm.payload(ref_, header.payload_len()), index)
     }
 
     fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
@@ -58,9 +54,9 @@ impl ArrayLike<u64> for IntegerArray {
 
 impl ArrayLike<i64> for IntegerArray {
     fn get(&self, index: usize) -> crate::RealmResult<i64> {
-        let value = self.array.get(index);
+        let value = self.array.get(index)?;
 
-        Ok(i64::from_le_bytes(value.to_le_bytes()))
+        Ok(signed(&self.array.node.header, value))
     }
 
     fn get_direct(
@@ -70,10 +66,10 @@ impl ArrayLike<i64> for IntegerArray {
         _: (),
     ) -> crate::RealmResult<i64> {
         let header = realm.header(ref_)?;
-        let width = header.width();
+        check_index(index, header.size as usize)?;
 
-        let value = read_array_value(realm.payload(ref_, header.payload_len()), width, index);
-        Ok(i64::from_le_bytes(value.to_le_bytes()))
+        let value = header.read_element(realm.payload(ref_, header.payload_len()), index)?;
+        Ok(signed(&header, value))
     }
 
     fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
@@ -85,15 +81,21 @@ impl ArrayLike<i64> for IntegerArray {
     }
 }
 
+/// Nullable integers are stored with the value that represents null as the
+/// first element, followed by the actual values. An empty array holds no
+/// values at all (so every index is out of bounds), and an array with width 0
+/// only holds nulls (every element, including the null value, reads as 0).
 impl ArrayLike<Option<i64>> for IntegerArray {
     fn get(&self, index: usize) -> crate::RealmResult<Option<i64>> {
-        let value = self.array.get(index + 1);
-        let null_value = self.array.get(0);
+        check_index(index, self.array.size().saturating_sub(1))?;
+
+        let value = self.array.get(index + 1)?;
+        let null_value = self.array.get(0)?;
 
         Ok(if value == null_value {
             None
         } else {
-            Some(i64::from_le_bytes(value.to_le_bytes()))
+            Some(signed(&self.array.node.header, value))
         })
     }
 
@@ -104,27 +106,56 @@ impl ArrayLike<Option<i64>> for IntegerArray {
         _: (),
     ) -> crate::RealmResult<Option<i64>> {
         let header = realm.header(ref_)?;
-        let width = header.width();
+        check_index(index, (header.size as usize).saturating_sub(1))?;
 
+        let width = header.width();
         let value = read_array_value(realm.payload(ref_, header.payload_len()), width, index + 1);
         let null_value = read_array_value(realm.payload(ref_, header.payload_len()), width, 0);
 
         Ok(if value == null_value {
             None
         } else {
-            Some(i64::from_le_bytes(value.to_le_bytes()))
+            Some(signed(&header, value))
         })
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        let value = self.array.get(index + 1);
-        let null_value = self.array.get(0);
+        Ok(<Self as ArrayLike<Option<i64>>>::get(self, index)?.is_none())
+    }
 
-        Ok(value == null_value)
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        if self.array.size() == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Compare the raw values with the null value, without converting them.
+        let null_value = self.array.get(0)?;
+
+        (1..self.array.size())
+            .map(|index| Ok(self.array.get(index)? == null_value))
+            .collect()
     }
 
     fn size(&self) -> usize {
-        self.array.node.header.size as usize
+        // The first element is the null value, not an actual value.
+        self.array.size().saturating_sub(1)
+    }
+}
+
+/// Interpret a value read from an integer array as a signed integer. Realm
+/// stores integers of 8 bits and wider as signed (two's complement) integers,
+/// so narrow negative values need to be sign-extended. Narrower widths only
+/// hold non-negative values.
+fn signed(header: &NodeHeader, value: u64) -> i64 {
+    if header.width_scheme() != 0 {
+        return value as i64;
+    }
+
+    match header.width() {
+        8 => value as u8 as i8 as i64,
+        16 => value as u16 as i16 as i64,
+        32 => value as u32 as i32 as i64,
+        _ => value as i64,
     }
 }
 
@@ -135,9 +166,102 @@ impl IntegerArray {
 }
 
 impl IntegerArray {
-    pub(crate) fn get_integers(&self) -> Vec<u64> {
-        (0..self.array.node.header.size as usize)
-            .map(|i| self.array.get(i))
-            .collect()
+    /// Iterate over the raw values in the array, without collecting them
+    /// first. Prefer this over [`get_integers`](Self::get_integers) for arrays
+    /// that may be large, such as link lists.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = crate::RealmResult<u64>> + '_ {
+        (0..self.array.node.header.size as usize).map(|i| self.array.get(i))
+    }
+
+    /// Get all raw values in the array. This is meant for small arrays, such
+    /// as the column types in a table spec.
+    pub(crate) fn get_integers(&self) -> crate::RealmResult<Vec<u64>> {
+        self.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_node;
+
+    fn nullable(array: &IntegerArray) -> Vec<Option<i64>> {
+        ArrayLike::<Option<i64>>::get_all(array).unwrap()
+    }
+
+    #[test]
+    fn test_nullable_with_values() {
+        // Width 8: null value 0xFF, then 1, null, 3.
+        let (realm, ref_) = single_node(0x04, 4, &[0xFF, 1, 0xFF, 3]);
+        let array = IntegerArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(ArrayLike::<Option<i64>>::size(&array), 3);
+        assert_eq!(nullable(&array), vec![Some(1), None, Some(3)]);
+        assert_eq!(
+            <IntegerArray as ArrayLike<Option<i64>>>::get_direct(realm, ref_, 2, ()).unwrap(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_nullable_zero_width() {
+        let (realm, ref_) = single_node(0x00, 4, &[]);
+        let array = IntegerArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(ArrayLike::<Option<i64>>::size(&array), 3);
+        assert_eq!(nullable(&array), vec![None, None, None]);
+        assert!(ArrayLike::<Option<i64>>::is_null(&array, 1).unwrap());
+        assert_eq!(
+            <IntegerArray as ArrayLike<Option<i64>>>::get_direct(realm, ref_, 0, ()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let (realm, ref_) = single_node(0x00, 0, &[]);
+        let array = IntegerArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(ArrayLike::<Option<i64>>::size(&array), 0);
+        assert!(nullable(&array).is_empty());
+        assert!(ArrayLike::<Option<i64>>::get(&array, 0).is_err());
+        assert!(<IntegerArray as ArrayLike<Option<i64>>>::get_direct(realm, ref_, 0, ()).is_err());
+        assert!(ArrayLike::<i64>::get_all(&array).unwrap().is_empty());
+        assert!(array.get_integers().unwrap().is_empty());
+        assert!(array.iter().next().is_none());
+    }
+
+    #[test]
+    fn test_iter() {
+        let (realm, ref_) = single_node(0x04, 3, &[5, 0, 7]);
+        let array = IntegerArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(
+            array.iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            [5, 0, 7]
+        );
+        assert_eq!(array.iter().nth(2).unwrap().unwrap(), 7);
+        assert_eq!(array.get_integers().unwrap(), [5, 0, 7]);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let (realm, ref_) = single_node(0x04, 3, &[0, 1, 2]);
+        let array = IntegerArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(ArrayLike::<u64>::get(&array, 2).unwrap(), 2);
+        assert!(ArrayLike::<u64>::get(&array, 3).is_err());
+        assert!(ArrayLike::<i64>::get(&array, 3).is_err());
+        assert!(ArrayLike::<Option<i64>>::get(&array, 2).is_err());
+        assert!(ArrayLike::<Option<i64>>::is_null(&array, 2).is_err());
+        assert!(<IntegerArray as ArrayLike<i64>>::get_direct(realm, ref_, 3, ()).is_err());
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let (realm, ref_) = single_node(0x00, 3, &[]);
+        let array = IntegerArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(ArrayLike::<i64>::get_all(&array).unwrap(), vec![0, 0, 0]);
     }
 }
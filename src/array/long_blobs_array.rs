@@ -3,10 +3,11 @@ use std::sync::Arc;
 
 use tracing::{instrument, warn};
 
+use crate::RealmFileError;
 use crate::array::{Array, RealmRef};
 use crate::realm::{Realm, RealmNode};
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::{self, read_array_value};
+use crate::utils::{self, check_index, read_array_value};
 
 #[derive(Debug, Clone)]
 pub(crate) struct LongBlobsArray {
@@ -21,14 +22,13 @@ impl NodeWithContext<()> for LongBlobsArray {
     {
         let array = Array::from_ref(realm, ref_)?;
 
-        assert!(
-            array.node.header.has_refs(),
-            "LongBlobsArray must have references"
-        );
-        assert!(
-            array.node.header.context_flag(),
-            "LongBlobsArray must have context flag set"
-        );
+        if !array.node.header.has_refs() || !array.node.header.context_flag() {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "long blobs array at {ref_:?} must hold refs and have its context flag set"
+                ),
+            });
+        }
 
         Ok(Self { array })
     }
@@ -36,6 +36,8 @@ impl NodeWithContext<()> for LongBlobsArray {
 
 impl LongBlobsArray {
     fn element_is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.array.size())?;
+
         Ok(self
             .array
             .get_node::<RealmNode>(index)?
@@ -52,11 +54,14 @@ impl LongBlobsArray {
             return Ok(None);
         }
 
-        assert!(
-            size <= payload.len(),
-            "LongBlobsArray: size ({size}) is greater than payload length ({})",
-            payload.len()
-        );
+        if size > payload.len() {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "blob at {ref_:?} has size {size}, but a payload of {} bytes",
+                    payload.len()
+                ),
+            });
+        }
 
         // The payload is owned by item.node, which is dropped at the end of this function.
         // Returning a reference to its data is invalid. Instead, return an owned Vec<u8>.
@@ -67,7 +72,9 @@ impl LongBlobsArray {
 impl ArrayLike<Option<Vec<u8>>> for LongBlobsArray {
     #[instrument(level = "debug")]
     fn get(&self, index: usize) -> crate::RealmResult<Option<Vec<u8>>> {
-        let Some(ref_) = self.array.get_ref(index) else {
+        check_index(index, self.array.size())?;
+
+        let Some(ref_) = self.array.get_ref(index)? else {
             warn!("get: index={index} returned NULL");
             return Ok(None);
         };
@@ -82,11 +89,7 @@ impl ArrayLike<Option<Vec<u8>>> for LongBlobsArray {
         _: (),
     ) -> crate::RealmResult<Option<Vec<u8>>> {
         let header = realm.header(ref_)?;
-
-        assert!(
-            index < header.size as usize,
-            "LongBlobsArray: index ({index}) is out of bounds"
-        );
+        check_index(index, header.size as usize)?;
 
         let item_ref = read_array_value(
             realm.payload(ref_, header.payload_len()),
@@ -114,7 +117,7 @@ impl ArrayLike<Option<String>> for LongBlobsArray {
     fn get(&self, index: usize) -> crate::RealmResult<Option<String>> {
         let bytes = <Self as ArrayLike<Option<Vec<u8>>>>::get(self, index)?;
 
-        Ok(bytes.map(utils::string_from_bytes))
+        bytes.map(utils::string_from_bytes).transpose()
     }
 
     fn get_direct(
@@ -128,7 +131,7 @@ impl ArrayLike<Option<String>> for LongBlobsArray {
     {
         let bytes = <Self as ArrayLike<Option<Vec<u8>>>>::get_direct(realm, ref_, index, context)?;
 
-        Ok(bytes.map(utils::string_from_bytes))
+        bytes.map(utils::string_from_bytes).transpose()
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
@@ -166,3 +169,44 @@ impl ArrayLike<String> for LongBlobsArray {
         self.array.node.header.size as usize
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{HAS_REFS, RealmBuilder};
+
+    #[test]
+    fn test_get() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.blob(b"first\0");
+        let array = builder.integers_with_flags(HAS_REFS | 0x20, &[first, 0]);
+        let (realm, ref_) = builder.build_with_ref(array);
+        let array = LongBlobsArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(
+            ArrayLike::<Option<String>>::get(&array, 0).unwrap(),
+            Some("first".to_string())
+        );
+        assert_eq!(ArrayLike::<Option<String>>::get(&array, 1).unwrap(), None);
+        assert!(ArrayLike::<Option<String>>::is_null(&array, 1).unwrap());
+        assert!(ArrayLike::<Option<String>>::get(&array, 2).is_err());
+        assert!(ArrayLike::<Option<String>>::is_null(&array, 2).is_err());
+        assert!(<LongBlobsArray as ArrayLike<String>>::get_direct(realm, ref_, 2, ()).is_err());
+    }
+
+    #[test]
+    fn test_get_large_blobs() {
+        // A blob spanning many 8-byte boundaries, whose length is not a
+        // multiple of 8, followed directly by another blob.
+        let large = "0123456789abcdef".repeat(4096) + "xyz";
+        let mut builder = RealmBuilder::new();
+        let first = builder.blob(format!("{large}\0").as_bytes());
+        let second = builder.blob(b"second\0");
+        let array = builder.integers_with_flags(HAS_REFS | 0x20, &[first, second]);
+        let (realm, ref_) = builder.build_with_ref(array);
+        let array = LongBlobsArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(ArrayLike::<String>::get(&array, 0).unwrap(), large);
+        assert_eq!(ArrayLike::<String>::get(&array, 1).unwrap(), "second");
+    }
+}
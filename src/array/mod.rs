@@ -18,10 +18,15 @@ use std::sync::Arc;
 
 use tracing::{debug, instrument};
 
+use crate::RealmFileError;
 use crate::realm::{Realm, RealmNode};
 use crate::traits::Node;
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
 
+/// The offset of a node in the file. Refs are stored as 64-bit integers in the
+/// file, and converted to `usize`, so on 64-bit platforms files larger than 4
+/// GiB can be read. Larger files can't be mapped into memory on 32-bit
+/// platforms in the first place.
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct RealmRef(usize);
 
@@ -33,7 +38,7 @@ impl Debug for RealmRef {
 
 impl RealmRef {
     pub(crate) fn new(ref_: usize) -> Self {
-        assert!(ref_ % 8 == 0, "RealmRef must be a multiple of 8");
+        assert!(ref_.is_multiple_of(8), "RealmRef must be a multiple of 8");
 
         Self(ref_)
     }
@@ -84,51 +89,60 @@ impl Node for Array {
 }
 
 impl Array {
+    /// Read the element at the given index as an unsigned integer.
+    ///
+    /// Returns an error if the index is out of bounds, or the elements of this
+    /// array can't be read as integers.
     #[instrument(level = "debug")]
-    pub(crate) fn get(&self, index: usize) -> u64 {
-        assert!(
-            index < self.node.header.size as usize,
-            "Index out of bounds: {index} >= {}",
-            self.node.header.size
-        );
+    pub(crate) fn get(&self, index: usize) -> crate::RealmResult<u64> {
+        check_index(index, self.size())?;
 
-        self.get_direct(self.width, index)
+        self.get_direct(index)
     }
 
+    /// Read the element at the given index as a ref, or `None` if it is 0.
+    ///
+    /// Returns an error if the index is out of bounds, the elements of this
+    /// array can't be read as integers, or the element is not a valid ref.
     #[instrument(level = "debug")]
-    pub(crate) fn get_ref(&self, index: usize) -> Option<RealmRef> {
-        assert!(
-            index < self.node.header.size as usize,
-            "Index out of bounds: {index} >= {}",
-            self.node.header.size
-        );
-
-        let ref_ = self.get_direct(self.width, index);
+    pub(crate) fn get_ref(&self, index: usize) -> crate::RealmResult<Option<RealmRef>> {
+        check_index(index, self.size())?;
 
+        let ref_ = self.get_direct(index)?;
         if ref_ == 0 {
-            return None;
+            return Ok(None);
         }
 
-        assert!(ref_ % 8 == 0);
+        if !ref_.is_multiple_of(8) {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "element {index} of array at {:?} is not a valid ref: 0x{ref_:X}",
+                    self.node.ref_
+                ),
+            });
+        }
 
-        Some(RealmRef(ref_ as usize))
+        Ok(Some(RealmRef(ref_ as usize)))
     }
 
+    /// Read the element at the given index as either a ref or a tagged
+    /// value, or `None` if it is 0.
+    ///
+    /// Returns an error if the index is out of bounds, or the elements of this
+    /// array can't be read as integers.
     #[instrument(level = "debug")]
-    pub(crate) fn get_ref_or_tagged_value(&self, index: usize) -> Option<RefOrTaggedValue> {
-        assert!(
-            index < self.node.header.size as usize,
-            "Index out of bounds: {index} >= {}",
-            self.node.header.size
-        );
-
-        let value = self.get_direct(self.width, index);
+    pub(crate) fn get_ref_or_tagged_value(
+        &self,
+        index: usize,
+    ) -> crate::RealmResult<Option<RefOrTaggedValue>> {
+        check_index(index, self.size())?;
 
+        let value = self.get_direct(index)?;
         if value == 0 {
-            return None;
+            return Ok(None);
         }
 
-        Some(RefOrTaggedValue::from_raw(value))
+        Ok(Some(RefOrTaggedValue::from_raw(value)))
     }
 
     #[instrument(level = "debug")]
@@ -136,7 +150,7 @@ impl Array {
     where
         N: Node,
     {
-        let Some(ref_) = self.get_ref(index) else {
+        let Some(ref_) = self.get_ref(index)? else {
             return Ok(None);
         };
 
@@ -148,21 +162,87 @@ impl Array {
         N::from_ref(self.node.realm.clone(), ref_).map(Some)
     }
 
-    pub(crate) fn back(&self) -> u64 {
-        let size = self.node.header.size as usize;
+    /// Read the last element, or 0 if the array is empty.
+    pub(crate) fn back(&self) -> crate::RealmResult<u64> {
+        let size = self.size();
         if size == 0 {
-            return 0;
+            return Ok(0);
         }
 
-        read_array_value(self.node.payload(), self.width, size - 1)
+        self.get_direct(size - 1)
     }
 
+    /// Read the element at the given index, which must be in bounds.
+    ///
+    /// Returns an error if the elements of this array can't be read as
+    /// integers, see
+    /// [`NodeHeader::read_element`](crate::realm::NodeHeader::read_element).
     #[instrument(level = "debug")]
-    fn get_direct(&self, width: u8, index: usize) -> u64 {
-        read_array_value(self.node.payload(), self.width, index)
+    fn get_direct(&self, index: usize) -> crate::RealmResult<u64> {
+        if self.node.header.width_scheme() == 0 {
+            return Ok(read_array_value(self.node.payload(), self.width, index));
+        }
+
+        self.node
+            .header
+            .read_element(self.node.payload(), index)
+            .map_err(|error| RealmFileError::InvalidRealmFile {
+                reason: format!("{error} (array at {:?})", self.node.ref_),
+            })
     }
 
     pub(crate) fn size(&self) -> usize {
         self.node.header.size as usize
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_node;
+
+    #[test]
+    fn test_get() {
+        // 8-bit elements (width index 4): a ref, a tagged value, and a null
+        // ref.
+        let (realm, ref_) = single_node(0x44, 3, &[0x10, 0x03, 0x00]);
+        let array = Array::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(array.get(1).unwrap(), 3);
+        assert_eq!(array.get_ref(0).unwrap(), Some(RealmRef::new(0x10)));
+        assert_eq!(array.get_ref(2).unwrap(), None);
+        assert!(matches!(
+            array.get_ref_or_tagged_value(1).unwrap(),
+            Some(RefOrTaggedValue::TaggedValue(1))
+        ));
+        assert_eq!(array.back().unwrap(), 0);
+
+        // Out of bounds, and a tagged value that is not a valid ref.
+        assert!(array.get(3).is_err());
+        assert!(array.get_ref_or_tagged_value(3).is_err());
+        assert!(array.get_ref(1).is_err());
+    }
+
+    #[test]
+    fn test_get_unreadable_width_scheme() {
+        // Width scheme 1 with elements of 16 bytes, which don't fit in a u64.
+        let (realm, ref_) = single_node(0x0D, 1, &[0; 16]);
+        let array = Array::from_ref(realm, ref_).unwrap();
+        assert!(matches!(
+            array.get(0),
+            Err(RealmFileError::InvalidRealmFile { .. })
+        ));
+        assert!(array.get_ref(0).is_err());
+        assert!(array.get_ref_or_tagged_value(0).is_err());
+        assert!(array.back().is_err());
+
+        // Width scheme 2, i.e. raw bytes.
+        let (realm, ref_) = single_node(0x10, 4, b"blob");
+        let array = Array::from_ref(realm, ref_).unwrap();
+        assert!(matches!(
+            array.get(0),
+            Err(RealmFileError::InvalidRealmFile { .. })
+        ));
+        assert!(array.get_ref(0).is_err());
+    }
+}
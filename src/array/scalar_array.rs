@@ -6,7 +6,7 @@ use tracing::instrument;
 use crate::Realm;
 use crate::realm::RealmNode;
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
 
 use super::RealmRef;
 
@@ -31,17 +31,14 @@ macro_rules! impl_scalar_bytewise {
         impl ArrayLike<$scalar> for ScalarArray {
             #[instrument(level = "debug")]
             fn get(&self, index: usize) -> crate::RealmResult<$scalar> {
-                assert!(
-                    index < self.node.header.size as usize,
-                    "Index out of bounds: {index} >= {}",
-                    self.node.header.size
-                );
-
-                let offset_start = index * std::mem::size_of::<$scalar>();
-                let offset_end = offset_start + std::mem::size_of::<$scalar>();
-                let bytes = &self.node.payload()[offset_start..offset_end];
-
-                Ok(<$scalar>::from_le_bytes(bytes.try_into().unwrap()))
+                check_index(index, self.node.header.size as usize)?;
+
+                Ok(Self::read_bytewise(
+                    self.node.header.width(),
+                    self.node.payload(),
+                    index,
+                    <$scalar>::from_le_bytes,
+                ))
             }
 
             fn get_direct(
@@ -51,12 +48,15 @@ macro_rules! impl_scalar_bytewise {
                 _: (),
             ) -> crate::RealmResult<$scalar> {
                 let header = realm.header(ref_)?;
+                check_index(index, header.size as usize)?;
                 let payload = realm.payload(ref_, header.payload_len());
-                let offset_start = index * std::mem::size_of::<$scalar>();
-                let offset_end = offset_start + std::mem::size_of::<$scalar>();
-                let bytes = &payload[offset_start..offset_end];
 
-                Ok(<$scalar>::from_le_bytes(bytes.try_into().unwrap()))
+                Ok(Self::read_bytewise(
+                    header.width(),
+                    payload,
+                    index,
+                    <$scalar>::from_le_bytes,
+                ))
             }
 
             fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
@@ -75,6 +75,7 @@ impl_scalar_bytewise!(f64);
 
 impl ArrayLike<bool> for ScalarArray {
     fn get(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.node.header.size as usize)?;
         let value = read_array_value(self.node.payload(), self.node.header.width(), index);
         Ok(value != 0)
     }
@@ -86,6 +87,7 @@ impl ArrayLike<bool> for ScalarArray {
         _: (),
     ) -> crate::RealmResult<bool> {
         let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
         let payload = realm.payload(ref_, header.payload_len());
 
         let value = read_array_value(payload, header.width(), index);
@@ -101,16 +103,18 @@ impl ArrayLike<bool> for ScalarArray {
     }
 }
 
+/// Nullable booleans use the same layout as nullable integers: the value that
+/// represents null comes first, followed by the actual values. See the
+/// `ArrayLike<Option<i64>>` implementation for
+/// [`IntegerArray`](crate::array::IntegerArray).
 impl ArrayLike<Option<bool>> for ScalarArray {
     fn get(&self, index: usize) -> crate::RealmResult<Option<bool>> {
-        let value = read_array_value(self.node.payload(), self.node.header.width(), index + 1);
-        let null_value = read_array_value(self.node.payload(), self.node.header.width(), 0);
-
-        Ok(if value == null_value {
-            None
-        } else {
-            Some(value != 0)
-        })
+        Self::get_nullable_bool(
+            self.node.payload(),
+            self.node.header.width(),
+            self.node.header.size as usize,
+            index,
+        )
     }
 
     fn get_direct(
@@ -122,8 +126,46 @@ impl ArrayLike<Option<bool>> for ScalarArray {
         let header = realm.header(ref_)?;
         let payload = realm.payload(ref_, header.payload_len());
 
-        let value = read_array_value(payload, header.width(), index + 1);
-        let null_value = read_array_value(payload, header.width(), 0);
+        Self::get_nullable_bool(payload, header.width(), header.size as usize, index)
+    }
+
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        Ok(<Self as ArrayLike<Option<bool>>>::get(self, index)?.is_none())
+    }
+
+    fn size(&self) -> usize {
+        // The first element is the null value, not an actual value.
+        (self.node.header.size as usize).saturating_sub(1)
+    }
+}
+
+impl ScalarArray {
+    /// Read a little-endian scalar of `N` bytes. An array with width 0 has no
+    /// payload, and all of its elements read as zero, as with integer arrays.
+    fn read_bytewise<T, const N: usize>(
+        width: u8,
+        payload: &[u8],
+        index: usize,
+        from_le_bytes: fn([u8; N]) -> T,
+    ) -> T {
+        if width == 0 {
+            return from_le_bytes([0; N]);
+        }
+
+        let offset_start = index * N;
+        from_le_bytes(payload[offset_start..offset_start + N].try_into().unwrap())
+    }
+
+    fn get_nullable_bool(
+        payload: &[u8],
+        width: u8,
+        size: usize,
+        index: usize,
+    ) -> crate::RealmResult<Option<bool>> {
+        check_index(index, size.saturating_sub(1))?;
+
+        let value = read_array_value(payload, width, index + 1);
+        let null_value = read_array_value(payload, width, 0);
 
         Ok(if value == null_value {
             None
@@ -131,15 +173,72 @@ impl ArrayLike<Option<bool>> for ScalarArray {
             Some(value != 0)
         })
     }
+}
 
-    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        let value = read_array_value(self.node.payload(), self.node.header.width(), index + 1);
-        let null_value = read_array_value(self.node.payload(), self.node.header.width(), 0);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_node;
+
+    #[test]
+    fn test_nullable_bool() {
+        // Width 2: null value 2, then true, null, false.
+        let (realm, ref_) = single_node(0x02, 4, &[0b00_10_01_10]);
+        let array = ScalarArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(ArrayLike::<Option<bool>>::size(&array), 3);
+        assert_eq!(
+            ArrayLike::<Option<bool>>::get_all(&array).unwrap(),
+            vec![Some(true), None, Some(false)]
+        );
+    }
+
+    #[test]
+    fn test_nullable_bool_zero_width() {
+        let (realm, ref_) = single_node(0x00, 3, &[]);
+        let array = ScalarArray::from_ref(realm, ref_).unwrap();
 
-        Ok(value == null_value)
+        assert_eq!(
+            ArrayLike::<Option<bool>>::get_all(&array).unwrap(),
+            vec![None, None]
+        );
+        assert!(ArrayLike::<Option<bool>>::is_null(&array, 0).unwrap());
     }
 
-    fn size(&self) -> usize {
-        self.node.header.size as usize
+    #[test]
+    fn test_empty() {
+        let (realm, ref_) = single_node(0x00, 0, &[]);
+        let array = ScalarArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(ArrayLike::<Option<bool>>::size(&array), 0);
+        assert!(ArrayLike::<Option<bool>>::get(&array, 0).is_err());
+        assert!(ArrayLike::<bool>::get_all(&array).unwrap().is_empty());
+        assert!(ArrayLike::<f64>::get_all(&array).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let (realm, ref_) = single_node(0x07, 1, &1.5f64.to_le_bytes());
+        let array = ScalarArray::from_ref(Arc::clone(&realm), ref_).unwrap();
+
+        assert_eq!(ArrayLike::<f64>::get(&array, 0).unwrap(), 1.5);
+        assert!(ArrayLike::<f64>::get(&array, 1).is_err());
+        assert!(ArrayLike::<f32>::get(&array, 1).is_err());
+        assert!(ArrayLike::<bool>::get(&array, 1).is_err());
+        assert!(ArrayLike::<Option<bool>>::get(&array, 0).is_err());
+        assert!(<ScalarArray as ArrayLike<f64>>::get_direct(realm, ref_, 1, ()).is_err());
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let (realm, ref_) = single_node(0x00, 2, &[]);
+        let array = ScalarArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(
+            ArrayLike::<bool>::get_all(&array).unwrap(),
+            vec![false, false]
+        );
+        assert_eq!(ArrayLike::<f32>::get_all(&array).unwrap(), vec![0.0, 0.0]);
+        assert_eq!(ArrayLike::<f64>::get_all(&array).unwrap(), vec![0.0, 0.0]);
     }
 }
@@ -3,10 +3,11 @@ use std::sync::Arc;
 
 use tracing::{instrument, warn};
 
+use crate::RealmFileError;
 use crate::array::{Array, RealmRef};
 use crate::realm::{Realm, RealmNode};
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils;
+use crate::utils::{self, check_index};
 
 #[derive(Debug, Clone)]
 pub(crate) struct SmallBlobsArray {
@@ -23,15 +24,31 @@ impl NodeWithContext<()> for SmallBlobsArray {
         let array = Array::from_ref(realm, ref_)?;
 
         let size = array.node.header.size as usize;
-        assert!(size >= 2, "SmallBlobsArray size must be at least 2");
-        assert!(size <= 3, "SmallBlobsArray size must be at most 3");
+        if !(2..=3).contains(&size) {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "small blobs array at {ref_:?} has {size} children, expected 2 or 3"
+                ),
+            });
+        }
 
-        let lengths_array: Array = array.get_node(0)?.unwrap();
-        let blobs: RealmNode = array.get_node(1)?.unwrap();
+        let missing = |what: &str| RealmFileError::InvalidRealmFile {
+            reason: format!("small blobs array at {ref_:?} has no {what}"),
+        };
+        let lengths_array: Array = array.get_node(0)?.ok_or_else(|| missing("lengths"))?;
+        let blobs: RealmNode = array.get_node(1)?.ok_or_else(|| missing("blobs"))?;
         let null_array: Option<Array> = if size == 3 { array.get_node(2)? } else { None };
 
-        if let Some(null_array) = &null_array {
-            assert_eq!(lengths_array.size(), null_array.size());
+        if let Some(null_array) = &null_array
+            && lengths_array.size() != null_array.size()
+        {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "small blobs array at {ref_:?} has {} lengths, but {} null flags",
+                    lengths_array.size(),
+                    null_array.size()
+                ),
+            });
         }
 
         Ok(Self {
@@ -42,39 +59,54 @@ impl NodeWithContext<()> for SmallBlobsArray {
     }
 }
 
+impl SmallBlobsArray {
+    fn element_is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.lengths.size())?;
+
+        Ok(match &self.null {
+            Some(nulls) => nulls.get(index)? == 0,
+            None => false,
+        })
+    }
+}
+
 impl ArrayLike<Option<Vec<u8>>> for SmallBlobsArray {
     #[instrument(level = "debug")]
     fn get(&self, index: usize) -> crate::RealmResult<Option<Vec<u8>>> {
+        check_index(index, self.lengths.size())?;
+
         if let Some(null_array) = &self.null {
-            let is_null = null_array.get(index);
-            assert!(
-                is_null == 0 || is_null == 1,
-                "Invalid null value: {is_null}"
-            );
-            if is_null == 0 {
-                return Ok(None);
+            match null_array.get(index)? {
+                0 => return Ok(None),
+                1 => {}
+                is_null => {
+                    return Err(RealmFileError::InvalidRealmFile {
+                        reason: format!(
+                            "small blobs array has invalid null flag {is_null} for element {index}"
+                        ),
+                    });
+                }
             }
         }
 
         let begin = if index == 0 {
             0
         } else {
-            self.lengths.get(index - 1) as usize
+            self.lengths.get(index - 1)? as usize
         };
-        let end = self.lengths.get(index) as usize;
-
-        assert!(
-            end > begin,
-            "Invalid blob length: end ({end}) <= begin ({begin})"
-        );
-
-        assert!(
-            end <= self.blobs.payload().len(),
-            "Blob end index out of bounds: {end} >= {}",
-            self.blobs.payload().len()
-        );
+        let end = self.lengths.get(index)? as usize;
+
+        let payload = self.blobs.payload();
+        if end <= begin || end > payload.len() {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "small blobs array has invalid bounds {begin}..{end} for element {index}, with {} bytes of blobs",
+                    payload.len()
+                ),
+            });
+        }
 
-        Ok(Some(self.blobs.payload()[begin..end].to_vec()))
+        Ok(Some(payload[begin..end].to_vec()))
     }
 
     fn get_direct(
@@ -89,11 +121,7 @@ impl ArrayLike<Option<Vec<u8>>> for SmallBlobsArray {
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        if let Some(nulls) = &self.null {
-            Ok(nulls.get(index) == 0)
-        } else {
-            Ok(false)
-        }
+        self.element_is_null(index)
     }
 
     fn size(&self) -> usize {
@@ -105,7 +133,7 @@ impl ArrayLike<Option<String>> for SmallBlobsArray {
     fn get(&self, index: usize) -> crate::RealmResult<Option<String>> {
         let bytes = <Self as ArrayLike<Option<Vec<u8>>>>::get(self, index)?;
 
-        Ok(bytes.map(utils::string_from_bytes))
+        bytes.map(utils::string_from_bytes).transpose()
     }
 
     fn get_direct(
@@ -119,15 +147,11 @@ impl ArrayLike<Option<String>> for SmallBlobsArray {
     {
         let bytes = <Self as ArrayLike<Option<Vec<u8>>>>::get_direct(realm, ref_, index, context)?;
 
-        Ok(bytes.map(utils::string_from_bytes))
+        bytes.map(utils::string_from_bytes).transpose()
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        if let Some(nulls) = &self.null {
-            Ok(nulls.get(index) == 0)
-        } else {
-            Ok(false)
-        }
+        self.element_is_null(index)
     }
 
     fn size(&self) -> usize {
@@ -154,14 +178,50 @@ impl ArrayLike<String> for SmallBlobsArray {
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        if let Some(nulls) = &self.null {
-            Ok(nulls.get(index) == 0)
-        } else {
-            Ok(false)
-        }
+        self.element_is_null(index)
     }
 
     fn size(&self) -> usize {
         self.lengths.size()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RealmBuilder;
+
+    #[test]
+    fn test_get() {
+        let mut builder = RealmBuilder::new();
+        let strings = builder.blob_strings(&["a", "bc"]);
+        let (realm, ref_) = builder.build_with_ref(strings);
+        let array = SmallBlobsArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(ArrayLike::<String>::size(&array), 2);
+        assert_eq!(ArrayLike::<String>::get(&array, 1).unwrap(), "bc");
+        assert!(!ArrayLike::<String>::is_null(&array, 1).unwrap());
+        assert!(ArrayLike::<String>::get(&array, 2).is_err());
+        assert!(ArrayLike::<Option<Vec<u8>>>::get(&array, 2).is_err());
+        assert!(ArrayLike::<String>::is_null(&array, 2).is_err());
+    }
+
+    #[test]
+    fn test_get_corrupt() {
+        let mut builder = RealmBuilder::new();
+        // The second end offset lies beyond the blob, and the string in the
+        // blob lacks its trailing null byte.
+        let ends = builder.integers(&[2, 9]);
+        let blob = builder.blob(b"ab");
+        let strings = builder.refs(&[ends, blob]);
+        let (realm, ref_) = builder.build_with_ref(strings);
+        let array = SmallBlobsArray::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(
+            ArrayLike::<Option<Vec<u8>>>::get(&array, 0).unwrap(),
+            Some(b"ab".to_vec())
+        );
+        assert!(ArrayLike::<String>::get(&array, 0).is_err());
+        assert!(ArrayLike::<Option<Vec<u8>>>::get(&array, 1).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use crate::array::{Array, IntegerArray, RealmRef, RefOrTaggedValue};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
 use crate::value::Backlink;
 use std::sync::Arc;
 
@@ -19,6 +20,8 @@ impl ColumnType for BacklinkColumnType {
     type Value = Option<Backlink>;
     type LeafType = BacklinkArray;
     type LeafContext = BacklinkContext;
+
+    const KIND: ColumnKind = ColumnKind::BackLink;
 }
 
 #[derive(Debug)]
@@ -45,7 +48,9 @@ impl NodeWithContext<BacklinkContext> for BacklinkArray {
 
 impl ArrayLike<Option<Backlink>, BacklinkContext> for BacklinkArray {
     fn get(&self, index: usize) -> crate::RealmResult<Option<Backlink>> {
-        let Some(ref_or_tagged) = self.root.get_ref_or_tagged_value(index) else {
+        check_index(index, self.root.size())?;
+
+        let Some(ref_or_tagged) = self.root.get_ref_or_tagged_value(index)? else {
             return Ok(None);
         };
 
@@ -63,6 +68,7 @@ impl ArrayLike<Option<Backlink>, BacklinkContext> for BacklinkArray {
         context: BacklinkContext,
     ) -> crate::RealmResult<Option<Backlink>> {
         let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
         let payload = realm.payload(ref_, header.payload_len());
 
         let ref_or_tagged = match read_array_value(payload, header.width(), index) {
@@ -77,7 +83,9 @@ impl ArrayLike<Option<Backlink>, BacklinkContext> for BacklinkArray {
         )?))
     }
 
-    fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.root.size())?;
+
         Ok(false)
     }
 
@@ -96,10 +104,9 @@ impl BacklinkArray {
             RefOrTaggedValue::Ref(ref_) => {
                 let backlink_list = IntegerArray::from_ref(Arc::clone(realm), ref_)?;
                 let values = backlink_list
-                    .get_integers()
-                    .into_iter()
-                    .map(|n| n as usize)
-                    .collect();
+                    .iter()
+                    .map(|n| Ok(n? as usize))
+                    .collect::<crate::RealmResult<_>>()?;
                 Ok(Backlink::new(
                     context.target_table_number,
                     context.target_table_column_number,
@@ -138,3 +145,25 @@ pub(crate) fn create_backlink_column(
 }
 
 type BacklinkColumn = ColumnImpl<BacklinkColumnType>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{HAS_REFS, single_node, tagged};
+
+    #[test]
+    fn test_leaf_out_of_bounds() {
+        let context = BacklinkContext {
+            target_table_number: 1,
+            target_table_column_number: 2,
+        };
+        let (realm, ref_) = single_node(HAS_REFS | 0x04, 2, &[0, tagged(4) as u8]);
+        let leaf = BacklinkArray::from_ref_with_context(Arc::clone(&realm), ref_, context).unwrap();
+
+        assert_eq!(leaf.get(0).unwrap(), None);
+        assert_eq!(leaf.get(1).unwrap(), Some(Backlink::new(1, 2, vec![4])));
+        assert!(leaf.get(2).is_err());
+        assert!(leaf.is_null(2).is_err());
+        assert!(BacklinkArray::get_direct(realm, ref_, 2, context).is_err());
+    }
+}
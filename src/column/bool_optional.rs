@@ -1,6 +1,7 @@
 use crate::array::{RealmRef, ScalarArray};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use std::sync::Arc;
 
@@ -11,6 +12,8 @@ impl ColumnType for BoolNullableColumnType {
     type Value = Option<bool>;
     type LeafType = ScalarArray;
     type LeafContext = ();
+
+    const KIND: ColumnKind = ColumnKind::Bool;
 }
 
 // Factory function for nullable bool columns
@@ -2,6 +2,7 @@ use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 use tracing::instrument;
 
+use crate::RealmFileError;
 use crate::array::{Array, RealmRef};
 use crate::column::ColumnType;
 use crate::realm::Realm;
@@ -62,6 +63,70 @@ impl<T: ColumnType> BpTree<T> {
         )
     }
 
+    /// Find the leaf holding the element at the given index, returning the ref
+    /// of the leaf, and the index of the element in that leaf.
+    pub(crate) fn find_leaf(&self, index: usize) -> crate::RealmResult<(RealmRef, usize)> {
+        if self.root_is_leaf() {
+            return Ok((self.root.node.ref_, index));
+        }
+
+        self.root_as_node().get_bptree_leaf(index)
+    }
+
+    pub(crate) fn realm(&self) -> &Arc<Realm> {
+        &self.root.node.realm
+    }
+
+    pub(crate) fn context(&self) -> T::LeafContext {
+        self.context
+    }
+
+    /// Get all values in the tree, walking the leaves from left to right
+    /// instead of descending from the root for every element.
+    #[instrument(level = "debug")]
+    pub(crate) fn get_all(&self) -> crate::RealmResult<Vec<T::Value>> {
+        if self.root_is_leaf() {
+            return self.root_as_leaf.get_all();
+        }
+
+        let mut values = Vec::with_capacity(self.count()?);
+        self.root_as_node().for_each_leaf(&mut |leaf_ref| {
+            let leaf = T::LeafType::from_ref_with_context(
+                Arc::clone(&self.root.node.realm),
+                leaf_ref,
+                self.context,
+            )?;
+            values.extend(leaf.get_all()?);
+
+            Ok(())
+        })?;
+
+        Ok(values)
+    }
+
+    /// Check for every value in the tree whether it is null, walking the
+    /// leaves from left to right.
+    #[instrument(level = "debug")]
+    pub(crate) fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        if self.root_is_leaf() {
+            return self.root_as_leaf.null_bitmap();
+        }
+
+        let mut nulls = Vec::with_capacity(self.count()?);
+        self.root_as_node().for_each_leaf(&mut |leaf_ref| {
+            let leaf = T::LeafType::from_ref_with_context(
+                Arc::clone(&self.root.node.realm),
+                leaf_ref,
+                self.context,
+            )?;
+            nulls.extend(leaf.null_bitmap()?);
+
+            Ok(())
+        })?;
+
+        Ok(nulls)
+    }
+
     #[instrument(level = "debug")]
     pub(crate) fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
         if self.root_is_leaf() {
@@ -86,11 +151,11 @@ impl<T: ColumnType> BpTree<T> {
 
     #[instrument(level = "debug")]
     pub(crate) fn count(&self) -> crate::RealmResult<usize> {
-        Ok(if self.root_is_leaf() {
-            self.root_as_leaf.size()
+        if self.root_is_leaf() {
+            Ok(self.root_as_leaf.size())
         } else {
             self.root_as_node().get_bptree_size()
-        })
+        }
     }
 }
 
@@ -154,10 +219,147 @@ impl<'a> BpTreeNode<'a> {
         }
     }
 
-    pub(crate) fn get_bptree_size(&self) -> usize {
+    /// Call `f` with the ref of every leaf below this node, in order.
+    pub(crate) fn for_each_leaf<F>(&self, f: &mut F) -> crate::RealmResult<()>
+    where
+        F: FnMut(RealmRef) -> crate::RealmResult<()>,
+    {
+        Self::visit_leaves(self.root, f)
+    }
+
+    fn visit_leaves<F>(node: &Array, f: &mut F) -> crate::RealmResult<()>
+    where
+        F: FnMut(RealmRef) -> crate::RealmResult<()>,
+    {
+        // The first element holds either the offsets array or the number of
+        // elements per child, and the last element holds the total size of the
+        // tree. Everything in between is a child ref.
+        for child_index in 1..node.size().saturating_sub(1) {
+            let child_ref =
+                node.get_ref(child_index)?
+                    .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                        reason: format!("B+Tree node is missing child {child_index}"),
+                    })?;
+            let child = Array::from_ref(Arc::clone(&node.node.realm), child_ref)?;
+            if child.node.header.is_inner_bptree() {
+                Self::visit_leaves(&child, f)?;
+            } else {
+                f(child_ref)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn get_bptree_size(&self) -> crate::RealmResult<usize> {
         assert!(self.root.node.header.is_inner_bptree());
-        let v = self.root.back();
+        let v = self.root.back()?;
+
+        Ok((v / 2) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::integer::IntColumnType;
+    use crate::test_utils::{RealmBuilder, tagged};
+
+    fn int_tree(builder: RealmBuilder, root: u64) -> BpTree<IntColumnType> {
+        let (realm, root) = builder.build_with_ref(root);
+
+        BpTree::from_ref_with_context(realm, root, ()).unwrap()
+    }
+
+    #[test]
+    fn test_leaf_root() {
+        let mut builder = RealmBuilder::new();
+        let root = builder.integers(&[1, 2, 3]);
+        let tree = int_tree(builder, root);
+
+        assert_eq!(tree.count().unwrap(), 3);
+        assert_eq!(tree.get(2).unwrap(), 3);
+        assert_eq!(tree.get_all().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_compact_form() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.integers(&[10, 11]);
+        let second = builder.integers(&[12]);
+        let root = builder.inner_node(tagged(2), &[first, second], 3);
+        let tree = int_tree(builder, root);
+
+        assert_eq!(tree.count().unwrap(), 3);
+        assert_eq!(tree.get(1).unwrap(), 11);
+        assert_eq!(tree.get(2).unwrap(), 12);
+        assert!(!tree.is_null(2).unwrap());
+        assert_eq!(tree.get_all().unwrap(), vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn test_general_form() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.integers(&[10]);
+        let second = builder.integers(&[11, 12, 13]);
+        let offsets = builder.integers(&[1]);
+        let root = builder.inner_node(offsets, &[first, second], 4);
+        let tree = int_tree(builder, root);
+
+        assert_eq!(tree.count().unwrap(), 4);
+        assert_eq!(tree.get(0).unwrap(), 10);
+        assert_eq!(tree.get(1).unwrap(), 11);
+        assert_eq!(tree.get(3).unwrap(), 13);
+        assert_eq!(tree.get_all().unwrap(), vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_nested_inner_nodes() {
+        let mut builder = RealmBuilder::new();
+        let leaves = [
+            builder.integers(&[1, 2]),
+            builder.integers(&[3, 4]),
+            builder.integers(&[5, 6]),
+        ];
+        let left = builder.inner_node(tagged(2), &leaves[..2], 4);
+        let right = builder.inner_node(tagged(2), &leaves[2..], 2);
+        let root = builder.inner_node(tagged(4), &[left, right], 6);
+        let tree = int_tree(builder, root);
+
+        assert_eq!(tree.count().unwrap(), 6);
+        assert_eq!(tree.get(3).unwrap(), 4);
+        assert_eq!(tree.get(4).unwrap(), 5);
+        assert_eq!(tree.get_all().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_header_cache() {
+        /// Read every element of a tree with three levels, returning the
+        /// number of node headers parsed.
+        fn scan(header_cache: usize) -> usize {
+            let mut builder = RealmBuilder::new();
+            builder.header_cache(header_cache);
+            let leaves = [
+                builder.integers(&[1, 2]),
+                builder.integers(&[3, 4]),
+                builder.integers(&[5, 6]),
+            ];
+            let left = builder.inner_node(tagged(2), &leaves[..2], 4);
+            let right = builder.inner_node(tagged(2), &leaves[2..], 2);
+            let root = builder.inner_node(tagged(4), &[left, right], 6);
+            let tree = int_tree(builder, root);
+
+            for index in 0..6 {
+                assert_eq!(tree.get(index).unwrap(), index as i64 + 1);
+            }
+
+            tree.realm().header_parses()
+        }
 
-        (v / 2) as usize
+        let uncached = scan(0);
+        let cached = scan(16);
+        // With the cache, each of the 6 nodes is parsed only once.
+        assert_eq!(cached, 6);
+        assert!(uncached > cached, "{uncached} <= {cached}");
     }
 }
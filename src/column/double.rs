@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::array::{RealmRef, ScalarArray};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 
 // Double column type implementation
@@ -13,6 +14,8 @@ impl ColumnType for DoubleColumnType {
     type Value = f64;
     type LeafType = ScalarArray;
     type LeafContext = ();
+
+    const KIND: ColumnKind = ColumnKind::Double;
 }
 
 // Factory function for Double columns
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::array::{RealmRef, ScalarArray};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 
 // Float column type implementation
@@ -13,6 +14,8 @@ impl ColumnType for FloatColumnType {
     type Value = f32;
     type LeafType = ScalarArray;
     type LeafContext = ();
+
+    const KIND: ColumnKind = ColumnKind::Float;
 }
 
 // Factory function for float columns
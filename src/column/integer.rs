@@ -1,6 +1,7 @@
 use crate::array::{IntegerArray, RealmRef};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use std::sync::Arc;
 
@@ -12,6 +13,8 @@ impl ColumnType for IntColumnType {
     type Value = i64;
     type LeafType = IntegerArray;
     type LeafContext = ();
+
+    const KIND: ColumnKind = ColumnKind::Int;
 }
 
 // Factory function for integer columns
@@ -1,6 +1,7 @@
 use crate::array::{IntegerArray, RealmRef};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use std::sync::Arc;
 
@@ -11,6 +12,8 @@ impl ColumnType for IntNullableColumnType {
     type Value = Option<i64>;
     type LeafType = IntegerArray;
     type LeafContext = ();
+
+    const KIND: ColumnKind = ColumnKind::Int;
 }
 
 // Factory function for integer columns
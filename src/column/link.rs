@@ -1,9 +1,10 @@
 use crate::array::{Array, RealmRef};
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
 use crate::value::Link;
 use std::sync::Arc;
 
@@ -18,6 +19,8 @@ impl ColumnType for LinkColumnType {
     type Value = Option<Link>;
     type LeafType = LinkLeaf;
     type LeafContext = LinkColumnContext;
+
+    const KIND: ColumnKind = ColumnKind::Link;
 }
 
 #[derive(Debug)]
@@ -42,7 +45,9 @@ impl NodeWithContext<LinkColumnContext> for LinkLeaf {
 
 impl ArrayLike<Option<Link>, LinkColumnContext> for LinkLeaf {
     fn get(&self, index: usize) -> crate::RealmResult<Option<Link>> {
-        let value = self.root.get(index);
+        check_index(index, self.root.size())?;
+
+        let value = self.root.get(index)?;
         if value == 0 {
             return Ok(None);
         }
@@ -60,6 +65,7 @@ impl ArrayLike<Option<Link>, LinkColumnContext> for LinkLeaf {
         context: LinkColumnContext,
     ) -> crate::RealmResult<Option<Link>> {
         let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
         let payload = realm.payload(ref_, header.payload_len());
 
         match read_array_value(payload, header.width(), index) {
@@ -72,7 +78,9 @@ impl ArrayLike<Option<Link>, LinkColumnContext> for LinkLeaf {
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        Ok(self.root.get(index) == 0)
+        check_index(index, self.root.size())?;
+
+        Ok(self.root.get(index)? == 0)
     }
 
     fn size(&self) -> usize {
@@ -100,3 +108,24 @@ pub(crate) fn create_link_column(
 }
 
 pub(crate) type LinkColumn = ColumnImpl<LinkColumnType>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::single_node;
+
+    #[test]
+    fn test_leaf_out_of_bounds() {
+        let context = LinkColumnContext {
+            target_table_index: 3,
+        };
+        let (realm, ref_) = single_node(0x04, 2, &[0, 5]);
+        let leaf = LinkLeaf::from_ref_with_context(Arc::clone(&realm), ref_, context).unwrap();
+
+        assert_eq!(leaf.get(0).unwrap(), None);
+        assert_eq!(leaf.get(1).unwrap(), Some(Link::new(3, 4)));
+        assert!(leaf.get(2).is_err());
+        assert!(leaf.is_null(2).is_err());
+        assert!(LinkLeaf::get_direct(realm, ref_, 2, context).is_err());
+    }
+}
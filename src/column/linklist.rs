@@ -1,10 +1,13 @@
-use crate::array::{Array, IntegerArray, RealmRef, RefOrTaggedValue};
+use crate::array::{Array, RealmRef, RefOrTaggedValue};
+use crate::column::bptree::BpTree;
+use crate::column::integer::IntColumnType;
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
-use crate::value::Link;
+use crate::utils::{check_index, read_array_value};
+use crate::value::{Link, LinkListRef, Value};
 use std::sync::Arc;
 
 pub(crate) struct LinkListColumnType;
@@ -18,6 +21,26 @@ impl ColumnType for LinkListColumnType {
     type Value = Vec<Link>;
     type LeafType = LinkListLeaf;
     type LeafContext = LinkListColumnContext;
+
+    const KIND: ColumnKind = ColumnKind::LinkList;
+
+    fn get_lazy(tree: &BpTree<Self>, index: usize) -> crate::RealmResult<Value> {
+        let (leaf_ref, index_in_leaf) = tree.find_leaf(index)?;
+        let leaf = Array::from_ref(Arc::clone(tree.realm()), leaf_ref)?;
+        check_index(index_in_leaf, leaf.size())?;
+
+        let links = match leaf.get_ref_or_tagged_value(index_in_leaf)? {
+            Some(RefOrTaggedValue::Ref(ref_)) => {
+                Some(BpTree::from_ref(Arc::clone(tree.realm()), ref_)?)
+            }
+            _ => None,
+        };
+
+        Ok(Value::LinkListLazy(LinkListRef::new(
+            links,
+            tree.context().target_table_index,
+        )?))
+    }
 }
 
 #[derive(Debug)]
@@ -42,14 +65,14 @@ impl NodeWithContext<LinkListColumnContext> for LinkListLeaf {
 
 impl ArrayLike<Vec<Link>, LinkListColumnContext> for LinkListLeaf {
     fn get(&self, index: usize) -> crate::RealmResult<Vec<Link>> {
-        let sub_array = match self.root.get_ref_or_tagged_value(index) {
+        check_index(index, self.root.size())?;
+
+        match self.root.get_ref_or_tagged_value(index)? {
             Some(RefOrTaggedValue::Ref(ref_)) => {
-                Array::from_ref(Arc::clone(&self.root.node.realm), ref_)?
+                Self::get_links(Arc::clone(&self.root.node.realm), ref_, self.context)
             }
-            _ => return Ok(vec![]),
-        };
-
-        Ok(Self::get_from_sub_array(sub_array, self.context))
+            _ => Ok(vec![]),
+        }
     }
 
     fn get_direct(
@@ -59,20 +82,21 @@ impl ArrayLike<Vec<Link>, LinkListColumnContext> for LinkListLeaf {
         context: LinkListColumnContext,
     ) -> crate::RealmResult<Vec<Link>> {
         let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
         let payload = realm.payload(ref_, header.payload_len());
 
-        let sub_array = match read_array_value(payload, header.width(), index) {
-            0 => return Ok(vec![]),
+        match read_array_value(payload, header.width(), index) {
+            0 => Ok(vec![]),
             n => match RefOrTaggedValue::from_raw(n) {
-                RefOrTaggedValue::Ref(ref_) => Array::from_ref(Arc::clone(&realm), ref_)?,
-                _ => return Ok(vec![]),
+                RefOrTaggedValue::Ref(ref_) => Self::get_links(Arc::clone(&realm), ref_, context),
+                _ => Ok(vec![]),
             },
-        };
-
-        Ok(Self::get_from_sub_array(sub_array, context))
+        }
     }
 
-    fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        check_index(index, self.root.size())?;
+
         Ok(false)
     }
 
@@ -82,14 +106,21 @@ impl ArrayLike<Vec<Link>, LinkListColumnContext> for LinkListLeaf {
 }
 
 impl LinkListLeaf {
-    fn get_from_sub_array(sub_array: Array, context: LinkListColumnContext) -> Vec<Link> {
-        assert!(!sub_array.node.header.is_inner_bptree());
+    /// Read the links of a single row, whose row numbers are stored in an
+    /// integer column of their own. Long lists are split over multiple leaves,
+    /// like any other column.
+    fn get_links(
+        realm: Arc<Realm>,
+        ref_: RealmRef,
+        context: LinkListColumnContext,
+    ) -> crate::RealmResult<Vec<Link>> {
+        let row_numbers: BpTree<IntColumnType> = BpTree::from_ref(realm, ref_)?;
 
-        IntegerArray::from_array(sub_array)
-            .get_integers()
+        Ok(row_numbers
+            .get_all()?
             .into_iter()
-            .map(|x| Link::new(context.target_table_index, x as usize))
-            .collect::<Vec<_>>()
+            .map(|row_number| Link::new(context.target_table_index, row_number as usize))
+            .collect())
     }
 }
 
@@ -113,3 +144,95 @@ pub(crate) fn create_linklist_column(
 }
 
 pub(crate) type LinkListColumn = ColumnImpl<LinkListColumnType>;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{LinkListColumnContext, LinkListLeaf};
+    use crate::test_utils::{RealmBuilder, tagged};
+    use crate::traits::{ArrayLike, NodeWithContext};
+    use crate::{Link, Value};
+
+    #[test]
+    fn test_leaf_out_of_bounds() {
+        let context = LinkListColumnContext {
+            target_table_index: 3,
+        };
+        let mut builder = RealmBuilder::new();
+        let links = builder.integers(&[4, 0]);
+        let leaf = builder.refs(&[links, 0]);
+        let (realm, ref_) = builder.build_with_ref(leaf);
+        let leaf = LinkListLeaf::from_ref_with_context(Arc::clone(&realm), ref_, context).unwrap();
+
+        assert_eq!(leaf.get(0).unwrap(), [Link::new(3, 4), Link::new(3, 0)]);
+        assert_eq!(leaf.get(1).unwrap(), []);
+        assert!(leaf.get(2).is_err());
+        assert!(leaf.is_null(2).is_err());
+        assert!(LinkListLeaf::get_direct(realm, ref_, 2, context).is_err());
+    }
+
+    #[test]
+    fn test_inner_nodes() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["a", "b", "c"]);
+        // The first row links to "b", the second has no links, and the links
+        // of the third row are themselves split over two leaves.
+        let first_links = builder.integers(&[1]);
+        let third_links = [builder.integers(&[0, 1]), builder.integers(&[2])];
+        let third_links = builder.inner_node(tagged(2), &third_links, 3);
+        let leaves = [
+            builder.refs(&[first_links, 0]),
+            builder.refs(&[third_links]),
+        ];
+        let links = builder.inner_node(tagged(2), &leaves, 3);
+        // Single links are stored as row number + 1, or 0 for null.
+        let next = [builder.integers(&[2, 0]), builder.integers(&[1])];
+        let next = builder.inner_node(tagged(2), &next, 3);
+        let table = builder.table(
+            &[2, 13, 12],
+            &["name", "links", "next"],
+            &[0, 0, 16],
+            &[tagged(0), tagged(0)],
+            &[names, links, next],
+        );
+        let top = builder.group(&[("class_Node", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(
+            table.column_values(1).unwrap(),
+            [
+                Value::LinkList(vec![Link::new(0, 1)]),
+                Value::LinkList(vec![]),
+                Value::LinkList(vec![Link::new(0, 0), Link::new(0, 1), Link::new(0, 2)]),
+            ]
+        );
+        assert_eq!(
+            table.get_row(2).unwrap().get("links"),
+            Some(&Value::LinkList(vec![
+                Link::new(0, 0),
+                Link::new(0, 1),
+                Link::new(0, 2)
+            ]))
+        );
+
+        assert_eq!(
+            table.column_values(2).unwrap(),
+            [
+                Value::Link(Link::new(0, 1)),
+                Value::None,
+                Value::Link(Link::new(0, 0)),
+            ]
+        );
+
+        let links = table.get_link_list(2, "links").unwrap();
+        assert_eq!(links.len(), 3);
+        assert_eq!(links.get(2), Some(Link::new(0, 2)));
+        assert!(table.get_link_list(1, "links").unwrap().is_empty());
+    }
+}
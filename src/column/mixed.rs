@@ -0,0 +1,342 @@
+//! # Mixed column implementation
+//!
+//! Columns of the (legacy) mixed type hold values of any type. They consist of
+//! an array with the refs of up to four columns:
+//!
+//! 1. The type of each value, see [`MixedColumn::get`].
+//! 2. The data of each value. Integers, booleans, floats, doubles and old
+//!    datetimes are stored inline, as tagged integers, and subtables as the
+//!    ref of the subtable. For strings, binary blobs and timestamps, this is
+//!    the tagged index of the value in one of the following columns.
+//! 3. A binary column holding the strings (with a trailing null byte) and
+//!    blobs. Only present if the column holds any.
+//! 4. A timestamp column. Only present if the column holds any timestamps.
+//!
+//! The tests build this layout by hand, following Realm core 5.x, the last
+//! version writing file format 9.9 with mixed columns. This repository ships
+//! no `.realm` files, and writing one needs that discontinued core and its
+//! bindings, so there is no fixture written by Realm itself.
+
+use chrono::DateTime;
+use std::sync::Arc;
+
+use crate::RealmFileError;
+use crate::array::{Array, LongBlobsArray, RealmRef, SmallBlobsArray};
+use crate::column::Column;
+use crate::column::bptree::{BpTree, BpTreeNode};
+use crate::column::integer::IntColumnType;
+use crate::column::timestamp::TimestampColumn;
+use crate::realm::Realm;
+use crate::spec::ColumnKind;
+use crate::table::{ColumnAttributes, Row, Table};
+use crate::traits::{ArrayLike, Node};
+use crate::utils;
+use crate::value::Value;
+
+#[derive(Debug)]
+pub(crate) struct MixedColumn {
+    types: BpTree<IntColumnType>,
+    data: BpTree<IntColumnType>,
+    blobs: Option<Array>,
+    timestamps: Option<TimestampColumn>,
+    attributes: ColumnAttributes,
+    name: String,
+    /// The nesting depth of the subtables in this column, see
+    /// [`Table::build_from`].
+    depth: usize,
+}
+
+impl MixedColumn {
+    const INT: u64 = 0;
+    const BOOL: u64 = 1;
+    const STRING: u64 = 2;
+    const BINARY: u64 = 4;
+    const TABLE: u64 = 5;
+    const OLD_DATE_TIME: u64 = 7;
+    const TIMESTAMP: u64 = 8;
+    const FLOAT: u64 = 9;
+    /// A double with the sign bit cleared, as it doesn't fit in a tagged
+    /// integer.
+    const DOUBLE: u64 = 10;
+    /// A negative double, stored with its sign bit cleared.
+    const DOUBLE_NEG: u64 = 11;
+    /// A negative integer, stored with its bits inverted.
+    const INT_NEG: u64 = 12;
+
+    pub(crate) fn new(
+        realm: Arc<Realm>,
+        data_ref: RealmRef,
+        attributes: ColumnAttributes,
+        name: String,
+        depth: usize,
+    ) -> crate::RealmResult<Self> {
+        let array = Array::from_ref(Arc::clone(&realm), data_ref)?;
+        let child = |index: usize| {
+            if index < array.size() {
+                array.get_ref(index)
+            } else {
+                Ok(None)
+            }
+        };
+        let missing = |what: &str| RealmFileError::InvalidRealmFile {
+            reason: format!("mixed column {name:?} has no {what} array"),
+        };
+
+        let types = child(0)?.ok_or_else(|| missing("types"))?;
+        let data = child(1)?.ok_or_else(|| missing("data"))?;
+        let blobs = child(2)?
+            .map(|ref_| Array::from_ref(Arc::clone(&realm), ref_))
+            .transpose()?;
+        let timestamps = child(3)?
+            .map(|ref_| {
+                TimestampColumn::new(Arc::clone(&realm), ref_, None, attributes, name.clone())
+            })
+            .transpose()?;
+
+        Ok(Self {
+            types: BpTree::from_ref(Arc::clone(&realm), types)?,
+            data: BpTree::from_ref(realm, data)?,
+            blobs,
+            timestamps,
+            attributes,
+            name,
+            depth,
+        })
+    }
+
+    /// Read the string or binary blob with the given index in the binary
+    /// column.
+    fn blob(&self, index: usize) -> crate::RealmResult<Vec<u8>> {
+        let root = self
+            .blobs
+            .as_ref()
+            .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                reason: format!("mixed column {:?} has no binary array", self.name),
+            })?;
+        let (leaf_ref, index_in_leaf) = if root.node.header.is_inner_bptree() {
+            BpTreeNode::new(root).get_bptree_leaf(index)?
+        } else {
+            (root.node.ref_, index)
+        };
+
+        let realm = Arc::clone(&root.node.realm);
+        let blob: Option<Vec<u8>> = if realm.header(leaf_ref)?.context_flag() {
+            LongBlobsArray::get_direct(realm, leaf_ref, index_in_leaf, ())?
+        } else {
+            SmallBlobsArray::get_direct(realm, leaf_ref, index_in_leaf, ())?
+        };
+
+        // Null blobs are stored as empty blobs in mixed columns.
+        Ok(blob.unwrap_or_default())
+    }
+}
+
+impl Column for MixedColumn {
+    /// Get the value for this column for the row with the given index. The
+    /// type of the value is one of the column types, except for negative
+    /// integers and doubles, which are stored using separate types.
+    fn get(&self, index: usize) -> crate::RealmResult<Value> {
+        let mixed_type = self.types.get(index)? as u64;
+        let data = self.data.get(index)? as u64;
+        // Inline values are tagged, so the lowest bit is always set.
+        let inline = data >> 1;
+
+        Ok(match mixed_type {
+            Self::INT => Value::Int(inline as i64),
+            Self::INT_NEG => Value::Int(!(inline as i64)),
+            Self::BOOL => Value::Bool(inline != 0),
+            Self::FLOAT => Value::Float(f32::from_bits(inline as u32)),
+            Self::DOUBLE => Value::Double(f64::from_bits(inline)),
+            Self::DOUBLE_NEG => Value::Double(f64::from_bits(inline | (1 << 63))),
+            Self::OLD_DATE_TIME => DateTime::from_timestamp(inline as i64, 0)
+                .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                    reason: format!("datetime {} out of range", inline as i64),
+                })?
+                .into(),
+            Self::STRING => {
+                // Strings are stored with a trailing null byte.
+                let bytes = self.blob(inline as usize)?;
+                if bytes.last() != Some(&0) {
+                    return Err(RealmFileError::InvalidRealmFile {
+                        reason: format!(
+                            "string {inline} in mixed column {:?} is not null-terminated",
+                            self.name
+                        ),
+                    });
+                }
+
+                Value::String(utils::string_from_bytes(bytes)?)
+            }
+            Self::BINARY => Value::Binary(self.blob(inline as usize)?),
+            Self::TIMESTAMP => {
+                let timestamps =
+                    self.timestamps
+                        .as_ref()
+                        .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                            reason: format!("mixed column {:?} has no timestamp array", self.name),
+                        })?;
+
+                timestamps.get(inline as usize)?
+            }
+            Self::TABLE => {
+                if data == 0 {
+                    return Ok(Value::None);
+                }
+
+                // Subtables in mixed columns have their own spec, so the ref
+                // points to an array holding the refs of the spec and of the
+                // columns, like the top array of a regular table.
+                let realm = Arc::clone(self.types.realm());
+                let top = Array::from_ref(realm, RealmRef::new(data as usize))?;
+                let missing = |what: &str| RealmFileError::InvalidRealmFile {
+                    reason: format!("subtable in mixed column {:?} has no {what}", self.name),
+                };
+                let header_array: Array = top.get_node(0)?.ok_or_else(|| missing("spec"))?;
+                let data_array: Array = top.get_node(1)?.ok_or_else(|| missing("columns"))?;
+                let rows = Table::build_from(&header_array, data_array, usize::MAX, self.depth)?
+                    .get_rows()?
+                    .into_iter()
+                    .map(Row::into_owned)
+                    .collect();
+
+                Value::Table(rows)
+            }
+            _ => {
+                return Err(RealmFileError::InvalidRealmFile {
+                    reason: format!(
+                        "value {index} in mixed column {:?} has unknown type {mixed_type}",
+                        self.name
+                    ),
+                });
+            }
+        })
+    }
+
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        Ok(self.get(index)?.is_none())
+    }
+
+    fn count(&self) -> crate::RealmResult<usize> {
+        self.types.count()
+    }
+
+    fn nullable(&self) -> bool {
+        self.attributes.is_nullable()
+    }
+
+    fn is_indexed(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    fn column_type(&self) -> ColumnKind {
+        ColumnKind::Mixed
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
+}
+
+// Factory function for (legacy) mixed columns
+pub(crate) fn create_old_mixed_column(
+    realm: Arc<Realm>,
+    data_ref: RealmRef,
+    attributes: ColumnAttributes,
+    name: String,
+    depth: usize,
+) -> crate::RealmResult<Box<dyn Column>> {
+    Ok(Box::new(MixedColumn::new(
+        realm, data_ref, attributes, name, depth,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::RealmFileError;
+    use crate::test_utils::{RealmBuilder, tagged};
+    use crate::value::Value;
+
+    #[test]
+    fn test_mixed_values() {
+        let mut builder = RealmBuilder::new();
+        let types = builder.integers(&[0, 12, 1, 9, 10, 11, 2, 4, 8, 7, 5, 5]);
+
+        // The string (with its trailing null byte) and the binary blob.
+        let ends = builder.integers(&[6, 9]);
+        let blob = builder.blob(b"hello\0\x01\x02\x03");
+        let blobs = builder.refs(&[ends, blob]);
+
+        // Nullable seconds, with u64::MAX as the null value.
+        let seconds = builder.integers(&[u64::MAX, 1_700_000_000]);
+        let nanoseconds = builder.integers(&[5]);
+        let timestamps = builder.refs(&[seconds, nanoseconds]);
+
+        let subtable_values = builder.integers(&[1, 2]);
+        let subtable = builder.table(&[0], &["value"], &[0], &[], &[subtable_values]);
+
+        let data = builder.refs(&[
+            tagged(5),
+            tagged(!(-3i64) as u64),
+            tagged(1),
+            tagged(1.5f32.to_bits() as u64),
+            tagged(2.5f64.to_bits()),
+            tagged((-2.5f64).to_bits() & !(1 << 63)),
+            tagged(0),
+            tagged(1),
+            tagged(0),
+            tagged(86_400),
+            subtable,
+            0,
+        ]);
+        let mixed = builder.refs(&[types, data, blobs, timestamps]);
+        let table = builder.table(&[6], &["value"], &[0], &[], &[mixed]);
+        let top = builder.group(&[("class_Legacy", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let values = table.column_values(0).unwrap();
+        let Value::Table(rows) = &values[10] else {
+            panic!("expected a subtable, found {:?}", values[10]);
+        };
+        let rows = rows
+            .iter()
+            .map(|row| row.get("value").cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [Some(Value::Int(1)), Some(Value::Int(2))]);
+
+        assert_eq!(
+            values[..10],
+            [
+                Value::Int(5),
+                Value::Int(-3),
+                Value::Bool(true),
+                Value::Float(1.5),
+                Value::Double(2.5),
+                Value::Double(-2.5),
+                Value::String("hello".into()),
+                Value::Binary(vec![1, 2, 3]),
+                DateTime::from_timestamp(1_700_000_000, 5).into(),
+                DateTime::from_timestamp(86_400, 0).into(),
+            ]
+        );
+        assert_eq!(values[11], Value::None);
+
+        let column = table.get_column_spec(0).unwrap();
+        assert_eq!(column.get_str_ref(6).unwrap().as_deref(), Some("hello"));
+        assert_eq!(column.get_str_ref(11).unwrap(), None);
+        assert!(matches!(
+            column.get_str_ref(0),
+            Err(RealmFileError::Unsupported { .. })
+        ));
+    }
+}
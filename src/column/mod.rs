@@ -1,21 +1,28 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 
+use crate::RealmFileError;
 use crate::array::RealmRef;
 pub(crate) use crate::column::backlink::create_backlink_column;
 pub(crate) use crate::column::bool::create_bool_column;
 pub(crate) use crate::column::bool_optional::create_bool_null_column;
-use crate::column::bptree::BpTree;
+pub(crate) use crate::column::bptree::BpTree;
 pub(crate) use crate::column::double::create_double_column;
 pub(crate) use crate::column::float::create_float_column;
-pub(crate) use crate::column::integer::create_int_column;
+pub(crate) use crate::column::integer::{IntColumnType, create_int_column};
 pub(crate) use crate::column::integer_optional::create_int_null_column;
 pub(crate) use crate::column::link::create_link_column;
 pub(crate) use crate::column::linklist::create_linklist_column;
+pub(crate) use crate::column::mixed::create_old_mixed_column;
 pub(crate) use crate::column::string::create_string_column;
+pub(crate) use crate::column::string_enum::create_string_enum_column;
 pub(crate) use crate::column::subtable::create_subtable_column;
 pub(crate) use crate::column::timestamp::create_timestamp_column;
-use crate::index::Index;
+pub(crate) use crate::column::unsupported::create_unsupported_column;
+use crate::error::TableError;
+use crate::index::{Index, IndexEntry};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::{ArrayLike, Node, NodeWithContext};
 use crate::value::Value;
@@ -31,19 +38,70 @@ mod integer;
 mod integer_optional;
 mod link;
 mod linklist;
+mod mixed;
 mod string;
+mod string_enum;
 mod subtable;
 mod timestamp;
+mod unsupported;
 
-/// A column for a table.
-pub trait Column: Debug + Send {
+/// A column for a table. Columns only read from the (immutable) Realm file, so
+/// they can be shared across threads.
+pub trait Column: Debug + Send + Sync {
     /// Get the value for this column for the row with the given index.
     fn get(&self, index: usize) -> crate::RealmResult<Value>;
 
+    /// Get the value for this column for the row with the given index, like
+    /// [`get`](Self::get), but without reading large values in their entirety.
+    /// Currently, this only affects link list columns, which return a
+    /// [`Value::LinkListLazy`] instead of a [`Value::LinkList`], and subtable
+    /// columns, which return a [`Value::TableLazy`] instead of a
+    /// [`Value::Table`].
+    fn get_lazy(&self, index: usize) -> crate::RealmResult<Value> {
+        self.get(index)
+    }
+
+    /// Get the string for this column for the row with the given index,
+    /// without copying it where possible. For string columns, short strings
+    /// are borrowed from the file (for example, the memory map), which is kept
+    /// alive by the [`Realm`](crate::Realm) the column belongs to; the result
+    /// can't outlive this column. Longer strings, and strings in other kinds
+    /// of columns, are read as usual and returned as owned strings.
+    ///
+    /// Returns `None` for null values, and [`RealmFileError::Unsupported`] if
+    /// the value is not a string.
+    fn get_str_ref(&self, index: usize) -> crate::RealmResult<Option<Cow<'_, str>>> {
+        match self.get(index)? {
+            Value::String(value) => Ok(Some(Cow::Owned(value))),
+            Value::None => Ok(None),
+            value => Err(RealmFileError::Unsupported {
+                reason: format!(
+                    "value {index} of column {:?} is not a string: {value:?}",
+                    self.name()
+                ),
+            }),
+        }
+    }
+
+    /// Get the values for all rows in this column, in row order.
+    fn get_all(&self) -> crate::RealmResult<Vec<Value>> {
+        (0..self.count()?).map(|index| self.get(index)).collect()
+    }
+
     /// Check whether the value at the given index is null. Note that some
     /// column types are never null, see [`Value`] for details.
     fn is_null(&self, index: usize) -> crate::RealmResult<bool>;
 
+    /// Check for every row whether the value in this column is null, in row
+    /// order. This is faster than calling [`is_null`](Self::is_null) for
+    /// every row, and for most column types, the values themselves are not
+    /// decoded.
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        (0..self.count()?)
+            .map(|index| self.is_null(index))
+            .collect()
+    }
+
     /// Get the total number of values in this column.
     fn count(&self) -> crate::RealmResult<usize>;
 
@@ -58,11 +116,108 @@ pub trait Column: Debug + Send {
 
     /// Look up a value for this column in the index.
     ///
-    /// Panics if this column is not indexed.
-    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::RealmResult<Option<usize>>;
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn get_row_number_by_index(&self, _lookup_value: &Value) -> crate::TableResult<Option<usize>> {
+        Err(not_indexed(self.name()))
+    }
+
+    /// Look up all rows holding a value for this column in the index, in
+    /// ascending order.
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn get_row_numbers_by_index(&self, lookup_value: &Value) -> crate::TableResult<Vec<usize>> {
+        Ok(self
+            .get_row_number_by_index(lookup_value)?
+            .into_iter()
+            .collect())
+    }
+
+    /// Check whether any row holds the given value, using the index.
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn index_contains(&self, lookup_value: &Value) -> crate::TableResult<bool> {
+        Ok(self.get_row_number_by_index(lookup_value)?.is_some())
+    }
+
+    /// Look up a row for every distinct value of this column in the index.
+    /// The rows are in the order of the index, which orders values by their
+    /// binary representation; for example, integers are ordered by their
+    /// least significant byte first. Null values are included.
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn get_distinct_row_numbers_by_index(&self) -> crate::TableResult<Vec<usize>> {
+        Err(not_indexed(self.name()))
+    }
+
+    /// Get every entry in the index of this column, in the order of their
+    /// keys. This is intended for debugging, see [`IndexEntry`].
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn index_entries(&self) -> crate::TableResult<Vec<IndexEntry>> {
+        Err(not_indexed(self.name()))
+    }
+
+    /// Get the smallest non-null value in this column, using the index, or
+    /// `None` if the column has no non-null values. See the [`Ord`]
+    /// implementation of [`Value`] for how values are ordered.
+    ///
+    /// As the index does not order values by their value (see
+    /// [`get_distinct_row_numbers_by_index`](Self::get_distinct_row_numbers_by_index)),
+    /// this reads every distinct value once, rather than every row.
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn index_min(&self) -> crate::TableResult<Option<Value>> {
+        Ok(index_values(self)?.into_iter().min())
+    }
+
+    /// Get the largest non-null value in this column, using the index, or
+    /// `None` if the column has no non-null values. See
+    /// [`index_min`](Self::index_min) for details.
+    ///
+    /// Returns [`TableError::ColumnNotIndexed`] if this column is not indexed.
+    fn index_max(&self) -> crate::TableResult<Option<Value>> {
+        Ok(index_values(self)?.into_iter().max())
+    }
 
     /// Get the name of this column. All columns except backlinks are named.
     fn name(&self) -> Option<&str>;
+
+    /// Get the kind of values stored in this column.
+    fn column_type(&self) -> ColumnKind;
+
+    /// Get the attributes of this column, such as whether it's nullable or
+    /// indexed.
+    fn attributes(&self) -> ColumnAttributes;
+}
+
+/// Read the distinct non-null values of an indexed column, in the order of its
+/// index.
+fn index_values<C: Column + ?Sized>(column: &C) -> crate::TableResult<Vec<Value>> {
+    let mut values = Vec::new();
+    for row_number in column.get_distinct_row_numbers_by_index()? {
+        let value = column.get(row_number)?;
+        if !value.is_none() {
+            values.push(value);
+        }
+    }
+
+    Ok(values)
+}
+
+/// The error for using the index of a column that is not indexed.
+fn not_indexed(name: Option<&str>) -> TableError {
+    TableError::ColumnNotIndexed {
+        name: name.unwrap_or_default().to_string(),
+    }
+}
+
+/// Get the index of a column, or [`TableError::ColumnNotIndexed`] if the
+/// column has none.
+pub(crate) fn require_index<'a>(
+    index: Option<&'a Index>,
+    name: Option<&str>,
+) -> crate::TableResult<&'a Index> {
+    index.ok_or_else(|| not_indexed(name))
 }
 
 /// The definition of a column type, which includes the value type, leaf type, and B+Tree type.
@@ -70,6 +225,18 @@ pub(crate) trait ColumnType {
     type Value: Into<Value>;
     type LeafContext: Copy + Debug;
     type LeafType: ArrayLike<Self::Value, Self::LeafContext>;
+
+    /// The kind of column, as exposed through [`Column::column_type`].
+    const KIND: ColumnKind;
+
+    /// Get the value at the given index without reading it in its entirety,
+    /// see [`Column::get_lazy`]. Defaults to reading the value as usual.
+    fn get_lazy(tree: &BpTree<Self>, index: usize) -> crate::RealmResult<Value>
+    where
+        Self: Sized,
+    {
+        Ok(tree.get(index)?.into())
+    }
 }
 
 struct ColumnImpl<T: ColumnType> {
@@ -89,20 +256,32 @@ impl<T: ColumnType> Debug for ColumnImpl<T> {
     }
 }
 
-impl<T: ColumnType + Send> Column for ColumnImpl<T>
+impl<T: ColumnType + Send + Sync> Column for ColumnImpl<T>
 where
     Value: From<T::Value>,
-    <T as ColumnType>::LeafContext: Send,
-    <T as ColumnType>::LeafType: Send,
+    <T as ColumnType>::LeafContext: Send + Sync,
+    <T as ColumnType>::LeafType: Send + Sync,
 {
     fn get(&self, index: usize) -> crate::RealmResult<Value> {
         Ok(Value::from(self.tree.get(index)?))
     }
 
+    fn get_lazy(&self, index: usize) -> crate::RealmResult<Value> {
+        T::get_lazy(&self.tree, index)
+    }
+
+    fn get_all(&self) -> crate::RealmResult<Vec<Value>> {
+        Ok(self.tree.get_all()?.into_iter().map(Value::from).collect())
+    }
+
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
         self.tree.is_null(index)
     }
 
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        self.tree.null_bitmap()
+    }
+
     fn count(&self) -> crate::RealmResult<usize> {
         self.tree.count()
     }
@@ -115,17 +294,37 @@ where
         self.attributes.is_indexed()
     }
 
-    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::RealmResult<Option<usize>> {
-        let Some(index) = &self.index else {
-            panic!("Column {:?} is not indexed", self.name());
-        };
+    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::TableResult<Option<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_first(lookup_value)?)
+    }
+
+    fn get_row_numbers_by_index(&self, lookup_value: &Value) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_all(lookup_value)?)
+    }
 
-        index.find_first(lookup_value)
+    fn index_contains(&self, lookup_value: &Value) -> crate::TableResult<bool> {
+        Ok(require_index(self.index.as_ref(), self.name())?.contains(lookup_value)?)
+    }
+
+    fn get_distinct_row_numbers_by_index(&self) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.distinct_row_numbers()?)
+    }
+
+    fn index_entries(&self) -> crate::TableResult<Vec<IndexEntry>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.entries()?)
     }
 
     fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
+
+    fn column_type(&self) -> ColumnKind {
+        T::KIND
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
 }
 
 impl<T: ColumnType> ColumnImpl<T> {
@@ -1,11 +1,13 @@
-use crate::array::{Array, ArrayString, LongBlobsArray, RealmRef, SmallBlobsArray};
-use crate::column::Column;
+use crate::array::{Array, ArrayString, ArrayStringShort, RealmRef};
 use crate::column::bptree::BpTreeNode;
-use crate::index::Index;
+use crate::column::{Column, require_index};
+use crate::index::{Index, IndexEntry};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::{ArrayLike, Node};
 use crate::value::Value;
+use std::borrow::Cow;
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
@@ -41,83 +43,89 @@ impl StringColumn {
 impl Column for StringColumn {
     /// Get the value for this column for the row with the given index.
     fn get(&self, index: usize) -> crate::RealmResult<Value> {
-        if self.root_is_leaf() {
-            return Ok(if self.nullable() {
-                ArrayString::<Option<String>>::get_inner(
-                    &self.root.node.header,
-                    Arc::clone(&self.root.node.realm),
-                    self.root.node.ref_,
-                )?
-                .get(index)?
-                .into()
-            } else {
-                ArrayString::<String>::get_inner(
-                    &self.root.node.header,
-                    Arc::clone(&self.root.node.realm),
-                    self.root.node.ref_,
-                )?
-                .get(index)?
-                .into()
-            });
-        }
+        let (leaf_ref, index_in_leaf) = self.find_leaf(index)?;
+        let value = self.leaf(leaf_ref)?.get(index_in_leaf)?;
 
-        // Non-leaf root
-        let (leaf_ref, index_in_leaf) = BpTreeNode::new(&self.root).get_bptree_leaf(index)?;
-        let leaf_header = self.root.node.realm.header(leaf_ref)?;
+        Ok(self.to_value(value))
+    }
+
+    /// Get the string for the row with the given index, borrowing it from the
+    /// file if it is stored in a leaf of short strings.
+    fn get_str_ref(&self, index: usize) -> crate::RealmResult<Option<Cow<'_, str>>> {
+        let (leaf_ref, index_in_leaf) = self.find_leaf(index)?;
+        let realm = &*self.root.node.realm;
+        let header = realm.header(leaf_ref)?;
+
+        // Leaves of longer strings hold refs to blobs. Their strings are
+        // null-terminated, so they are copied to drop the terminator.
+        let value = if header.has_refs() && header.size > 0 {
+            self.leaf(leaf_ref)?.get(index_in_leaf)?.map(Cow::Owned)
+        } else {
+            ArrayStringShort::get_borrowed(realm, leaf_ref, index_in_leaf)?
+        };
 
         Ok(if self.nullable() {
-            ArrayString::<Option<String>>::get_inner(
-                &leaf_header,
-                Arc::clone(&self.root.node.realm),
-                leaf_ref,
-            )?
-            .get(index_in_leaf)?
-            .into()
+            value
         } else {
-            ArrayString::<String>::get_inner(
-                &leaf_header,
-                Arc::clone(&self.root.node.realm),
-                leaf_ref,
-            )?
-            .get(index_in_leaf)?
-            .into()
+            Some(value.unwrap_or_default())
         })
     }
 
+    /// Get all values in this column, walking the leaves of the B+Tree from
+    /// left to right.
+    fn get_all(&self) -> crate::RealmResult<Vec<Value>> {
+        let mut values = Vec::with_capacity(self.count()?);
+        let mut read_leaf = |leaf_ref| {
+            let leaf = self.leaf(leaf_ref)?;
+            values.extend(leaf.get_all()?.into_iter().map(|v| self.to_value(v)));
+
+            Ok(())
+        };
+
+        if self.root_is_leaf() {
+            read_leaf(self.root.node.ref_)?;
+        } else {
+            BpTreeNode::new(&self.root).for_each_leaf(&mut read_leaf)?;
+        }
+
+        Ok(values)
+    }
+
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
         Ok(self.nullable() && self.get(index)?.is_none())
     }
 
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        if !self.nullable() {
+            return Ok(vec![false; self.count()?]);
+        }
+
+        let mut nulls = Vec::with_capacity(self.count()?);
+        let mut read_leaf = |leaf_ref| {
+            nulls.extend(self.leaf(leaf_ref)?.null_bitmap()?);
+
+            Ok(())
+        };
+
+        if self.root_is_leaf() {
+            read_leaf(self.root.node.ref_)?;
+        } else {
+            BpTreeNode::new(&self.root).for_each_leaf(&mut read_leaf)?;
+        }
+
+        Ok(nulls)
+    }
+
     /// Get the total number of values in this column.
     fn count(&self) -> crate::RealmResult<usize> {
         if self.root_is_leaf() {
-            let long_strings = self.root.node.header.has_refs();
-            if !long_strings {
-                return Ok(self.root.node.header.size as usize);
-            }
-
-            let is_big = self.root.node.header.context_flag();
-            if !is_big {
-                // Small strings
-                let small_blobs_array = SmallBlobsArray::from_ref(
-                    Arc::clone(&self.root.node.realm),
-                    self.root.node.ref_,
-                )?;
-                return Ok(<SmallBlobsArray as ArrayLike<String>>::size(
-                    &small_blobs_array,
-                ));
-            }
-
-            // Long strings
-            let long_blobs_array =
-                LongBlobsArray::from_ref(Arc::clone(&self.root.node.realm), self.root.node.ref_)?;
-            return Ok(<LongBlobsArray as ArrayLike<String>>::size(
-                &long_blobs_array,
-            ));
+            // Leaves holding long strings are arrays of refs, whose own size
+            // is not the number of strings, so defer to the leaf type.
+            return Ok(self.leaf(self.root.node.ref_)?.size());
         }
 
         // Non-leaf root
-        Ok(BpTreeNode::new(&self.root).get_bptree_size())
+        BpTreeNode::new(&self.root).get_bptree_size()
     }
 
     /// Get whether this column is nullable.
@@ -129,23 +137,73 @@ impl Column for StringColumn {
         self.attributes.is_indexed()
     }
 
-    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::RealmResult<Option<usize>> {
-        let Some(index) = &self.index else {
-            panic!("Column {:?} is not indexed", self.name());
-        };
+    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::TableResult<Option<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_first(lookup_value)?)
+    }
+
+    fn get_row_numbers_by_index(&self, lookup_value: &Value) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_all(lookup_value)?)
+    }
+
+    fn index_contains(&self, lookup_value: &Value) -> crate::TableResult<bool> {
+        Ok(require_index(self.index.as_ref(), self.name())?.contains(lookup_value)?)
+    }
+
+    fn get_distinct_row_numbers_by_index(&self) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.distinct_row_numbers()?)
+    }
 
-        index.find_first(lookup_value)
+    fn index_entries(&self) -> crate::TableResult<Vec<IndexEntry>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.entries()?)
     }
 
     fn name(&self) -> Option<&str> {
         Some(&self.name)
     }
+
+    fn column_type(&self) -> ColumnKind {
+        ColumnKind::String
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
 }
 
 impl StringColumn {
     fn root_is_leaf(&self) -> bool {
         !self.root.node.header.is_inner_bptree()
     }
+
+    /// Find the leaf holding the string with the given index, and the index
+    /// of the string within that leaf.
+    fn find_leaf(&self, index: usize) -> crate::RealmResult<(RealmRef, usize)> {
+        if self.root_is_leaf() {
+            Ok((self.root.node.ref_, index))
+        } else {
+            BpTreeNode::new(&self.root).get_bptree_leaf(index)
+        }
+    }
+
+    /// Load the leaf at `leaf_ref`, which may use any of the string array
+    /// layouts, depending on the length of the longest string in the leaf.
+    fn leaf(&self, leaf_ref: RealmRef) -> crate::RealmResult<Box<dyn ArrayLike<Option<String>>>> {
+        let header = self.root.node.realm.header(leaf_ref)?;
+
+        ArrayString::<Option<String>>::get_inner(
+            &header,
+            Arc::clone(&self.root.node.realm),
+            leaf_ref,
+        )
+    }
+
+    fn to_value(&self, value: Option<String>) -> Value {
+        if self.nullable() {
+            value.into()
+        } else {
+            value.unwrap_or_default().into()
+        }
+    }
 }
 
 // Factory function for string columns
@@ -160,3 +218,144 @@ pub(crate) fn create_string_column(
         realm, data_ref, index_ref, attributes, name,
     )?))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::array::FromU64;
+    use crate::error::TableError;
+    use crate::test_utils::{RealmBuilder, tagged};
+
+    fn string_column(builder: RealmBuilder, root: u64) -> StringColumn {
+        let (realm, root) = builder.build_with_ref(root);
+
+        StringColumn::new(
+            realm,
+            root,
+            None,
+            ColumnAttributes::from_u64(0),
+            "name".to_string(),
+        )
+        .unwrap()
+    }
+
+    const LONG: &str = "a string that is too long for a short string array";
+
+    #[test]
+    fn test_general_form_with_blob_leaf() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.short_strings(&["a", "bb"]);
+        let second = builder.blob_strings(&[LONG, "c", LONG]);
+        let offsets = builder.integers(&[2]);
+        let root = builder.inner_node(offsets, &[first, second], 5);
+        let column = string_column(builder, root);
+
+        let expected: Vec<Value> = vec![
+            "a".into(),
+            "bb".into(),
+            LONG.into(),
+            "c".into(),
+            LONG.into(),
+        ];
+        assert_eq!(column.count().unwrap(), 5);
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(&column.get(i).unwrap(), value);
+        }
+        assert_eq!(column.get_all().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_compact_form() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.blob_strings(&[LONG, "a"]);
+        let second = builder.short_strings(&["b", "c"]);
+        let third = builder.short_strings(&["d"]);
+        let root = builder.inner_node(tagged(2), &[first, second, third], 5);
+        let column = string_column(builder, root);
+
+        let expected: Vec<Value> =
+            vec![LONG.into(), "a".into(), "b".into(), "c".into(), "d".into()];
+        assert_eq!(column.count().unwrap(), 5);
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(&column.get(i).unwrap(), value);
+        }
+        assert_eq!(column.get_all().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_blob_leaf_root() {
+        let mut builder = RealmBuilder::new();
+        let root = builder.blob_strings(&[LONG, "a", "b"]);
+        let column = string_column(builder, root);
+
+        assert_eq!(column.column_type(), ColumnKind::String);
+        assert_eq!(column.count().unwrap(), 3);
+        assert_eq!(column.get(2).unwrap(), "b".into());
+    }
+
+    #[test]
+    fn test_get_str_ref() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.short_strings(&["a", ""]);
+        let second = builder.blob_strings(&[LONG]);
+        let root = builder.inner_node(tagged(2), &[first, second], 3);
+        let column = string_column(builder, root);
+
+        assert!(matches!(
+            column.get_str_ref(0).unwrap(),
+            Some(Cow::Borrowed("a"))
+        ));
+        assert!(matches!(
+            column.get_str_ref(1).unwrap(),
+            Some(Cow::Borrowed(""))
+        ));
+        assert_eq!(column.get_str_ref(2).unwrap().as_deref(), Some(LONG));
+        assert!(column.get_str_ref(3).is_err());
+
+        let mut builder = RealmBuilder::new();
+        let root = builder.nullable_short_strings(&[Some("a"), None]);
+        let (realm, root) = builder.build_with_ref(root);
+        let column = StringColumn::new(
+            realm,
+            root,
+            None,
+            ColumnAttributes::from_u64(16),
+            "name".to_string(),
+        )
+        .unwrap();
+        assert_eq!(column.get_str_ref(0).unwrap().as_deref(), Some("a"));
+        assert_eq!(column.get_str_ref(1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut builder = RealmBuilder::new();
+        // A truncated multi-byte character, and a lone continuation byte.
+        let first = builder.blob_strings(&[b"caf\xC3".as_slice(), b"ok"]);
+        let second = builder.short_strings(&[b"\x80ab".as_slice()]);
+        let root = builder.inner_node(tagged(2), &[first, second], 3);
+        let column = string_column(builder, root);
+
+        let expected: Vec<Value> = vec!["caf\u{FFFD}".into(), "ok".into(), "\u{FFFD}ab".into()];
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(&column.get(i).unwrap(), value);
+        }
+        assert_eq!(column.get_all().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_not_indexed() {
+        let mut builder = RealmBuilder::new();
+        let root = builder.short_strings(&["a"]);
+        let column = string_column(builder, root);
+        let not_indexed = |result: crate::TableResult<_>| matches!(result, Err(TableError::ColumnNotIndexed { name }) if name == "name");
+
+        assert!(not_indexed(
+            column.get_row_number_by_index(&"a".into()).map(drop)
+        ));
+        assert!(not_indexed(column.index_contains(&"a".into()).map(drop)));
+        assert!(not_indexed(column.index_entries().map(drop)));
+        assert!(not_indexed(column.index_min().map(drop)));
+        assert!(not_indexed(column.index_max().map(drop)));
+    }
+}
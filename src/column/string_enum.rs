@@ -0,0 +1,221 @@
+//! # Enumerated string column implementation
+//!
+//! Realm may store string columns with few distinct values as enumerations:
+//! the distinct strings (the keys) are stored once, in a string column whose
+//! ref is held by the spec of the table, and the column data is an integer
+//! column holding, for every row, the index of its string in the keys.
+//!
+//! These columns hold regular strings as far as users are concerned, so they
+//! are read as [`Value::String`]s.
+//!
+//! In file format 9.9, this is the only layout of string columns using keys:
+//! such columns have their own column type (`ColumnType::OldStringEnum`), and
+//! their keys are always referenced from the spec. Columns of the regular
+//! string type never refer to keys; when their root node has refs, it is
+//! either an inner B+Tree node or a leaf of long strings.
+
+use std::sync::Arc;
+
+use crate::RealmFileError;
+use crate::array::RealmRef;
+use crate::column::bptree::BpTree;
+use crate::column::integer::IntColumnType;
+use crate::column::string::StringColumn;
+use crate::column::{Column, require_index};
+use crate::index::{Index, IndexEntry};
+use crate::realm::Realm;
+use crate::spec::ColumnKind;
+use crate::table::ColumnAttributes;
+use crate::traits::Node;
+use crate::value::Value;
+
+#[derive(Debug)]
+pub(crate) struct StringEnumColumn {
+    /// The distinct strings in this column.
+    keys: StringColumn,
+    /// The index of the string in [`keys`](Self::keys) for every row.
+    key_indices: BpTree<IntColumnType>,
+    index: Option<Index>,
+    attributes: ColumnAttributes,
+    name: String,
+}
+
+impl StringEnumColumn {
+    pub(crate) fn new(
+        realm: Arc<Realm>,
+        data_ref: RealmRef,
+        keys_ref: RealmRef,
+        index_ref: Option<RealmRef>,
+        attributes: ColumnAttributes,
+        name: String,
+    ) -> crate::RealmResult<Self> {
+        let keys = StringColumn::new(Arc::clone(&realm), keys_ref, None, attributes, name.clone())?;
+        let key_indices = BpTree::from_ref(Arc::clone(&realm), data_ref)?;
+        let index = index_ref
+            .map(|ref_| Index::from_ref(realm, ref_))
+            .transpose()?;
+
+        Ok(Self {
+            keys,
+            key_indices,
+            index,
+            attributes,
+            name,
+        })
+    }
+
+    /// Look up the string with the given index in the keys.
+    fn key(&self, key_index: i64) -> crate::RealmResult<Value> {
+        let key_count = self.keys.count()?;
+        match usize::try_from(key_index) {
+            Ok(key_index) if key_index < key_count => self.keys.get(key_index),
+            _ => Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "enumerated string column {:?} refers to key {key_index}, but has {key_count} keys",
+                    self.name
+                ),
+            }),
+        }
+    }
+}
+
+impl Column for StringEnumColumn {
+    fn get(&self, index: usize) -> crate::RealmResult<Value> {
+        self.key(self.key_indices.get(index)?)
+    }
+
+    /// Get all values in this column, reading every key only once.
+    fn get_all(&self) -> crate::RealmResult<Vec<Value>> {
+        let keys = self.keys.get_all()?;
+
+        self.key_indices
+            .get_all()?
+            .into_iter()
+            .map(|key_index| match usize::try_from(key_index) {
+                Ok(key_index) if key_index < keys.len() => Ok(keys[key_index].clone()),
+                _ => self.key(key_index),
+            })
+            .collect()
+    }
+
+    fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
+        Ok(self.nullable() && self.get(index)?.is_none())
+    }
+
+    fn count(&self) -> crate::RealmResult<usize> {
+        self.key_indices.count()
+    }
+
+    fn nullable(&self) -> bool {
+        self.attributes.is_nullable()
+    }
+
+    fn is_indexed(&self) -> bool {
+        self.attributes.is_indexed()
+    }
+
+    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::TableResult<Option<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_first(lookup_value)?)
+    }
+
+    fn get_row_numbers_by_index(&self, lookup_value: &Value) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_all(lookup_value)?)
+    }
+
+    fn index_contains(&self, lookup_value: &Value) -> crate::TableResult<bool> {
+        Ok(require_index(self.index.as_ref(), self.name())?.contains(lookup_value)?)
+    }
+
+    fn get_distinct_row_numbers_by_index(&self) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.distinct_row_numbers()?)
+    }
+
+    fn index_entries(&self) -> crate::TableResult<Vec<IndexEntry>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.entries()?)
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    fn column_type(&self) -> ColumnKind {
+        ColumnKind::String
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
+}
+
+// Factory function for enumerated string columns
+pub(crate) fn create_string_enum_column(
+    realm: Arc<Realm>,
+    data_ref: RealmRef,
+    keys_ref: RealmRef,
+    index_ref: Option<RealmRef>,
+    attributes: ColumnAttributes,
+    name: String,
+) -> crate::RealmResult<Box<dyn Column>> {
+    Ok(Box::new(StringEnumColumn::new(
+        realm, data_ref, keys_ref, index_ref, attributes, name,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{RealmBuilder, tagged};
+    use crate::value::Value;
+    use crate::{ColumnKind, Group};
+
+    /// A group with a single table, with an indexed enumerated string column
+    /// `color`, followed by a nullable one, `shade`, and an integer column.
+    fn colors() -> Group {
+        let mut builder = RealmBuilder::new();
+        let types = builder.integers(&[3, 3, 0]);
+        let names = builder.short_strings(&["color", "shade", "id"]);
+        let attributes = builder.integers(&[1, 16, 0]);
+        let color_keys = builder.short_strings(&["blue", "red"]);
+        let shade_keys = builder.nullable_short_strings(&[None, Some("dark")]);
+        let enum_keys = builder.refs(&[color_keys, shade_keys]);
+        // Tables without subtable or link columns have no sub-spec.
+        let spec = builder.refs(&[types, names, attributes, 0, enum_keys]);
+
+        let colors = builder.integers(&[1, 0, 1]);
+        let red_rows = builder.integers(&[0, 2]);
+        let color_index = builder.index_leaf(&[(b"blue", tagged(1)), (b"red\0", red_rows)]);
+        let shades = builder.integers(&[0, 1, 1]);
+        let ids = builder.integers(&[7, 8, 9]);
+        let data = builder.refs(&[colors, color_index, shades, ids]);
+        let table = builder.refs(&[spec, data]);
+        let top = builder.group(&[("class_Paint", table)]);
+
+        builder.build(top).into_group().unwrap()
+    }
+
+    #[test]
+    fn test_string_enum_column() {
+        let table = colors().get_table(0).unwrap();
+
+        assert_eq!(table.schema().columns[0].kind, ColumnKind::String);
+        assert_eq!(table.row_count().unwrap(), 3);
+        assert_eq!(
+            table.column_values(0).unwrap(),
+            [Value::from("red"), "blue".into(), "red".into()]
+        );
+        assert_eq!(
+            table.column_values(1).unwrap(),
+            [Value::None, "dark".into(), "dark".into()]
+        );
+        let row = table.get_row(2).unwrap();
+        assert_eq!(row.get("color"), Some(&Value::from("red")));
+        assert_eq!(row.get("shade"), Some(&Value::from("dark")));
+        assert_eq!(row.get("id"), Some(&Value::Int(9)));
+
+        assert_eq!(
+            table
+                .find_row_number_from_indexed_column("color", &"blue".into())
+                .unwrap(),
+            Some(1)
+        );
+    }
+}
@@ -16,16 +16,22 @@
 use tracing::instrument;
 
 use crate::array::{Array, RealmRef, RefOrTaggedValue};
+use crate::column::bptree::BpTree;
 use crate::column::{Column, ColumnImpl, ColumnType};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::{ColumnAttributes, Row, Table};
 use crate::traits::{ArrayLike, Node, NodeWithContext};
-use crate::utils::read_array_value;
+use crate::utils::{check_index, read_array_value};
+use crate::value::{SubtableRef, Value};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct SubtableContext {
     header_ref: RealmRef,
+    /// The nesting depth of the subtables in this column, see
+    /// [`Table::build_from`].
+    depth: usize,
 }
 
 pub(crate) struct SubtableColumnType;
@@ -33,12 +39,31 @@ impl ColumnType for SubtableColumnType {
     type Value = Option<Vec<Row<'static>>>;
     type LeafType = SubtableArrayLeaf;
     type LeafContext = SubtableContext;
+
+    const KIND: ColumnKind = ColumnKind::Table;
+
+    fn get_lazy(tree: &BpTree<Self>, index: usize) -> crate::RealmResult<Value> {
+        let (leaf_ref, index_in_leaf) = tree.find_leaf(index)?;
+        let leaf = Array::from_ref(Arc::clone(tree.realm()), leaf_ref)?;
+        check_index(index_in_leaf, leaf.size())?;
+
+        let Some(data_array) = leaf.get_node::<Array>(index_in_leaf)? else {
+            return Ok(Value::TableLazy(SubtableRef::new(None)));
+        };
+        let context = tree.context();
+        let header_array = Array::from_ref(Arc::clone(tree.realm()), context.header_ref)?;
+        let data_ref = data_array.node.ref_;
+        let table = Table::build_from(&header_array, data_array, usize::MAX, context.depth)?;
+
+        Ok(Value::TableLazy(SubtableRef::new(Some((data_ref, table)))))
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct SubtableArrayLeaf {
     root: Array,
     header_array: Array,
+    depth: usize,
 }
 
 impl NodeWithContext<SubtableContext> for SubtableArrayLeaf {
@@ -54,18 +79,24 @@ impl NodeWithContext<SubtableContext> for SubtableArrayLeaf {
         let root = Array::from_ref(Arc::clone(&realm), ref_)?;
         let header_array = Array::from_ref(realm, context.header_ref)?;
 
-        Ok(SubtableArrayLeaf { root, header_array })
+        Ok(SubtableArrayLeaf {
+            root,
+            header_array,
+            depth: context.depth,
+        })
     }
 }
 
 impl ArrayLike<Option<Vec<Row<'static>>>, SubtableContext> for SubtableArrayLeaf {
     fn get(&self, index: usize) -> crate::RealmResult<Option<Vec<Row<'static>>>> {
+        check_index(index, self.root.size())?;
+
         let Some(data_array) = self.root.get_node(index)? else {
             return Ok(None);
         };
 
         Ok(Some(
-            Table::build_from(&self.header_array, data_array, usize::MAX)?
+            Table::build_from(&self.header_array, data_array, usize::MAX, self.depth)?
                 .get_rows()?
                 .into_iter()
                 .map(Row::into_owned)
@@ -80,6 +111,7 @@ impl ArrayLike<Option<Vec<Row<'static>>>, SubtableContext> for SubtableArrayLeaf
         context: SubtableContext,
     ) -> crate::RealmResult<Option<Vec<Row<'static>>>> {
         let header = realm.header(ref_)?;
+        check_index(index, header.size as usize)?;
         let payload = realm.payload(ref_, header.payload_len());
 
         let data_array = match read_array_value(payload, header.width(), index) {
@@ -92,7 +124,7 @@ impl ArrayLike<Option<Vec<Row<'static>>>, SubtableContext> for SubtableArrayLeaf
         let header_array = Array::from_ref(realm, context.header_ref)?;
 
         Ok(Some(
-            Table::build_from(&header_array, data_array, usize::MAX)?
+            Table::build_from(&header_array, data_array, usize::MAX, context.depth)?
                 .get_rows()?
                 .into_iter()
                 .map(|row| Row::into_owned(row))
@@ -101,7 +133,9 @@ impl ArrayLike<Option<Vec<Row<'static>>>, SubtableContext> for SubtableArrayLeaf
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        Ok(self.root.get_ref(index).is_none())
+        check_index(index, self.root.size())?;
+
+        Ok(self.root.get_ref(index)?.is_none())
     }
 
     fn size(&self) -> usize {
@@ -116,6 +150,7 @@ pub(crate) fn create_subtable_column(
     data_ref: RealmRef,
     attributes: ColumnAttributes,
     name: String,
+    depth: usize,
 ) -> crate::RealmResult<Box<dyn Column>> {
     Ok(Box::new(SubtableColumn::new(
         realm,
@@ -124,8 +159,38 @@ pub(crate) fn create_subtable_column(
         None,
         attributes,
         Some(name),
-        SubtableContext { header_ref },
+        SubtableContext { header_ref, depth },
     )?))
 }
 
 pub(crate) type SubtableColumn = ColumnImpl<SubtableColumnType>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RealmBuilder;
+
+    #[test]
+    fn test_leaf_out_of_bounds() {
+        let mut builder = RealmBuilder::new();
+        let spec = builder.table_spec(&[0], &["value"], &[0], &[]);
+        let values = builder.integers(&[7]);
+        let rows = builder.refs(&[values]);
+        let leaf = builder.refs(&[rows, 0]);
+        let (realm, ref_) = builder.build_with_ref(leaf);
+        let context = SubtableContext {
+            header_ref: RealmRef::new(spec as usize),
+            depth: 1,
+        };
+        let leaf =
+            SubtableArrayLeaf::from_ref_with_context(Arc::clone(&realm), ref_, context).unwrap();
+
+        let rows = leaf.get(0).unwrap().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get("value"), Some(&Value::Int(7)));
+        assert_eq!(leaf.get(1).unwrap(), None);
+        assert!(leaf.get(2).is_err());
+        assert!(leaf.is_null(2).is_err());
+        assert!(SubtableArrayLeaf::get_direct(realm, ref_, 2, context).is_err());
+    }
+}
@@ -1,9 +1,11 @@
+use crate::RealmFileError;
 use crate::array::{Array, RealmRef};
 use crate::column::integer::IntColumnType;
 use crate::column::integer_optional::IntNullableColumnType;
-use crate::column::{BpTree, Column};
-use crate::index::Index;
+use crate::column::{BpTree, Column, require_index};
+use crate::index::{Index, IndexEntry};
 use crate::realm::Realm;
+use crate::spec::ColumnKind;
 use crate::table::ColumnAttributes;
 use crate::traits::Node;
 use crate::value::Value;
@@ -12,13 +14,26 @@ use std::sync::Arc;
 
 #[derive(Debug)]
 pub(crate) struct TimestampColumn {
-    seconds: BpTree<IntNullableColumnType>,
-    nanoseconds: BpTree<IntColumnType>,
+    data: TimestampData,
     index: Option<Index>,
     attributes: ColumnAttributes,
     name: String,
 }
 
+/// The layout of the data of a timestamp column.
+#[derive(Debug)]
+enum TimestampData {
+    /// The current layout: an array holding the refs of the (nullable)
+    /// seconds and of the nanoseconds.
+    Split {
+        seconds: BpTree<IntNullableColumnType>,
+        nanoseconds: BpTree<IntColumnType>,
+    },
+    /// The legacy layout, used by older files: a single integer column of
+    /// seconds, without nanoseconds or nulls.
+    Seconds(BpTree<IntColumnType>),
+}
+
 impl TimestampColumn {
     pub(crate) fn new(
         realm: Arc<Realm>,
@@ -31,12 +46,25 @@ impl TimestampColumn {
         let index = index_ref
             .map(|ref_| Index::from_ref(Arc::clone(&realm), ref_))
             .transpose()?;
-        let seconds = array.get_node(0)?.unwrap();
-        let nanoseconds = array.get_node(1)?.unwrap();
+
+        // The current layout is a leaf with exactly two refs. Anything else,
+        // such as an integer leaf or an integer B+Tree, is a single column of
+        // seconds.
+        let header = &array.node.header;
+        let data = if header.has_refs() && !header.is_inner_bptree() && array.size() == 2 {
+            let missing = |what: &str| RealmFileError::InvalidRealmFile {
+                reason: format!("timestamp column {name:?} has no {what} array"),
+            };
+            TimestampData::Split {
+                seconds: array.get_node(0)?.ok_or_else(|| missing("seconds"))?,
+                nanoseconds: array.get_node(1)?.ok_or_else(|| missing("nanoseconds"))?,
+            }
+        } else {
+            TimestampData::Seconds(BpTree::from_ref(realm, data_ref)?)
+        };
 
         Ok(Self {
-            seconds,
-            nanoseconds,
+            data,
             index,
             attributes,
             name,
@@ -46,31 +74,45 @@ impl TimestampColumn {
 
 impl Column for TimestampColumn {
     fn get(&self, index: usize) -> crate::RealmResult<Value> {
-        // Get seconds value
-        let seconds = match self.seconds.get(index)? {
-            Some(seconds) => seconds,
-            None => return Ok(Value::None),
-        };
+        match &self.data {
+            TimestampData::Split {
+                seconds,
+                nanoseconds,
+            } => {
+                // The seconds are stored as a nullable integer, which yields
+                // `None` for the null sentinel. Any other value, including 0,
+                // is a real timestamp.
+                let seconds = seconds.get(index)?;
+                let nanoseconds = match seconds {
+                    Some(_) => nanoseconds.get(index)?,
+                    None => 0,
+                };
 
-        // If seconds is 0, the timestamp is null
-        if seconds == 0 {
-            return Ok(Value::Timestamp(Default::default()));
+                Ok(timestamp_value(seconds, nanoseconds))
+            }
+            TimestampData::Seconds(seconds) => Ok(timestamp_value(Some(seconds.get(index)?), 0)),
         }
-
-        // Get nanoseconds value
-        let nanoseconds = self.nanoseconds.get(index)?;
-
-        // Convert to DateTime
-        let seconds = i64::from_le_bytes(seconds.to_le_bytes());
-        Ok(DateTime::from_timestamp(seconds, nanoseconds as u32).into())
     }
 
     fn is_null(&self, index: usize) -> crate::RealmResult<bool> {
-        self.seconds.is_null(index)
+        match &self.data {
+            TimestampData::Split { seconds, .. } => seconds.is_null(index),
+            TimestampData::Seconds(_) => Ok(false),
+        }
+    }
+
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        match &self.data {
+            TimestampData::Split { seconds, .. } => seconds.null_bitmap(),
+            TimestampData::Seconds(seconds) => Ok(vec![false; seconds.count()?]),
+        }
     }
 
     fn count(&self) -> crate::RealmResult<usize> {
-        self.seconds.count()
+        match &self.data {
+            TimestampData::Split { seconds, .. } => seconds.count(),
+            TimestampData::Seconds(seconds) => seconds.count(),
+        }
     }
 
     fn nullable(&self) -> bool {
@@ -81,17 +123,60 @@ impl Column for TimestampColumn {
         self.attributes.is_indexed()
     }
 
-    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::RealmResult<Option<usize>> {
-        let Some(index) = &self.index else {
-            panic!("Column {:?} is not indexed", self.name());
-        };
+    fn get_row_number_by_index(&self, lookup_value: &Value) -> crate::TableResult<Option<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_first(lookup_value)?)
+    }
 
-        index.find_first(lookup_value)
+    fn get_row_numbers_by_index(&self, lookup_value: &Value) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.find_all(lookup_value)?)
+    }
+
+    fn index_contains(&self, lookup_value: &Value) -> crate::TableResult<bool> {
+        Ok(require_index(self.index.as_ref(), self.name())?.contains(lookup_value)?)
+    }
+
+    fn get_distinct_row_numbers_by_index(&self) -> crate::TableResult<Vec<usize>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.distinct_row_numbers()?)
+    }
+
+    fn index_entries(&self) -> crate::TableResult<Vec<IndexEntry>> {
+        Ok(require_index(self.index.as_ref(), self.name())?.entries()?)
     }
 
     fn name(&self) -> Option<&str> {
         Some(&self.name)
     }
+
+    fn column_type(&self) -> ColumnKind {
+        ColumnKind::Timestamp
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
+}
+
+/// Convert the stored seconds and nanoseconds of a timestamp into a [`Value`].
+/// Null timestamps have no seconds.
+///
+/// Realm stores the nanoseconds as a signed 32-bit integer, with the same sign
+/// as the seconds, so -1.5 seconds is stored as (-1, -500,000,000). `chrono`
+/// expects the nanoseconds to always be positive, so negative nanoseconds are
+/// normalized by borrowing a second.
+fn timestamp_value(seconds: Option<i64>, nanoseconds: i64) -> Value {
+    const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
+    let Some(mut seconds) = seconds else {
+        return Value::None;
+    };
+
+    let mut nanoseconds = nanoseconds as i32;
+    if nanoseconds < 0 {
+        seconds -= 1;
+        nanoseconds += NANOS_PER_SECOND;
+    }
+
+    DateTime::from_timestamp(seconds, nanoseconds as u32).into()
 }
 
 // Factory function for timestamp columns
@@ -106,3 +191,111 @@ pub(crate) fn create_timestamp_column(
         realm, data_ref, index_ref, attributes, name,
     )?))
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::test_utils::RealmBuilder;
+
+    fn timestamp(seconds: i64, nanoseconds: u32) -> Value {
+        DateTime::from_timestamp(seconds, nanoseconds).into()
+    }
+
+    #[test]
+    fn test_split_layout() {
+        let mut builder = RealmBuilder::new();
+        // Nullable seconds, with 7 as the null value.
+        let seconds = builder.integers(&[7, 100, 7]);
+        let nanoseconds = builder.integers(&[5, 0]);
+        let data = builder.refs(&[seconds, nanoseconds]);
+        let (realm, ref_) = builder.build_with_ref(data);
+        let column =
+            TimestampColumn::new(realm, ref_, None, ColumnAttributes::new(0), "t".into()).unwrap();
+
+        assert_eq!(column.count().unwrap(), 2);
+        assert_eq!(column.get(0).unwrap(), timestamp(100, 5));
+        assert_eq!(column.get(1).unwrap(), Value::None);
+        assert!(column.is_null(1).unwrap());
+    }
+
+    #[test]
+    fn test_narrow_negative_seconds() {
+        // Seconds are stored at the smallest width that holds them, as signed
+        // integers. The first value is the null value.
+        let seconds: [(u8, usize, &[i64]); 3] = [
+            (0x04, 1, &[i8::MAX as i64, -1, -100]),
+            (0x05, 2, &[i16::MAX as i64, -1, -30_000]),
+            (0x06, 4, &[i32::MAX as i64, -1, -86_400 * 365]),
+        ];
+
+        for (flags, width, values) in seconds {
+            let mut builder = RealmBuilder::new();
+            let payload = values
+                .iter()
+                .flat_map(|value| value.to_le_bytes()[..width].to_vec())
+                .collect::<Vec<_>>();
+            let seconds = builder.node(flags, values.len() as u32, &payload);
+            let nanoseconds = builder.integers(&[(-500_000_000i64) as u64, 0]);
+            let data = builder.refs(&[seconds, nanoseconds]);
+            let (realm, ref_) = builder.build_with_ref(data);
+            let column =
+                TimestampColumn::new(realm, ref_, None, ColumnAttributes::new(0), "t".into())
+                    .unwrap();
+
+            assert_eq!(column.get(0).unwrap(), timestamp(-2, 500_000_000));
+            assert_eq!(column.get(1).unwrap(), timestamp(values[2], 0));
+        }
+    }
+
+    #[test]
+    fn test_legacy_seconds_layout() {
+        let mut builder = RealmBuilder::new();
+        let data = builder.integers(&[100, 200]);
+        let (realm, ref_) = builder.build_with_ref(data);
+        let column =
+            TimestampColumn::new(realm, ref_, None, ColumnAttributes::new(0), "t".into()).unwrap();
+
+        assert_eq!(column.count().unwrap(), 2);
+        assert_eq!(column.get(0).unwrap(), timestamp(100, 0));
+        assert_eq!(column.get(1).unwrap(), timestamp(200, 0));
+        assert!(!column.is_null(1).unwrap());
+    }
+
+    #[test]
+    fn test_null_timestamp() {
+        assert!(matches!(timestamp_value(None, 0), Value::None));
+    }
+
+    #[test]
+    fn test_epoch_timestamp() {
+        let Value::Timestamp(timestamp) = timestamp_value(Some(0), 0) else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(timestamp, DateTime::UNIX_EPOCH);
+
+        let Value::Timestamp(timestamp) = timestamp_value(Some(0), 500) else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(timestamp.timestamp(), 0);
+        assert_eq!(timestamp.timestamp_subsec_nanos(), 500);
+    }
+
+    #[test]
+    fn test_pre_epoch_timestamp() {
+        // 1969-12-31T23:59:58.500Z, i.e. -1.5 seconds.
+        let Value::Timestamp(timestamp) = timestamp_value(Some(-1), -500_000_000) else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(timestamp.timestamp(), -2);
+        assert_eq!(timestamp.timestamp_subsec_nanos(), 500_000_000);
+        assert_eq!(timestamp.to_rfc3339(), "1969-12-31T23:59:58.500+00:00");
+
+        // Whole seconds before the epoch have no nanoseconds.
+        let Value::Timestamp(timestamp) = timestamp_value(Some(-86_400), 0) else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(timestamp.to_rfc3339(), "1969-12-31T00:00:00+00:00");
+    }
+}
@@ -0,0 +1,111 @@
+//! # Unsupported column implementation
+//!
+//! Columns of types that can't be read yet are represented by placeholders
+//! when [`RealmOptions::skip_unsupported`](crate::RealmOptions::skip_unsupported)
+//! is set, so the other columns of their tables can still be read. Every value
+//! in these columns is a [`Value::Unsupported`].
+
+use std::sync::Arc;
+
+use crate::array::{Array, RealmRef};
+use crate::column::Column;
+use crate::column::bptree::BpTreeNode;
+use crate::realm::Realm;
+use crate::spec::ColumnKind;
+use crate::table::ColumnAttributes;
+use crate::traits::Node;
+use crate::value::Value;
+
+#[derive(Debug)]
+pub(crate) struct UnsupportedColumn {
+    kind: ColumnKind,
+    count: usize,
+    attributes: ColumnAttributes,
+    name: String,
+}
+
+impl UnsupportedColumn {
+    pub(crate) fn new(
+        realm: Arc<Realm>,
+        data_ref: RealmRef,
+        kind: ColumnKind,
+        attributes: ColumnAttributes,
+        name: String,
+    ) -> crate::RealmResult<Self> {
+        let root = Array::from_ref(Arc::clone(&realm), data_ref)?;
+        let header = &root.node.header;
+
+        // The values can't be read, but the number of values is needed for
+        // the row count of the table. Every column type is stored as a B+Tree,
+        // so only the layout of the leaves differs: either an array holding
+        // the values (or their refs), or, for binary data, an array holding
+        // the refs of the offsets and the blob, where the number of offsets is
+        // the number of values.
+        let count = if header.is_inner_bptree() {
+            BpTreeNode::new(&root).get_bptree_size()?
+        } else if header.has_refs() && !header.context_flag() && kind == ColumnKind::Binary {
+            match root.get_ref(0)? {
+                Some(offsets) => realm.header(offsets)?.size as usize,
+                None => 0,
+            }
+        } else {
+            root.size()
+        };
+
+        Ok(Self {
+            kind,
+            count,
+            attributes,
+            name,
+        })
+    }
+}
+
+impl Column for UnsupportedColumn {
+    fn get(&self, _: usize) -> crate::RealmResult<Value> {
+        Ok(Value::Unsupported { kind: self.kind })
+    }
+
+    fn is_null(&self, _: usize) -> crate::RealmResult<bool> {
+        Ok(false)
+    }
+
+    fn count(&self) -> crate::RealmResult<usize> {
+        Ok(self.count)
+    }
+
+    fn nullable(&self) -> bool {
+        self.attributes.is_nullable()
+    }
+
+    /// Indexes of unsupported columns can't be used, as the values can't be
+    /// compared, so these columns are never reported as indexed.
+    fn is_indexed(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+
+    fn column_type(&self) -> ColumnKind {
+        self.kind
+    }
+
+    fn attributes(&self) -> ColumnAttributes {
+        self.attributes
+    }
+}
+
+// Factory function for placeholders of unsupported columns
+pub(crate) fn create_unsupported_column(
+    realm: Arc<Realm>,
+    data_ref: RealmRef,
+    kind: ColumnKind,
+    attributes: ColumnAttributes,
+    name: String,
+) -> crate::RealmResult<Box<dyn Column>> {
+    Ok(Box::new(UnsupportedColumn::new(
+        realm, data_ref, kind, attributes, name,
+    )?))
+}
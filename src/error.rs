@@ -1,12 +1,14 @@
 use std::error::Error;
 
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
-use crate::{Row, Value};
+use crate::{ColumnKind, Row, Value};
 
 /// Errors that occur while reading a Realm file, such as I/O errors or invalid
 /// file formats.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum RealmFileError {
     /// Error occurred while reading the file.
     #[error("I/O error: {0}")]
@@ -31,6 +33,7 @@ pub enum RealmFileError {
 /// Errors that occur while reading a table, such as invalid column names or
 /// missing columns.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TableError {
     /// A file error occurred. See [`RealmFileError`].
     #[error("Realm file error: {0}")]
@@ -39,7 +42,9 @@ pub enum TableError {
     /// Tried to access a table that does not exist.
     #[error("Table not found with name '{name}'")]
     TableNotFound {
-        /// Name of the table that was not found.
+        /// Name of the table that was not found. For tables accessed by
+        /// number, such as the target of a [`Link`](crate::Link), this is the
+        /// number formatted as `table#N`.
         name: String,
     },
 
@@ -60,11 +65,43 @@ pub enum TableError {
         /// Name of the column that is not indexed.
         name: String,
     },
+
+    /// Tried to look up rows by a word (using
+    /// [`find_rows_by_token`](crate::Table::find_rows_by_token)), but the
+    /// column does not have a full-text index.
+    #[error("Column '{name}' does not have a full-text index")]
+    ColumnNotFullTextIndexed {
+        /// Name of the column without a full-text index.
+        name: String,
+    },
+
+    /// Tried to look up a row by its primary key (using
+    /// [`find_by_primary_key`](crate::Group::find_by_primary_key)), but the
+    /// table has no primary key.
+    #[error("Table '{name}' has no primary key")]
+    NoPrimaryKey {
+        /// Name of the table without a primary key.
+        name: String,
+    },
+
+    /// Tried to query a column using a typed helper, such as
+    /// [`find_by_str`](crate::Table::find_by_str), but the column holds a
+    /// different kind of value.
+    #[error("Column '{name}' has type {found:?}, expected {expected:?}")]
+    ColumnTypeMismatch {
+        /// Name of the column.
+        name: String,
+        /// The kind of column the helper works on.
+        expected: ColumnKind,
+        /// The actual kind of the column.
+        found: ColumnKind,
+    },
 }
 
 /// Errors related to value conversions, usually when converting to a model
 /// using [`realm_model`](crate::realm_model).
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ValueError {
     /// Expected a Table value, found something else. This can happen when
     /// trying to convert a [`Value`] into a `Vec<T>`, if the data is not
@@ -84,7 +121,7 @@ pub enum ValueError {
         /// The name of the field that was expected.
         field: &'static str,
         /// The row that was found, which did not have the expected field.
-        found: Row<'static>,
+        found: Box<Row<'static>>,
     },
 
     /// Expected a different type. This could happen if your
@@ -98,6 +135,27 @@ pub enum ValueError {
         found: Value,
     },
 
+    /// An integer value doesn't fit in the integer type it is converted to,
+    /// e.g. a negative value converted to a `u32`.
+    #[error("Integer {value} is out of range for {target}")]
+    IntOutOfRange {
+        /// The integer value.
+        value: i64,
+        /// The integer type the value was converted to.
+        target: &'static str,
+    },
+
+    /// A timestamp value can't be represented by the type it is converted to,
+    /// e.g. a timestamp before the Unix epoch converted to a
+    /// [`SystemTime`](std::time::SystemTime).
+    #[error("Timestamp {value} is out of range for {target}")]
+    TimestampOutOfRange {
+        /// The timestamp value.
+        value: DateTime<Utc>,
+        /// The type the value was converted to.
+        target: &'static str,
+    },
+
     /// Failed to convert a [`Row`] from a subtable into a `Vec<T>`, because the
     /// underlying `T: TryFrom<Row>>` failed.
     #[error("Failed to convert value in row to Vec<{element_type}>: {source}")]
@@ -116,14 +174,55 @@ pub enum ValueError {
     )]
     MissingField {
         /// The name of the missing field.
-        field: &'static str,
+        field: String,
         /// The type of the target struct.
         target_type: &'static str,
         /// The remaining fields in the row. Note that if the missing field is
         /// not the first field, some fields may be missing from the overall
         /// row, as they were already converted before the missing field was
         /// encountered.
-        remaining_fields: Row<'static>,
+        remaining_fields: Box<Row<'static>>,
+    },
+
+    /// Failed to convert the value of a field when converting a [`Row`] into
+    /// a struct. This can happen if your [`realm_model`](crate::realm_model)
+    /// definition is incorrect, and a field has a different type than the
+    /// column it is read from.
+    #[error("Failed to convert field '{field}' of '{target_type}': {source}")]
+    FieldConversion {
+        /// The name of the field (or column alias) that failed to convert.
+        field: String,
+        /// The type of the target struct.
+        target_type: &'static str,
+        /// The error that occurred during conversion.
+        source: Box<ValueError>,
+    },
+}
+
+/// Errors that occur while reading rows into a model, such as when resolving a
+/// [`Link`](crate::Link) or [`Backlink`](crate::Backlink) using e.g.
+/// [`Group::resolve_link_as`](crate::Group::resolve_link_as), or iterating
+/// over [`Table::iter_models`](crate::Table::iter_models).
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// Loading a row failed, such as the target of a link, or the next row of
+    /// [`Table::iter_models`](crate::Table::iter_models). See [`TableError`].
+    #[error("Failed to load row: {0}")]
+    Table(#[from] TableError),
+
+    /// Converting a row into the model failed. See [`ValueError`].
+    #[error("Failed to convert row: {0}")]
+    Value(#[from] ValueError),
+
+    /// Resolving one of several links failed, e.g. using
+    /// [`Group::resolve_link_list_as`](crate::Group::resolve_link_list_as).
+    #[error("Failed to resolve link {index}: {source}")]
+    LinkListElement {
+        /// The position of the link that failed.
+        index: usize,
+        /// The error that occurred while resolving the link.
+        source: Box<ResolveError>,
     },
 }
 
@@ -135,3 +234,6 @@ pub type TableResult<T> = std::result::Result<T, TableError>;
 
 /// Convenience type alias for `Result<T, ValueError>`.
 pub type ValueResult<T> = std::result::Result<T, ValueError>;
+
+/// Convenience type alias for `Result<T, ResolveError>`.
+pub type ResolveResult<T> = std::result::Result<T, ResolveError>;
@@ -1,15 +1,22 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
 use tracing::{instrument, warn};
 
 use crate::array::{Array, ArrayStringShort};
-use crate::error::TableError;
-use crate::table::Table;
+use crate::error::{RealmFileError, ResolveError, TableError, ValueError};
+use crate::realm::Realm;
+use crate::spec::ColumnKind;
+use crate::table::{DatabaseSchema, DatabaseTableSchema, Row, Table};
 use crate::traits::ArrayLike;
+use crate::value::{Backlink, Link, ResolvedLink, Value};
 
 /// The group is the central root of a Realm database. It contains all the
 /// tables and their names.
 ///
 /// The main way to interact with the Realm database, is by opening the
-/// [`Realm`](crate::Realm::open), and calling
+/// [`Realm`](Realm::open), and calling
 /// [`realm.into_group`][`crate::Realm::into_group`]. The resulting [`Group`]
 /// can then be used to access tables.
 ///
@@ -22,51 +29,181 @@ use crate::traits::ArrayLike;
 /// let table = group.get_table(0).unwrap();
 /// let row = table.get_row(0).unwrap();
 /// ```
+///
+/// # Thread safety
+///
+/// [`Group`], [`Table`] and [`Row`] are all [`Send`] and [`Sync`], as they only
+/// ever read from the underlying file. To read from multiple threads, wrap the
+/// group in an [`Arc`](std::sync::Arc):
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use realm_db_reader::Realm;
+///
+/// let group = Arc::new(Realm::open("example.realm").unwrap().into_group().unwrap());
+///
+/// let handles = (0..group.table_count())
+///     .map(|table_number| {
+///         let group = Arc::clone(&group);
+///         thread::spawn(move || group.get_table(table_number).unwrap().row_count().unwrap())
+///     })
+///     .collect::<Vec<_>>();
+///
+/// for handle in handles {
+///     println!("{} rows", handle.join().unwrap());
+/// }
+/// ```
 #[derive(Debug)]
 pub struct Group {
-    tables_array: Array,
+    tables_array: TablesArray,
     table_names: Vec<String>,
+    /// The number of each table, by name, for looking up tables by name
+    /// without scanning [`table_names`](Self::table_names).
+    table_numbers: HashMap<String, usize>,
+    /// Tables loaded through [`get_table_cached`](Self::get_table_cached), by
+    /// number. The file is never modified, so these never need to be
+    /// invalidated.
+    table_cache: Mutex<HashMap<usize, Arc<Table>>>,
 }
 
 impl Group {
+    /// The name of the table holding the primary key of each class.
+    const PK_TABLE: &str = "pk";
+    /// The name of the table holding the schema version.
+    const METADATA_TABLE: &str = "metadata";
+    /// The schema version Realm stores before a schema has been set.
+    const NOT_VERSIONED: u64 = u64::MAX;
+
     #[instrument(level = "debug")]
     pub(crate) fn build(array: Array) -> crate::RealmResult<Self> {
-        let table_names = {
+        let table_names: Vec<String> = {
             let array: ArrayStringShort = array.get_node(0)?.unwrap();
             array.get_all()?
         };
 
-        let tables_array = array.get_node(1)?.unwrap();
+        let tables_array: Array = array.get_node(1)?.unwrap();
+        if tables_array.size() != table_names.len() {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "group has {} table names, but {} tables",
+                    table_names.len(),
+                    tables_array.size()
+                ),
+            });
+        }
+
+        // If a name occurs more than once, the first table with that name is
+        // used, as when scanning the list of names.
+        let mut table_numbers = HashMap::with_capacity(table_names.len());
+        for (table_number, name) in table_names.iter().enumerate() {
+            table_numbers.entry(name.clone()).or_insert(table_number);
+        }
 
         Ok(Self {
-            tables_array,
+            tables_array: TablesArray::Array(tables_array),
             table_names,
+            table_numbers,
+            table_cache: Mutex::default(),
         })
     }
+
+    /// Create a group without any tables, for a database that holds no data
+    /// yet, i.e. whose top ref is 0.
+    pub(crate) fn empty(realm: Arc<Realm>) -> Self {
+        Self {
+            tables_array: TablesArray::Empty(realm),
+            table_names: Vec::new(),
+            table_numbers: HashMap::new(),
+            table_cache: Mutex::default(),
+        }
+    }
+}
+
+/// The array holding the tables of a [`Group`], which an empty database does
+/// not have. Either way, this holds the group's reference to the [`Realm`].
+#[derive(Debug)]
+enum TablesArray {
+    Array(Array),
+    Empty(Arc<Realm>),
+}
+
+impl TablesArray {
+    fn realm(&self) -> &Arc<Realm> {
+        match self {
+            TablesArray::Array(array) => &array.node.realm,
+            TablesArray::Empty(realm) => realm,
+        }
+    }
 }
 
 impl Group {
     /// Get the [`Table`] with the given number (starting from 0).
     ///
-    /// Panics if the table number is out of bounds.
+    /// Returns [`TableError::TableNotFound`] if there is no table with the
+    /// given number, e.g. when resolving a [`Link`] to a table that no longer
+    /// exists. The name in the error is the number, formatted as `table#N`.
     #[instrument(level = "debug", skip(self), fields(table_names = ?self.table_names))]
     pub fn get_table(&self, table_number: usize) -> crate::TableResult<Table> {
-        let table_array = self.tables_array.get_node(table_number)?.unwrap();
+        if table_number >= self.table_count() {
+            return Err(TableError::TableNotFound {
+                name: format!("table#{table_number}"),
+            });
+        }
+
+        let table_array = match &self.tables_array {
+            TablesArray::Array(array) => array.get_node(table_number)?,
+            TablesArray::Empty(_) => None,
+        }
+        .ok_or_else(|| RealmFileError::InvalidRealmFile {
+            reason: format!("table {table_number} has no data"),
+        })?;
 
         let table = Table::build(table_array, table_number)?;
 
         Ok(table)
     }
 
+    /// Get the [`Table`] with the given number, like
+    /// [`get_table`](Self::get_table), but keep it for later calls. Building a
+    /// table parses the specs of all its columns, so this is faster when the
+    /// same table is accessed repeatedly, e.g. when resolving many links into
+    /// it. The cached tables are kept until the group is dropped.
+    ///
+    /// Returns the same errors as [`get_table`](Self::get_table). Errors are
+    /// not cached.
+    #[instrument(level = "debug", skip(self))]
+    pub fn get_table_cached(&self, table_number: usize) -> crate::TableResult<Arc<Table>> {
+        if let Some(table) = self.cached_tables().get(&table_number) {
+            return Ok(Arc::clone(table));
+        }
+
+        // Build the table without holding the lock, so other threads can
+        // access the cache in the meantime. If another thread built the same
+        // table first, its table is kept.
+        let table = Arc::new(self.get_table(table_number)?);
+
+        Ok(Arc::clone(
+            self.cached_tables().entry(table_number).or_insert(table),
+        ))
+    }
+
+    fn cached_tables(&self) -> MutexGuard<'_, HashMap<usize, Arc<Table>>> {
+        // The cache is always left in a consistent state, so a panic in
+        // another thread while holding the lock doesn't matter.
+        self.table_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Get the [`Table`] with the given name.
     ///
     /// Panics if the table name is not found.
     #[instrument(level = "debug", skip(self), fields(table_names = ?self.table_names))]
     pub fn get_table_by_name(&self, name: &str) -> crate::TableResult<Table> {
         let table_number = self
-            .table_names
-            .iter()
-            .position(|n| n == name)
+            .table_number(name)
             .ok_or_else(|| TableError::TableNotFound {
                 name: name.to_string(),
             })?;
@@ -74,6 +211,11 @@ impl Group {
         self.get_table(table_number)
     }
 
+    /// Get the number of the table with the given name.
+    fn table_number(&self, name: &str) -> Option<usize> {
+        self.table_numbers.get(name).copied()
+    }
+
     /// Get the number of tables in the group.
     pub fn table_count(&self) -> usize {
         self.table_names.len()
@@ -90,4 +232,1190 @@ impl Group {
     pub fn get_table_names(&self) -> &[String] {
         &self.table_names
     }
+
+    /// The number of live references to the underlying [`Realm`], including
+    /// the one held by this group. Every [`Table`] obtained from the group,
+    /// and values read lazily such as [`LinkListRef`](crate::LinkListRef),
+    /// hold a reference of their own, as do the tables cached by
+    /// [`get_table_cached`](Self::get_table_cached). The file stays open (and
+    /// memory-mapped) until all of them are dropped.
+    pub fn realm_reference_count(&self) -> usize {
+        Arc::strong_count(self.tables_array.realm())
+    }
+
+    /// The number of node headers parsed so far, see [`Realm::header_parses`].
+    #[cfg(feature = "test-utils")]
+    #[doc(hidden)]
+    pub fn header_parses(&self) -> usize {
+        self.tables_array.realm().header_parses()
+    }
+
+    /// Turn this group back into the [`Realm`] it was created from.
+    ///
+    /// Returns `None` if anything else obtained from the group, such as a
+    /// [`Table`], is still alive, in which case the file is kept open until
+    /// those are dropped too. See [`realm_reference_count`](Self::realm_reference_count).
+    pub fn into_realm(self) -> Option<Realm> {
+        let realm = Arc::clone(self.tables_array.realm());
+        drop(self);
+
+        Arc::try_unwrap(realm).ok()
+    }
+
+    /// Load the row the given [`Link`] points to.
+    #[instrument(level = "debug", skip(self))]
+    pub fn resolve_link(&self, link: &Link) -> crate::TableResult<Row<'static>> {
+        let table = self.get_table_cached(link.target_table_number)?;
+        let row = table.get_row(link.row_number)?;
+
+        Ok(row.into_owned())
+    }
+
+    /// Bind the given [`Link`] to this group, so it can be resolved without
+    /// passing the group around, e.g.
+    /// `group.link(link).resolve()?.get("name")`. See [`BoundLink`].
+    pub fn link(&self, link: Link) -> BoundLink<'_> {
+        BoundLink { group: self, link }
+    }
+
+    /// Expand the [`Link`]s in the given row inline: every link field is
+    /// replaced with a [`Value::Table`] holding the single row it points to,
+    /// whose own links are expanded in turn, up to the given depth. A depth of
+    /// 0 returns the row unchanged. Links beyond the depth limit are left as
+    /// they are, so cycles between rows are not followed endlessly.
+    ///
+    /// This is useful for exporting rows with their to-one relationships as
+    /// nested objects. Null links and link lists are left as they are.
+    #[instrument(level = "debug", skip(self))]
+    pub fn expand_links(&self, row: &Row<'_>, depth: usize) -> crate::TableResult<Row<'static>> {
+        let mut expanded = row.clone().into_owned();
+        if depth == 0 {
+            return Ok(expanded);
+        }
+
+        for (name, value) in row.ordered_entries() {
+            let Value::Link(link) = value else {
+                continue;
+            };
+
+            let target = self.expand_links(&self.resolve_link(link)?, depth - 1)?;
+            expanded = expanded.with_field(name.to_string(), Value::Table(vec![target]));
+        }
+
+        Ok(expanded)
+    }
+
+    /// Format the given [`Link`] like its [`Display`](std::fmt::Display)
+    /// implementation, but using the name of the target table, e.g.
+    /// `class_Person -> row#5`. See [`describe_link`](Self::describe_link).
+    pub fn format_link(&self, link: &Link) -> String {
+        match self.describe_link(link) {
+            Some(resolved) => resolved.to_string(),
+            None => link.to_string(),
+        }
+    }
+
+    /// Get the name of the table the given [`Link`] points to, or `None` if
+    /// there is no table with its number.
+    pub fn link_target_name(&self, link: &Link) -> Option<&str> {
+        self.table_names
+            .get(link.target_table_number)
+            .map(String::as_str)
+    }
+
+    /// Describe the given [`Link`] using the name of its target table, rather
+    /// than its number, or `None` if there is no table with that number.
+    pub fn describe_link(&self, link: &Link) -> Option<ResolvedLink> {
+        Some(ResolvedLink {
+            table_name: self.link_target_name(link)?.to_string(),
+            row_number: link.row_number,
+        })
+    }
+
+    /// Load all rows the given [`Backlink`] points to, i.e. the rows in the
+    /// origin table that link to the row containing the backlink.
+    #[instrument(level = "debug", skip(self))]
+    pub fn resolve_backlinks(&self, backlink: &Backlink) -> crate::TableResult<Vec<Row<'static>>> {
+        let table = self.get_table_cached(backlink.origin_table_number)?;
+
+        let mut rows = Vec::with_capacity(backlink.row_numbers.len());
+        for row_number in &backlink.row_numbers {
+            rows.push(table.get_row(*row_number)?.into_owned());
+        }
+
+        Ok(rows)
+    }
+
+    /// Describe the backlinks of the given row in terms of names: for every
+    /// [`Backlink`] in the row, this returns the name of the origin table, the
+    /// name of the link column in that table, and the numbers of the rows in
+    /// the origin table that link to this row.
+    ///
+    /// This loads the header of each origin table.
+    #[instrument(level = "debug", skip(self))]
+    pub fn describe_backlinks(
+        &self,
+        row: &Row<'_>,
+    ) -> crate::TableResult<Vec<(String, String, Vec<usize>)>> {
+        let mut descriptions = Vec::new();
+        for backlink in row.backlinks() {
+            let Some(table_name) = self.table_names.get(backlink.origin_table_number) else {
+                return Err(RealmFileError::InvalidRealmFile {
+                    reason: format!(
+                        "backlink refers to table {}, which does not exist",
+                        backlink.origin_table_number
+                    ),
+                }
+                .into());
+            };
+
+            let table = self.get_table_cached(backlink.origin_table_number)?;
+            let column_name = table
+                .get_column_spec(backlink.origin_column_number)
+                .and_then(|column| column.name())
+                .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                    reason: format!(
+                        "backlink refers to column {} in table '{table_name}', which does not exist",
+                        backlink.origin_column_number
+                    ),
+                })?;
+
+            descriptions.push((
+                table_name.clone(),
+                column_name.to_string(),
+                backlink.row_numbers.clone(),
+            ));
+        }
+
+        Ok(descriptions)
+    }
+
+    /// Load the row the given [`Link`] points to, and convert it into `T`,
+    /// usually a struct using [`realm_model`](crate::realm_model).
+    pub fn resolve_link_as<T>(&self, link: &Link) -> crate::ResolveResult<T>
+    where
+        T: TryFrom<Row<'static>>,
+        ValueError: From<T::Error>,
+    {
+        let row = self.resolve_link(link)?;
+
+        Ok(T::try_from(row).map_err(ValueError::from)?)
+    }
+
+    /// Load the rows the given [`Link`]s point to, e.g. the links of a link
+    /// list field, and convert each of them into `T`, usually a struct using
+    /// [`realm_model`](crate::realm_model). The rows are returned in the order
+    /// of the links.
+    ///
+    /// If a link can't be resolved or converted, this returns
+    /// [`ResolveError::LinkListElement`] with the position of that link.
+    pub fn resolve_link_list_as<T>(&self, links: &[Link]) -> crate::ResolveResult<Vec<T>>
+    where
+        T: TryFrom<Row<'static>>,
+        ValueError: From<T::Error>,
+    {
+        let mut result = Vec::with_capacity(links.len());
+        for (index, link) in links.iter().enumerate() {
+            let model =
+                self.resolve_link_as(link)
+                    .map_err(|source| ResolveError::LinkListElement {
+                        index,
+                        source: Box::new(source),
+                    })?;
+            result.push(model);
+        }
+
+        Ok(result)
+    }
+
+    /// Load all rows the given [`Backlink`] points to, and convert them into
+    /// `T`, usually a struct using [`realm_model`](crate::realm_model).
+    pub fn resolve_backlinks_as<T>(&self, backlink: &Backlink) -> crate::ResolveResult<Vec<T>>
+    where
+        T: TryFrom<Row<'static>>,
+        ValueError: From<T::Error>,
+    {
+        let rows = self.resolve_backlinks(backlink)?;
+
+        let mut result = Vec::with_capacity(rows.len());
+        for row in rows {
+            result.push(T::try_from(row).map_err(ValueError::from)?);
+        }
+
+        Ok(result)
+    }
+
+    /// Get the name of the primary key column of the table with the given
+    /// name, as recorded in the `pk` table.
+    ///
+    /// Returns `None` if the table has no primary key, or if the group has no
+    /// `pk` table at all.
+    #[instrument(level = "debug", skip(self))]
+    pub fn primary_key_column(&self, table_name: &str) -> crate::TableResult<Option<String>> {
+        // The `pk` table refers to classes by their name, without the prefix
+        // of the table name.
+        let class_name = table_name
+            .strip_prefix(TableKind::CLASS_PREFIX)
+            .unwrap_or(table_name);
+
+        Ok(self.primary_key_columns()?.remove(class_name))
+    }
+
+    /// Find and load the row with the given primary key, in the table with
+    /// the given name. The primary key column is determined using
+    /// [`primary_key_column`](Self::primary_key_column), and must be indexed.
+    /// See [`Table::find_row_from_indexed_column`].
+    ///
+    /// Returns an error if the table does not exist or has no primary key.
+    ///
+    /// Returns `None` if no row has the given primary key.
+    #[instrument(level = "debug", skip(self))]
+    pub fn find_by_primary_key(
+        &self,
+        table_name: &str,
+        value: &Value,
+    ) -> crate::TableResult<Option<Row<'static>>> {
+        let table = self.get_table_by_name(table_name)?;
+        let column_name =
+            self.primary_key_column(table_name)?
+                .ok_or_else(|| TableError::NoPrimaryKey {
+                    name: table_name.to_string(),
+                })?;
+
+        let row = table.find_row_from_indexed_column(&column_name, value)?;
+
+        Ok(row.map(Row::into_owned))
+    }
+
+    /// Find the row with the given key in any table, e.g. an object key from
+    /// a log, when its table is not known. This searches the
+    /// [user tables](Self::user_tables) whose primary key column (or, for
+    /// tables without a primary key, whose `id` column) is indexed and holds
+    /// values of the same kind as the key, returning the number of the first
+    /// table with a match along with the row.
+    ///
+    /// Tables that can't be loaded, e.g. because they have a column of an
+    /// unsupported type, are skipped. The tables searched are kept, see
+    /// [`get_table_cached`](Self::get_table_cached).
+    ///
+    /// Returns `None` if no table has a row with the given key, or if the key
+    /// is not a string, integer or timestamp.
+    #[instrument(level = "debug", skip(self))]
+    pub fn find_object_by_key(
+        &self,
+        key: &Value,
+    ) -> crate::TableResult<Option<(usize, Row<'static>)>> {
+        let kind = match key {
+            Value::String(_) => ColumnKind::String,
+            Value::Int(_) => ColumnKind::Int,
+            Value::Timestamp(_) => ColumnKind::Timestamp,
+            _ => return Ok(None),
+        };
+        let primary_keys = self.primary_key_columns()?;
+
+        for (table_number, table_name) in self.table_names.iter().enumerate() {
+            if TableKind::from_table_name(table_name) != TableKind::TopLevel {
+                continue;
+            }
+
+            let class_name = table_name
+                .strip_prefix(TableKind::CLASS_PREFIX)
+                .unwrap_or(table_name);
+            let key_column = primary_keys.get(class_name).map_or("id", String::as_str);
+
+            let Ok(table) = self.get_table_cached(table_number) else {
+                continue;
+            };
+            let is_candidate = table.get_column_specs().iter().any(|column| {
+                column.name() == Some(key_column)
+                    && column.is_indexed()
+                    && column.column_type() == kind
+            });
+            if !is_candidate {
+                continue;
+            }
+
+            if let Some(row) = table.find_row_from_indexed_column(key_column, key)? {
+                return Ok(Some((table_number, row.into_owned())));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read the primary key column of every class from the `pk` table, by
+    /// class name (without the prefix of the table name).
+    fn primary_key_columns(&self) -> crate::TableResult<HashMap<String, String>> {
+        let mut columns = HashMap::new();
+        let Some(pk_table_number) = self.table_number(Self::PK_TABLE) else {
+            return Ok(columns);
+        };
+
+        let pk_table = self.get_table(pk_table_number)?;
+        for row_number in 0..pk_table.row_count()? {
+            let Value::String(pk_class) = pk_table.get_cell(row_number, "pk_table")? else {
+                continue;
+            };
+            if let Value::String(property) = pk_table.get_cell(row_number, "pk_property")? {
+                columns.entry(pk_class).or_insert(property);
+            }
+        }
+
+        Ok(columns)
+    }
+
+    /// Get the version of the app's schema, as recorded in the `metadata`
+    /// table. This is the schema version set by the app (and used to decide
+    /// which migrations to run), not the version of the file format.
+    ///
+    /// Returns `None` if the group has no `metadata` table, or if no schema
+    /// version has been set.
+    #[instrument(level = "debug", skip(self))]
+    pub fn schema_version(&self) -> crate::RealmResult<Option<u64>> {
+        let Some(table_number) = self.table_number(Self::METADATA_TABLE) else {
+            return Ok(None);
+        };
+
+        let table = self.get_table(table_number).map_err(|error| match error {
+            TableError::FileError(error) => error,
+            error => RealmFileError::InvalidRealmFile {
+                reason: error.to_string(),
+            },
+        })?;
+        let column = table
+            .get_column_specs()
+            .iter()
+            .find(|column| column.name() == Some("version"))
+            .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                reason: "metadata table has no version column".to_string(),
+            })?;
+        if table.row_count()? == 0 {
+            return Ok(None);
+        }
+
+        match column.get(0)? {
+            Value::Int(version) if version as u64 != Self::NOT_VERSIONED => {
+                Ok(Some(version as u64))
+            }
+            Value::Int(_) | Value::None => Ok(None),
+            found => Err(RealmFileError::InvalidRealmFile {
+                reason: format!("unexpected schema version {found:?}"),
+            }),
+        }
+    }
+
+    /// Determine what kind of table the table with the given number is, based
+    /// on its name. See [`TableKind`] for details.
+    ///
+    /// Panics if the table number is out of bounds.
+    pub fn table_kind(&self, table_number: usize) -> TableKind {
+        TableKind::from_table_name(&self.table_names[table_number])
+    }
+
+    /// Whether the table with the given name backs a model class of the
+    /// application, i.e. its name starts with `class_`. Tables used internally
+    /// by Realm, such as `pk`, `metadata`, and the tables synced Realms use for
+    /// their history, are not class tables. See [`TableKind::TopLevel`].
+    pub fn is_class_table(name: &str) -> bool {
+        TableKind::from_table_name(name) == TableKind::TopLevel
+    }
+
+    /// Get the names of the model classes of the application, for presenting
+    /// them to users. These are the names of the
+    /// [class tables](Self::is_class_table), without their `class_` prefix,
+    /// in the order of the tables in the group. Tables used internally by
+    /// Realm are left out.
+    ///
+    /// No tables are loaded. To load the tables of these classes, see
+    /// [`user_tables`](Self::user_tables).
+    pub fn class_names(&self) -> Vec<&str> {
+        self.table_names
+            .iter()
+            .filter_map(|name| name.strip_prefix(TableKind::CLASS_PREFIX))
+            .collect()
+    }
+
+    /// Iterate over the tables backing the model classes of the application,
+    /// skipping tables used internally by Realm, such as `pk`, `metadata`,
+    /// and the tables synced Realms use for their history. See
+    /// [`TableKind::TopLevel`].
+    ///
+    /// Each table is loaded as the iterator advances, and yielded along with
+    /// its name. A table that can't be loaded, e.g. because it has a column of
+    /// an unsupported type, yields an error without ending the iteration.
+    pub fn user_tables(&self) -> impl Iterator<Item = (&str, crate::TableResult<Table>)> + '_ {
+        self.table_names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| TableKind::from_table_name(name) == TableKind::TopLevel)
+            .map(|(table_number, name)| (name.as_str(), self.get_table(table_number)))
+    }
+
+    /// Count the rows of every table in the group, without loading any rows.
+    /// Each table name is paired with the result of counting its rows, so a
+    /// table that can't be loaded doesn't prevent counting the others.
+    #[instrument(level = "debug", skip(self))]
+    pub fn row_counts(&self) -> Vec<(String, crate::TableResult<usize>)> {
+        self.table_names
+            .iter()
+            .enumerate()
+            .map(|(table_number, name)| {
+                let row_count = self
+                    .get_table(table_number)
+                    .and_then(|table| Ok(table.row_count()?));
+
+                (name.clone(), row_count)
+            })
+            .collect()
+    }
+
+    /// Describe all tables in the group, including the names of the tables
+    /// their link columns point to.
+    ///
+    /// This loads every table in the group.
+    #[instrument(level = "debug", skip(self))]
+    pub fn schema(&self) -> crate::TableResult<DatabaseSchema> {
+        let mut tables = Vec::with_capacity(self.table_count());
+        for (table_number, name) in self.table_names.iter().enumerate() {
+            let schema = self.get_table(table_number)?.schema();
+            let link_targets = schema
+                .columns
+                .iter()
+                .filter(|column| matches!(column.kind, ColumnKind::Link | ColumnKind::LinkList))
+                .filter_map(|column| {
+                    let target_name = self.table_names.get(column.link_target?)?;
+                    Some((column.name.clone()?, target_name.clone()))
+                })
+                .collect();
+
+            tables.push(DatabaseTableSchema {
+                name: name.clone(),
+                schema,
+                link_targets,
+            });
+        }
+
+        Ok(DatabaseSchema { tables })
+    }
+
+    /// Find the link and link list columns pointing to the table with the
+    /// given number, as pairs of a table number and a column number (counting
+    /// all columns, including backlinks). This is the schema-level
+    /// counterpart of the backlink columns in the target table.
+    ///
+    /// Tables that can't be loaded, e.g. because they have a column of an
+    /// unsupported type, are skipped. The tables inspected are kept, see
+    /// [`get_table_cached`](Self::get_table_cached).
+    #[instrument(level = "debug", skip(self))]
+    pub fn referencing_columns(&self, target_table: usize) -> Vec<(usize, usize)> {
+        let mut columns = Vec::new();
+        for table_number in 0..self.table_count() {
+            let Ok(table) = self.get_table_cached(table_number) else {
+                continue;
+            };
+
+            columns.extend(
+                table
+                    .schema()
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, column)| {
+                        matches!(column.kind, ColumnKind::Link | ColumnKind::LinkList)
+                            && column.link_target == Some(target_table)
+                    })
+                    .map(|(column_number, _)| (table_number, column_number)),
+            );
+        }
+
+        columns
+    }
+}
+
+/// The kind of a table in a [`Group`], as returned by [`Group::table_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableKind {
+    /// A table backing a model class defined by the application. These tables
+    /// have a name starting with `class_`.
+    TopLevel,
+    /// A table backing an embedded object type, i.e. objects that are owned
+    /// by their parent and not accessible on their own.
+    ///
+    /// Embedded objects were introduced in a later Realm file format than the
+    /// one supported by this library, so tables are currently never reported
+    /// as embedded.
+    Embedded,
+    /// A table used internally by Realm, such as the `pk` table holding the
+    /// primary key of each class, or the `metadata` table holding the schema
+    /// version.
+    Metadata,
+}
+
+impl TableKind {
+    const CLASS_PREFIX: &str = "class_";
+
+    fn from_table_name(name: &str) -> Self {
+        if name.starts_with(Self::CLASS_PREFIX) {
+            TableKind::TopLevel
+        } else {
+            TableKind::Metadata
+        }
+    }
+}
+
+/// A [`Link`] bound to the [`Group`] it was read from, as returned by
+/// [`Group::link`]. This is a thin wrapper around the link methods of the
+/// group, such as [`Group::resolve_link`].
+#[derive(Debug, Clone)]
+pub struct BoundLink<'g> {
+    group: &'g Group,
+    link: Link,
+}
+
+impl<'g> BoundLink<'g> {
+    /// The link itself.
+    pub fn link(&self) -> &Link {
+        &self.link
+    }
+
+    /// Load the row the link points to. See [`Group::resolve_link`].
+    pub fn resolve(&self) -> crate::TableResult<Row<'static>> {
+        self.group.resolve_link(&self.link)
+    }
+
+    /// Get the name of the table the link points to, or `None` if there is no
+    /// table with its number. See [`Group::link_target_name`].
+    pub fn target_table_name(&self) -> Option<&'g str> {
+        self.group.link_target_name(&self.link)
+    }
+}
+
+/// Formats the link using the name of the target table, see
+/// [`Group::format_link`].
+impl Display for BoundLink<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.group.format_link(&self.link))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::test_utils::{RealmBuilder, tagged};
+    use crate::{
+        Backlink, ColumnKind, ColumnSchema, Group, Link, Realm, RealmFileError, ResolveError,
+        ResolvedLink, Row, TableError, Value, ValueError, realm_model,
+    };
+
+    /// Build a Realm file with the given tables, each holding a single column
+    /// `value` of the given type, with 64-bit wide integer data.
+    fn tables_realm(tables: &[(&str, u64, &[u64])]) -> Realm {
+        let mut builder = RealmBuilder::new();
+        let tables = tables
+            .iter()
+            .map(|(name, column_type, values)| {
+                let data = builder.integers(values);
+                let table = builder.table(&[*column_type], &["value"], &[0], &[], &[data]);
+                (*name, table)
+            })
+            .collect::<Vec<_>>();
+        let top = builder.group(&tables);
+
+        builder.build(top)
+    }
+
+    /// Build a Realm file with a single table `class_Numbers`, holding a single
+    /// integer column `value`.
+    fn numbers_realm(values: &[u64]) -> Realm {
+        tables_realm(&[("class_Numbers", 0, values)])
+    }
+
+    #[test]
+    fn test_mismatched_table_count() {
+        let mut builder = RealmBuilder::new();
+        let data = builder.integers(&[1]);
+        let table = builder.table(&[0], &["value"], &[0], &[], &[data]);
+        let table_names = builder.short_strings(&["class_First", "class_Second"]);
+        let tables = builder.refs(&[table]);
+        let top = builder.refs(&[table_names, tables]);
+
+        let error = builder.build(top).into_group().unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                RealmFileError::InvalidRealmFile { reason } if reason.contains("2 table names")
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn test_read_from_multiple_threads() {
+        let values = (0..100).collect::<Vec<u64>>();
+        let group = Arc::new(numbers_realm(&values).into_group().unwrap());
+
+        let handles = (0..4)
+            .map(|_| {
+                let group = Arc::clone(&group);
+                thread::spawn(move || {
+                    let table = group.get_table(0).unwrap();
+                    table
+                        .get_rows()
+                        .unwrap()
+                        .into_iter()
+                        .map(|row| row.get("value").cloned().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let expected = values
+            .iter()
+            .map(|v| Value::Int(*v as i64))
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_into_realm() {
+        let group = numbers_realm(&[1, 2, 3]).into_group().unwrap();
+        assert_eq!(group.realm_reference_count(), 1);
+
+        let table = group.get_table(0).unwrap();
+        assert!(group.realm_reference_count() > 1);
+        assert!(group.into_realm().is_none());
+        drop(table);
+
+        let group = numbers_realm(&[1, 2, 3]).into_group().unwrap();
+        let table = group.get_table(0).unwrap();
+        drop(table);
+        assert_eq!(group.realm_reference_count(), 1);
+
+        let realm = group.into_realm().unwrap();
+        let group = realm.into_group().unwrap();
+        assert_eq!(group.get_table(0).unwrap().row_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_get_table_cached() {
+        let group = numbers_realm(&[1, 2, 3]).into_group().unwrap();
+
+        let table = group.get_table_cached(0).unwrap();
+        assert!(Arc::ptr_eq(&table, &group.get_table_cached(0).unwrap()));
+        assert_eq!(table.row_count().unwrap(), 3);
+
+        // Resolving links reuses the cached table, rather than building (and
+        // holding on to) a new one.
+        let references = group.realm_reference_count();
+        group.resolve_link(&Link::new(0, 1)).unwrap();
+        assert_eq!(group.realm_reference_count(), references);
+
+        assert!(matches!(
+            group.get_table_cached(1),
+            Err(TableError::TableNotFound { .. })
+        ));
+        drop(table);
+        assert!(group.into_realm().is_some());
+    }
+
+    #[test]
+    fn test_find_by_primary_key() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", tagged(1))]);
+        let ages = builder.integers(&[31, 42]);
+        let people = builder.table(
+            &[2, 0],
+            &["name", "age"],
+            &[1, 0],
+            &[],
+            &[names, names_index, ages],
+        );
+        let numbers = builder.integers(&[]);
+        let numbers = builder.table(&[0], &["value"], &[0], &[], &[numbers]);
+        let pk_tables = builder.short_strings(&["Person"]);
+        let pk_properties = builder.short_strings(&["name"]);
+        let pk = builder.table(
+            &[2, 2],
+            &["pk_table", "pk_property"],
+            &[0, 0],
+            &[],
+            &[pk_tables, pk_properties],
+        );
+        let top = builder.group(&[
+            ("pk", pk),
+            ("class_Person", people),
+            ("class_Numbers", numbers),
+        ]);
+        let group = builder.build(top).into_group().unwrap();
+
+        assert_eq!(
+            group.primary_key_column("class_Person").unwrap(),
+            Some("name".to_string())
+        );
+        assert_eq!(group.primary_key_column("class_Numbers").unwrap(), None);
+
+        let row = group
+            .find_by_primary_key("class_Person", &Value::from("bob"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.get("age"), Some(&Value::Int(42)));
+        assert!(
+            group
+                .find_by_primary_key("class_Person", &Value::from("carl"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(matches!(
+            group.find_by_primary_key("class_Numbers", &Value::Int(1)),
+            Err(TableError::NoPrimaryKey { .. })
+        ));
+        assert!(matches!(
+            group.find_by_primary_key("class_Missing", &Value::Int(1)),
+            Err(TableError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_object_by_key() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", tagged(1))]);
+        let people = builder.table(&[2], &["name"], &[1], &[], &[names, names_index]);
+        let ids = builder.short_strings(&["other", "item1"]);
+        let ids_index = builder.index_leaf(&[(b"item", tagged(1)), (b"othe", tagged(0))]);
+        let items = builder.table(&[2], &["id"], &[1], &[], &[ids, ids_index]);
+        let pk_tables = builder.short_strings(&["Person"]);
+        let pk_properties = builder.short_strings(&["name"]);
+        let pk = builder.table(
+            &[2, 2],
+            &["pk_table", "pk_property"],
+            &[0, 0],
+            &[],
+            &[pk_tables, pk_properties],
+        );
+        let top = builder.group(&[("pk", pk), ("class_Person", people), ("class_Item", items)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let (table_number, row) = group
+            .find_object_by_key(&Value::from("alice"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(table_number, 1);
+        assert_eq!(row.get("name"), Some(&Value::from("alice")));
+
+        let (table_number, row) = group
+            .find_object_by_key(&Value::from("item1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(table_number, 2);
+        assert_eq!(row.get("id"), Some(&Value::from("item1")));
+
+        assert!(
+            group
+                .find_object_by_key(&Value::from("missing"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(group.find_object_by_key(&Value::Int(1)).unwrap().is_none());
+        assert!(
+            group
+                .find_object_by_key(&Value::Bool(true))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_expand_links() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["a", "b"]);
+        // Both rows link to each other, stored as row number + 1.
+        let next = builder.integers(&[2, 1]);
+        let nodes = builder.table(
+            &[2, 12],
+            &["name", "next"],
+            &[0, 0],
+            &[tagged(0)],
+            &[names, next],
+        );
+        let top = builder.group(&[("class_Node", nodes)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let row = group.resolve_link(&Link::new(0, 0)).unwrap();
+        assert_eq!(group.expand_links(&row, 0).unwrap(), row);
+
+        let expanded = group.expand_links(&row, 2).unwrap();
+        let Some(Value::Table(next)) = expanded.get("next") else {
+            panic!("link not expanded: {expanded:?}");
+        };
+        assert_eq!(next[0].get("name"), Some(&Value::from("b")));
+        let Some(Value::Table(next)) = next[0].get("next") else {
+            panic!("nested link not expanded: {expanded:?}");
+        };
+        assert_eq!(next[0].get("name"), Some(&Value::from("a")));
+        assert_eq!(next[0].get("next"), Some(&Value::Link(Link::new(0, 1))));
+    }
+
+    #[test]
+    fn test_bound_link() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["a", "b"]);
+        let nodes = builder.table(&[2], &["name"], &[0], &[], &[names]);
+        let top = builder.group(&[("class_Node", nodes)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let link = group.link(Link::new(0, 1));
+        assert_eq!(link.link(), &Link::new(0, 1));
+        assert_eq!(link.target_table_name(), Some("class_Node"));
+        assert_eq!(link.to_string(), "class_Node -> row#1");
+        assert_eq!(link.resolve().unwrap().get("name"), Some(&Value::from("b")));
+
+        let dangling = group.link(Link::new(1, 0));
+        assert_eq!(dangling.target_table_name(), None);
+        assert!(matches!(
+            dangling.resolve(),
+            Err(TableError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_links_in_subtables() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["x", "y"]);
+        let tags = builder.table(&[2], &["name"], &[0], &[], &[names]);
+        // The link column of the subtable refers to `class_Tag` by its number
+        // in the group, stored as row number + 1.
+        let subtable_spec = builder.table_spec(&[12], &["tag"], &[0], &[tagged(0)]);
+        let links = builder.integers(&[2, 1]);
+        let subtable = builder.refs(&[links]);
+        let subtables = builder.refs(&[subtable]);
+        let posts = builder.table(&[5], &["tags"], &[0], &[subtable_spec], &[subtables]);
+        let top = builder.group(&[("class_Tag", tags), ("class_Post", posts)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let post = group.resolve_link(&Link::new(1, 0)).unwrap();
+        let Some(Value::Table(tags)) = post.get("tags") else {
+            panic!("subtable not loaded: {post:?}");
+        };
+        let names = tags
+            .iter()
+            .map(|tag| {
+                let Some(Value::Link(link)) = tag.get("tag") else {
+                    panic!("link not loaded: {tag:?}");
+                };
+                assert_eq!(
+                    group.format_link(link),
+                    format!("class_Tag -> row#{}", link.row_number)
+                );
+                group.resolve_link(link).unwrap().take("name").unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Value::from("y"), Value::from("x")]);
+
+        let expanded = group.expand_links(&tags[0], 1).unwrap();
+        let Some(Value::Table(tag)) = expanded.get("tag") else {
+            panic!("link not expanded: {expanded:?}");
+        };
+        assert_eq!(tag[0].get("name"), Some(&Value::from("y")));
+    }
+
+    #[test]
+    fn test_referencing_columns() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["a"]);
+        let backlinks = builder.refs(&[0]);
+        let people = builder.table(
+            &[2, 14, 14],
+            &["name"],
+            &[0, 0, 0],
+            &[tagged(1), tagged(0), tagged(1), tagged(1)],
+            &[names, backlinks, backlinks],
+        );
+        let owners = builder.integers(&[0]);
+        let watchers = builder.refs(&[0]);
+        let dogs = builder.table(
+            &[13, 12, 12],
+            &["watchers", "owner", "bowl"],
+            &[0, 0, 0],
+            &[tagged(0), tagged(0), tagged(3)],
+            &[watchers, owners, owners],
+        );
+        // Binary columns are not supported, so this table is skipped.
+        let blobs = builder.table(&[4], &["data"], &[0], &[], &[owners]);
+        let top = builder.group(&[
+            ("class_Person", people),
+            ("class_Dog", dogs),
+            ("class_Blobs", blobs),
+        ]);
+        let group = builder.build(top).into_group().unwrap();
+
+        assert_eq!(group.referencing_columns(0), [(1, 0), (1, 1)]);
+        assert_eq!(group.referencing_columns(1), []);
+        assert_eq!(group.referencing_columns(3), [(1, 2)]);
+    }
+
+    #[test]
+    fn test_schema() {
+        let group = numbers_realm(&[1, 2, 3]).into_group().unwrap();
+        let schema = group.schema().unwrap();
+
+        assert_eq!(schema.tables.len(), 1);
+        let table = schema.table("class_Numbers").unwrap();
+        assert_eq!(
+            table.schema.columns,
+            vec![ColumnSchema {
+                name: Some("value".to_string()),
+                kind: ColumnKind::Int,
+                nullable: false,
+                indexed: false,
+                link_target: None,
+            }]
+        );
+        assert!(table.link_targets.is_empty());
+    }
+
+    #[test]
+    fn test_user_tables() {
+        let group = tables_realm(&[
+            ("metadata", 0, &[1]),
+            ("class_Numbers", 0, &[1, 2]),
+            ("pk", 0, &[]),
+            // Binary columns are not supported.
+            ("class_Blobs", 4, &[]),
+            ("class_More", 0, &[3]),
+        ])
+        .into_group()
+        .unwrap();
+
+        let tables = group.user_tables().collect::<Vec<_>>();
+        assert_eq!(
+            tables.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            vec!["class_Numbers", "class_Blobs", "class_More"]
+        );
+        assert_eq!(tables[0].1.as_ref().unwrap().row_count().unwrap(), 2);
+        assert!(matches!(
+            tables[1].1,
+            Err(TableError::FileError(RealmFileError::Unsupported { .. }))
+        ));
+        assert_eq!(tables[2].1.as_ref().unwrap().get_table_number(), 4);
+    }
+
+    #[test]
+    fn test_class_names() {
+        let group = tables_realm(&[
+            ("metadata", 0, &[1]),
+            ("class_Person", 0, &[]),
+            ("pk", 0, &[]),
+            ("!client_history", 0, &[]),
+            ("class_Dog", 0, &[]),
+        ])
+        .into_group()
+        .unwrap();
+
+        assert_eq!(group.class_names(), vec!["Person", "Dog"]);
+        assert!(Group::is_class_table("class_Person"));
+        assert!(!Group::is_class_table("pk"));
+        assert!(!Group::is_class_table("Person"));
+    }
+
+    #[test]
+    fn test_schema_version() {
+        /// Build a group with a `metadata` table holding the given versions.
+        fn metadata_group(versions: &[u64]) -> Group {
+            let mut builder = RealmBuilder::new();
+            let versions = builder.integers(versions);
+            let metadata = builder.table(&[0], &["version"], &[0], &[], &[versions]);
+            let top = builder.group(&[("metadata", metadata)]);
+
+            builder.build(top).into_group().unwrap()
+        }
+
+        let group = numbers_realm(&[1]).into_group().unwrap();
+        assert_eq!(group.schema_version().unwrap(), None);
+
+        assert_eq!(metadata_group(&[42]).schema_version().unwrap(), Some(42));
+        // A version of -1 means no schema version has been set.
+        assert_eq!(metadata_group(&[u64::MAX]).schema_version().unwrap(), None);
+        assert_eq!(metadata_group(&[]).schema_version().unwrap(), None);
+
+        // The metadata table must have a version column.
+        let group = tables_realm(&[("metadata", 0, &[1])]).into_group().unwrap();
+        assert!(matches!(
+            group.schema_version(),
+            Err(RealmFileError::InvalidRealmFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_row_counts() {
+        let group = tables_realm(&[
+            ("class_Numbers", 0, &[1, 2, 3]),
+            // Binary columns are not supported.
+            ("class_Blobs", 4, &[]),
+            ("class_Empty", 0, &[]),
+        ])
+        .into_group()
+        .unwrap();
+
+        let row_counts = group.row_counts();
+        assert_eq!(
+            row_counts
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["class_Numbers", "class_Blobs", "class_Empty"]
+        );
+        assert_eq!(row_counts[0].1.as_ref().unwrap(), &3);
+        assert!(row_counts[1].1.is_err());
+        assert_eq!(row_counts[2].1.as_ref().unwrap(), &0);
+    }
+
+    #[test]
+    fn test_format_link() {
+        let group = numbers_realm(&[1]).into_group().unwrap();
+
+        assert_eq!(
+            group.format_link(&Link::new(0, 5)),
+            "class_Numbers -> row#5"
+        );
+        assert_eq!(group.format_link(&Link::new(3, 5)), "table#3 -> row#5");
+    }
+
+    #[test]
+    fn test_describe_link() {
+        let group = numbers_realm(&[1]).into_group().unwrap();
+
+        assert_eq!(
+            group.link_target_name(&Link::new(0, 5)),
+            Some("class_Numbers")
+        );
+        assert_eq!(
+            group.describe_link(&Link::new(0, 5)),
+            Some(ResolvedLink {
+                table_name: "class_Numbers".to_string(),
+                row_number: 5
+            })
+        );
+        assert_eq!(group.link_target_name(&Link::new(3, 5)), None);
+        assert_eq!(group.describe_link(&Link::new(3, 5)), None);
+    }
+
+    #[test]
+    fn test_get_table_by_name() {
+        let group = tables_realm(&[
+            ("class_Numbers", 0, &[1]),
+            ("class_Other", 0, &[1, 2]),
+            ("class_Numbers", 0, &[1, 2, 3]),
+        ])
+        .into_group()
+        .unwrap();
+
+        assert_eq!(
+            group
+                .get_table_by_name("class_Other")
+                .unwrap()
+                .get_table_number(),
+            1
+        );
+        // The first table with a duplicate name wins.
+        assert_eq!(
+            group
+                .get_table_by_name("class_Numbers")
+                .unwrap()
+                .get_table_number(),
+            0
+        );
+        assert!(matches!(
+            group.get_table_by_name("class_Missing"),
+            Err(TableError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_dangling_link() {
+        let group = numbers_realm(&[1]).into_group().unwrap();
+
+        assert_eq!(
+            group.resolve_link(&Link::new(0, 0)).unwrap().get("value"),
+            Some(&Value::Int(1))
+        );
+        let Err(TableError::TableNotFound { name }) = group.resolve_link(&Link::new(3, 0)) else {
+            panic!("expected TableNotFound");
+        };
+        assert_eq!(name, "table#3");
+        assert!(matches!(
+            group.resolve_backlinks(&Backlink::new(1, 0, vec![0])),
+            Err(TableError::TableNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_link_list_as() {
+        struct Number {
+            value: u32,
+        }
+        realm_model!(Number => value);
+
+        let group = numbers_realm(&[1, 2, 1 << 40]).into_group().unwrap();
+
+        let numbers: Vec<Number> = group
+            .resolve_link_list_as(&[Link::new(0, 1), Link::new(0, 0), Link::new(0, 1)])
+            .unwrap();
+        assert_eq!(
+            numbers
+                .iter()
+                .map(|number| number.value)
+                .collect::<Vec<_>>(),
+            [2, 1, 2]
+        );
+        assert!(
+            group
+                .resolve_link_list_as::<Number>(&[])
+                .unwrap()
+                .is_empty()
+        );
+
+        let Err(ResolveError::LinkListElement { index, source }) =
+            group.resolve_link_list_as::<Number>(&[Link::new(0, 0), Link::new(0, 2)])
+        else {
+            panic!("expected LinkListElement");
+        };
+        assert_eq!(index, 1);
+        assert!(matches!(
+            *source,
+            ResolveError::Value(ValueError::FieldConversion { .. })
+        ));
+
+        assert!(matches!(
+            group.resolve_link_list_as::<Number>(&[Link::new(3, 0)]),
+            Err(ResolveError::LinkListElement { index: 0, source })
+                if matches!(*source, ResolveError::Table(TableError::TableNotFound { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_describe_backlinks() {
+        let group = tables_realm(&[("class_Other", 0, &[]), ("class_Numbers", 0, &[1])])
+            .into_group()
+            .unwrap();
+
+        let row = Row::new(vec![Backlink::new(1, 0, vec![0, 3]).into()], vec![]);
+        assert_eq!(
+            group.describe_backlinks(&row).unwrap(),
+            vec![("class_Numbers".to_string(), "value".to_string(), vec![0, 3])]
+        );
+
+        let row = Row::new(vec![Backlink::new(1, 5, vec![0]).into()], vec![]);
+        assert!(matches!(
+            group.describe_backlinks(&row),
+            Err(TableError::FileError(
+                RealmFileError::InvalidRealmFile { .. }
+            ))
+        ));
+    }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use tracing::{debug, info, instrument, warn};
 
+use crate::RealmFileError;
 use crate::array::{Array, RealmRef, RefOrTaggedValue};
 use crate::realm::Realm;
 use crate::traits::Node;
@@ -31,13 +32,161 @@ impl Node for Index {
 
 type KeyType = u32;
 
+/// A single entry in the search index of a column, as returned by
+/// [`Table::index_entries`](crate::Table::index_entries). This is intended for
+/// debugging lookups that don't find the expected rows.
+///
+/// The index doesn't store values, but keys derived from their binary
+/// representation: the first four bytes of the value, read as a big-endian
+/// number. Values sharing their first four bytes are stored in a sub-index,
+/// keyed by their next four bytes, and so on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexEntry {
+    /// The keys leading up to this entry: one for the index itself, followed
+    /// by one for every sub-index.
+    pub keys: Vec<u32>,
+    /// The row holding the value.
+    pub row_number: usize,
+}
+
+/// The entry in the index matching a value.
+enum IndexMatch {
+    /// The value is held by a single row.
+    Row(usize),
+    /// The value is held by multiple rows, whose numbers are in this array.
+    Rows(Array),
+}
+
 impl Index {
     const KEY_SIZE: u8 = 4; // 32 bits for the key
     const KEY_SIZE_BITS: u8 = Self::KEY_SIZE * 8;
 
+    /// Find the number of the first row holding the given value.
     #[instrument(level = "debug", skip(self))]
     pub(crate) fn find_first(&self, value: &Value) -> crate::RealmResult<Option<usize>> {
-        let value = Self::coerce_to_string(value);
+        Ok(match self.find(value)? {
+            Some(IndexMatch::Row(row_number)) => Some(row_number),
+            Some(IndexMatch::Rows(rows)) => Some(rows.get(0)? as usize),
+            None => None,
+        })
+    }
+
+    /// Find the numbers of all rows holding the given value, in ascending
+    /// order.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn find_all(&self, value: &Value) -> crate::RealmResult<Vec<usize>> {
+        Ok(match self.find(value)? {
+            Some(IndexMatch::Row(row_number)) => vec![row_number],
+            Some(IndexMatch::Rows(rows)) => (0..rows.size())
+                .map(|index| Ok(rows.get(index)? as usize))
+                .collect::<crate::RealmResult<_>>()?,
+            None => Vec::new(),
+        })
+    }
+
+    /// Check whether any row holds the given value. Unlike
+    /// [`find_first`](Self::find_first), this does not read the row numbers
+    /// of values held by multiple rows.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn contains(&self, value: &Value) -> crate::RealmResult<bool> {
+        Ok(self.find(value)?.is_some())
+    }
+
+    /// Find the number of a row for every distinct value in the index, in the
+    /// order of their keys.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn distinct_row_numbers(&self) -> crate::RealmResult<Vec<usize>> {
+        let mut row_numbers = Vec::new();
+        self.walk(&mut Vec::new(), &mut |_, entry| {
+            match entry {
+                IndexMatch::Row(row_number) => row_numbers.push(row_number),
+                // All rows in the list hold the same value.
+                IndexMatch::Rows(rows) if rows.size() > 0 => {
+                    row_numbers.push(rows.get(0)? as usize)
+                }
+                IndexMatch::Rows(_) => {}
+            }
+
+            Ok(())
+        })?;
+
+        Ok(row_numbers)
+    }
+
+    /// Get every entry stored in the index, in the order of their keys.
+    /// Values held by multiple rows have an entry for each of these rows.
+    #[instrument(level = "debug", skip(self))]
+    pub(crate) fn entries(&self) -> crate::RealmResult<Vec<IndexEntry>> {
+        let mut entries = Vec::new();
+        self.walk(&mut Vec::new(), &mut |keys, entry| {
+            let row_numbers = match entry {
+                IndexMatch::Row(row_number) => vec![row_number],
+                IndexMatch::Rows(rows) => (0..rows.size())
+                    .map(|index| Ok(rows.get(index)? as usize))
+                    .collect::<crate::RealmResult<_>>()?,
+            };
+
+            entries.extend(row_numbers.into_iter().map(|row_number| IndexEntry {
+                keys: keys.to_vec(),
+                row_number,
+            }));
+
+            Ok(())
+        })?;
+
+        Ok(entries)
+    }
+
+    /// Call `f` for every value in the index, in the order of their keys, with
+    /// the keys leading up to it (one for this index, and one for every
+    /// sub-index below it).
+    fn walk(
+        &self,
+        keys: &mut Vec<KeyType>,
+        f: &mut impl FnMut(&[KeyType], IndexMatch) -> crate::RealmResult<()>,
+    ) -> crate::RealmResult<()> {
+        let realm = &self.array.node.realm;
+
+        // The first element holds the keys, followed by an entry for each key.
+        for pos in 0..(self.array.node.header.size as usize).saturating_sub(1) {
+            let ref_ = self.array.get(pos + 1)?;
+
+            // The keys of inner nodes are those of their children.
+            if self.array.node.header.is_inner_bptree() {
+                Self::from_ref(Arc::clone(realm), RealmRef::new(ref_ as usize))?.walk(keys, f)?;
+
+                continue;
+            }
+
+            keys.push(self.offsets.get(pos)? as KeyType);
+            match RefOrTaggedValue::from_raw(ref_) {
+                RefOrTaggedValue::TaggedValue(row_index) => {
+                    f(keys, IndexMatch::Row(row_index as usize))?;
+                }
+                RefOrTaggedValue::Ref(ref_) => {
+                    let array = Array::from_ref(Arc::clone(realm), ref_)?;
+                    if array.node.header.context_flag() {
+                        Self::from_ref(Arc::clone(realm), ref_)?.walk(keys, f)?;
+                    } else {
+                        f(keys, IndexMatch::Rows(array))?;
+                    }
+                }
+            }
+            keys.pop();
+        }
+
+        Ok(())
+    }
+
+    /// Check that the given value can be looked up in an index. Only strings,
+    /// integers, booleans and timestamps can; in particular, null values
+    /// can't.
+    pub(crate) fn check_lookup_value(value: &Value) -> crate::RealmResult<()> {
+        Self::coerce_to_string(value).map(drop)
+    }
+
+    fn find(&self, value: &Value) -> crate::RealmResult<Option<IndexMatch>> {
+        let value = Self::coerce_to_string(value)?;
 
         let mut value_offset: usize = 0;
         let mut key = Self::create_key(&value);
@@ -69,7 +218,7 @@ impl Index {
             assert!(pos < current_index.array.node.header.size as usize);
 
             let pos_refs = pos + 1;
-            let ref_ = current_index.array.get(pos_refs);
+            let ref_ = current_index.array.get(pos_refs)?;
 
             if current_index.array.node.header.is_inner_bptree() {
                 let ref_ = RealmRef::new(ref_ as usize);
@@ -81,7 +230,7 @@ impl Index {
                 continue;
             }
 
-            let stored_key = current_index.offsets.get(pos) as KeyType;
+            let stored_key = current_index.offsets.get(pos)? as KeyType;
             if stored_key != key {
                 warn!(
                     "Key mismatch: stored_key = {stored_key:?}, expected key = {key:?} at pos = {pos}",
@@ -92,7 +241,7 @@ impl Index {
 
             match RefOrTaggedValue::from_raw(ref_) {
                 RefOrTaggedValue::TaggedValue(row_index) => {
-                    return Ok(Some(row_index as usize));
+                    return Ok(Some(IndexMatch::Row(row_index as usize)));
                 }
                 RefOrTaggedValue::Ref(ref_) => {
                     let array = Array::from_ref(Arc::clone(&self.array.node.realm), ref_)?;
@@ -103,7 +252,7 @@ impl Index {
                             "Found row index at pos {pos}: {ref_:?}, value = {:?}",
                             value
                         );
-                        return Ok(Some(array.get(0) as usize));
+                        return Ok(Some(IndexMatch::Rows(array)));
                     }
 
                     // Otherwise, go into the sub-index.
@@ -156,8 +305,8 @@ impl Index {
         Self::create_key(&value[offset..])
     }
 
-    fn coerce_to_string(value: &Value) -> Cow<'_, [u8]> {
-        match value {
+    fn coerce_to_string(value: &Value) -> crate::RealmResult<Cow<'_, [u8]>> {
+        Ok(match value {
             Value::String(s) => Cow::Borrowed(s.as_bytes()),
             Value::Int(n) => {
                 let mut str = Vec::with_capacity(std::mem::size_of_val(n));
@@ -175,7 +324,81 @@ impl Index {
                 str.extend_from_slice(&ns.to_le_bytes());
                 Cow::Owned(str)
             }
-            _ => unimplemented!("Unsupported value type for coercion to string: {value:?}"),
-        }
+            _ => {
+                return Err(RealmFileError::Unsupported {
+                    reason: format!("{value:?} can't be looked up in an index"),
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RealmBuilder, tagged};
+
+    #[test]
+    fn test_find_and_contains() {
+        let mut builder = RealmBuilder::new();
+        // "bob" is held by rows 0 and 2, "alice" by row 1.
+        let bob_rows = builder.integers(&[0, 2]);
+        let index = builder.index_leaf(&[(b"alic", tagged(1)), (b"bob\0", bob_rows)]);
+        let (realm, ref_) = builder.build_with_ref(index);
+        let index = Index::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(index.find_first(&Value::from("alice")).unwrap(), Some(1));
+        assert_eq!(index.find_first(&Value::from("bob")).unwrap(), Some(0));
+        assert_eq!(index.find_first(&Value::from("carl")).unwrap(), None);
+
+        assert!(index.contains(&Value::from("alice")).unwrap());
+        assert!(index.contains(&Value::from("bob")).unwrap());
+        assert!(!index.contains(&Value::from("carl")).unwrap());
+        assert!(!index.contains(&Value::from("bobby")).unwrap());
+
+        assert_eq!(index.find_all(&Value::from("alice")).unwrap(), vec![1]);
+        assert_eq!(index.find_all(&Value::from("bob")).unwrap(), vec![0, 2]);
+        assert!(index.find_all(&Value::from("carl")).unwrap().is_empty());
+
+        assert_eq!(index.distinct_row_numbers().unwrap(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_entries() {
+        let key = |bytes: &[u8; 4]| u32::from_be_bytes(*bytes);
+
+        let mut builder = RealmBuilder::new();
+        // "alice" and "alicia" share their first four bytes, so they are
+        // stored in a sub-index, with the rest of the value followed by 'X'.
+        let alic = builder.sub_index_leaf(&[(b"eX\0\0", tagged(1)), (b"iaX\0", tagged(3))]);
+        let bob_rows = builder.integers(&[0, 2]);
+        let index = builder.index_leaf(&[(b"alic", alic), (b"bob\0", bob_rows)]);
+        let (realm, ref_) = builder.build_with_ref(index);
+        let index = Index::from_ref(realm, ref_).unwrap();
+
+        assert_eq!(
+            index.entries().unwrap(),
+            vec![
+                IndexEntry {
+                    keys: vec![key(b"alic"), key(b"eX\0\0")],
+                    row_number: 1
+                },
+                IndexEntry {
+                    keys: vec![key(b"alic"), key(b"iaX\0")],
+                    row_number: 3
+                },
+                IndexEntry {
+                    keys: vec![key(b"bob\0")],
+                    row_number: 0
+                },
+                IndexEntry {
+                    keys: vec![key(b"bob\0")],
+                    row_number: 2
+                },
+            ]
+        );
+        assert_eq!(index.distinct_row_numbers().unwrap(), vec![1, 3, 0]);
+        assert_eq!(index.find_first(&Value::from("alicia")).unwrap(), Some(3));
+        assert_eq!(index.find_first(&Value::from("alice")).unwrap(), Some(1));
     }
 }
@@ -38,6 +38,9 @@
 //! let row_count = table.row_count().unwrap();
 //! let middle_row = table.get_row(row_count / 2).unwrap();
 //!
+//! // To preview a large table, load a few rows spread across the table.
+//! let preview = table.sample(20).unwrap();
+//!
 //! let row = table.get_row(0).unwrap();
 //! dbg!(row);
 //! ```
@@ -89,17 +92,52 @@ mod error;
 mod group;
 mod index;
 mod model;
+mod node;
 mod realm;
 mod spec;
 mod table;
+#[cfg(any(test, feature = "test-utils"))]
+#[doc(hidden)]
+pub mod test_utils;
 mod traits;
 mod utils;
 mod value;
 
 // Export public types.
 pub use column::Column;
-pub use error::{RealmFileError, RealmResult, TableError, TableResult, ValueError, ValueResult};
-pub use group::Group;
-pub use realm::Realm;
-pub use table::{Row, Table};
-pub use value::{Backlink, Link, Value};
+pub use error::{
+    RealmFileError, RealmResult, ResolveError, ResolveResult, TableError, TableResult, ValueError,
+    ValueResult,
+};
+pub use group::{BoundLink, Group, TableKind};
+pub use index::IndexEntry;
+pub use node::{NodeStats, NodeVisit, RawNode, RawSlot};
+pub use realm::{Realm, RealmOptions};
+pub use spec::ColumnKind;
+pub use table::{
+    ColumnAttributes, ColumnSchema, DatabaseSchema, DatabaseTableSchema, Row, SchemaChange, Table,
+    TableSchema,
+};
+pub use value::{Backlink, Link, LinkListRef, ResolvedLink, SubtableRef, TimestampMillis, Value};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_send_sync<T: Send + Sync>() {}
+
+    /// Everything in the Realm file is only ever read, so all public types can
+    /// be shared across threads. This fails to compile if that stops being
+    /// the case.
+    #[test]
+    fn assert_send_sync() {
+        is_send_sync::<Realm>();
+        is_send_sync::<Group>();
+        is_send_sync::<Table>();
+        is_send_sync::<Row<'static>>();
+        is_send_sync::<Value>();
+        is_send_sync::<Box<dyn Column>>();
+        is_send_sync::<LinkListRef>();
+        is_send_sync::<SubtableRef>();
+    }
+}
@@ -1,14 +1,30 @@
 #[doc(hidden)]
 #[macro_export]
 macro_rules! realm_model_field {
-    ($struct:ident, $row:ident, $field:ident = $alias:expr) => {
-        $row.take($alias)
+    (@convert $struct:ident, $row:ident, $value:expr, $label:expr) => {
+        $value
             .ok_or_else(|| $crate::ValueError::MissingField {
-                field: $alias,
+                field: $label.to_string(),
+                target_type: stringify!($struct),
+                remaining_fields: Box::new($row.clone().into_owned()),
+            })?
+            .try_into()
+            .map_err(|error| $crate::ValueError::FieldConversion {
+                field: $label.to_string(),
                 target_type: stringify!($struct),
-                remaining_fields: $row.clone().into_owned(),
+                source: Box::new($crate::ValueError::from(error)),
             })?
-            .try_into()?
+    };
+    ($struct:ident, $row:ident, $field:ident @ $index:literal) => {
+        $crate::realm_model_field!(
+            @convert $struct,
+            $row,
+            $row.take_by_index($index),
+            concat!("#", stringify!($index))
+        )
+    };
+    ($struct:ident, $row:ident, $field:ident = $alias:expr) => {
+        $crate::realm_model_field!(@convert $struct, $row, $row.take($alias), $alias)
     };
     ($struct:ident, $row:ident, $field:ident) => {
         $crate::realm_model_field!($struct, $row, $field = stringify!($field))
@@ -34,10 +50,20 @@ macro_rules! realm_model_field {
 ///
 /// - `String` and `Option<String>`
 /// - `i64` and `Option<i64>`
+/// - `i32`, `u32`, `u64` and `usize`, and their `Option` variants, which fail
+///   with [`ValueError::IntOutOfRange`](crate::ValueError::IntOutOfRange) if
+///   the value doesn't fit
 /// - `bool` and `Option<bool>`
 /// - `f32`
 /// - `f64`
 /// - `chrono::DateTime<Utc>` and `Option<chrono::DateTime<Utc>>`
+/// - `chrono::NaiveDateTime` and `Option<chrono::NaiveDateTime>`
+/// - [`TimestampMillis`](crate::TimestampMillis) and
+///   `Option<TimestampMillis>`, for timestamps as milliseconds since the Unix
+///   epoch
+/// - `std::time::SystemTime` and `Option<SystemTime>`, which fail with
+///   [`ValueError::TimestampOutOfRange`](crate::ValueError::TimestampOutOfRange)
+///   for timestamps before the Unix epoch
 /// - [`Link`](crate::Link), `Option<Link>`, and `Vec<Link>`
 ///
 /// All struct fields must be present, but you may omit columns that you don't
@@ -60,6 +86,24 @@ macro_rules! realm_model_field {
 /// realm_model!(MyStruct => my_struct_field, my_other_struct_field = "realmColumnName");
 /// ```
 ///
+/// # Binding fields by position
+///
+/// Some tables have duplicate or empty column names, which makes looking up
+/// values by name ambiguous. In that case, you can use the `@` syntax to bind
+/// a field to the column at the given index instead (see
+/// [`Row::get_by_index`](crate::Row::get_by_index)):
+///
+/// ```rust
+/// use realm_db_reader::realm_model;
+///
+/// struct MyStruct {
+///     first: String,
+///     second: String,
+/// }
+///
+/// realm_model!(MyStruct => first @ 0, second @ 3);
+/// ```
+///
 /// # Backlinks
 ///
 /// Some tables in Realm can be linked to each other using backlinks. To define
@@ -111,13 +155,13 @@ macro_rules! realm_model_field {
 /// ```
 #[macro_export]
 macro_rules! realm_model {
-    ($struct:ident => $($field:ident$(= $alias:expr)?),*$(; $backlinks:ident)?) => {
+    ($struct:ident => $($field:ident$(@ $index:literal)?$(= $alias:expr)?),*$(; $backlinks:ident)?) => {
         impl<'a> ::core::convert::TryFrom<$crate::Row<'a>> for $struct {
             type Error = $crate::ValueError;
 
             fn try_from(mut row: $crate::Row<'a>) -> $crate::ValueResult<Self> {
                 $(
-                let $field = $crate::realm_model_field!($struct, row, $field$(= $alias)?);
+                let $field = $crate::realm_model_field!($struct, row, $field$(@ $index)?$(= $alias)?);
                 )*
                 $(
                 let $backlinks = row.take_backlinks();
@@ -139,7 +183,7 @@ macro_rules! realm_model {
 #[cfg(test)]
 mod tests {
     use crate::value::ARRAY_VALUE_KEY;
-    use crate::{Backlink, Link, Row, Value};
+    use crate::{Backlink, Link, Row, TimestampMillis, Value};
     use itertools::*;
 
     #[test]
@@ -271,4 +315,130 @@ mod tests {
         assert_eq!(model.link_a, Link::new(12, 5));
         assert_eq!(model.optional_link, None);
     }
+
+    #[test]
+    fn test_floating_point_fields() {
+        struct MyModel {
+            single: f32,
+            double: f64,
+        }
+
+        realm_model!(MyModel => single, double);
+
+        let double = 0.1f64 + 0.2f64;
+        assert_eq!(Value::from(double), Value::Double(double));
+
+        let row = Row::new(
+            vec![1.5f32.into(), double.into()],
+            vec!["single".into(), "double".into()],
+        );
+
+        let model: MyModel = row.try_into().unwrap();
+        assert_eq!(model.single, 1.5);
+        assert_eq!(model.double, double);
+    }
+
+    #[test]
+    fn test_timestamp_fields() {
+        struct MyModel {
+            created: TimestampMillis,
+            updated: Option<TimestampMillis>,
+            deleted: Option<TimestampMillis>,
+        }
+
+        realm_model!(MyModel => created, updated, deleted);
+
+        let created = chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        let created_value = Value::from(created);
+        assert_eq!(created_value.timestamp_millis(), Some(1_700_000_000_123));
+        assert_eq!(Value::Int(1).timestamp_millis(), None);
+
+        let row = Row::new(
+            vec![created_value.clone(), Value::None, created_value.clone()],
+            vec!["created".into(), "deleted".into(), "updated".into()],
+        );
+
+        let model: MyModel = row.try_into().unwrap();
+        assert_eq!(model.created, TimestampMillis(1_700_000_000_123));
+        assert_eq!(model.updated, Some(TimestampMillis(1_700_000_000_123)));
+        assert_eq!(model.deleted, None);
+
+        let naive: chrono::NaiveDateTime = created_value.try_into().unwrap();
+        assert_eq!(naive, created.naive_utc());
+    }
+
+    #[test]
+    fn test_positional_fields() {
+        struct MyModel {
+            first: String,
+            second: String,
+            count: i64,
+        }
+
+        realm_model!(MyModel => first @ 0, second @ 2, count);
+
+        let row = Row::new(
+            vec!["a".into(), 1.into(), "b".into()],
+            vec!["".into(), "count".into(), "".into()],
+        );
+        assert_eq!(row.get(""), Some(&"a".into()));
+        assert_eq!(row.get_by_index(2), Some(&"b".into()));
+        assert_eq!(row.get_by_index(3), None);
+
+        let model: MyModel = row.try_into().unwrap();
+        assert_eq!(model.first, "a");
+        assert_eq!(model.second, "b");
+        assert_eq!(model.count, 1);
+
+        #[derive(Debug)]
+        #[allow(unused)]
+        struct Missing {
+            value: String,
+        }
+
+        realm_model!(Missing => value @ 1);
+
+        let row = Row::new(vec!["a".into()], vec!["".into()]);
+        let error = Missing::try_from(row).unwrap_err();
+        assert!(
+            matches!(&error, crate::ValueError::MissingField { field, .. } if field == "#1"),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn test_field_conversion_error() {
+        #[derive(Debug)]
+        #[allow(unused)]
+        struct MyModel {
+            id: String,
+            count: i64,
+        }
+
+        realm_model!(MyModel => id, count = "realmCount");
+
+        let row = Row::new(
+            vec!["id_value".into(), "not a number".into()],
+            vec!["id".into(), "realmCount".into()],
+        );
+
+        let error = MyModel::try_from(row).unwrap_err();
+        let crate::ValueError::FieldConversion {
+            field,
+            target_type,
+            source,
+        } = error
+        else {
+            panic!("unexpected error: {error:?}");
+        };
+        assert_eq!(field, "realmCount");
+        assert_eq!(target_type, "MyModel");
+        assert!(matches!(
+            *source,
+            crate::ValueError::UnexpectedType {
+                expected: "i64",
+                ..
+            }
+        ));
+    }
 }
@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+
+use crate::RealmFileError;
+use crate::array::RealmRef;
+use crate::realm::{NodeHeader, Realm};
+
+/// A raw node in a Realm file, as returned by [`Realm::node_at`].
+///
+/// This is a low-level escape hatch for reading parts of a Realm file that
+/// aren't modelled by the rest of this library. Every node consists of a header
+/// and a payload of `size` elements, each `width` bits wide. Elements are either
+/// plain integers, or, if the node [has refs](Self::has_refs), references to
+/// other nodes. Integers stored in a node with refs are tagged by setting the
+/// lowest bit.
+#[derive(Clone, Copy)]
+pub struct RawNode<'a> {
+    realm: &'a Realm,
+    ref_: RealmRef,
+    header: NodeHeader,
+    payload: &'a [u8],
+}
+
+impl Debug for RawNode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawNode")
+            .field("ref_", &self.ref_)
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+/// A node encountered while walking the node tree using
+/// [`Realm::walk_nodes`](crate::Realm::walk_nodes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeVisit {
+    /// The offset of the node in the file.
+    pub offset: usize,
+    /// The number of refs followed from the top node to reach this node. The
+    /// top node has depth 0.
+    pub depth: usize,
+    /// The raw flags byte of the node header. See [`RawNode::is_inner_bptree`],
+    /// [`RawNode::has_refs`] and [`RawNode::context_flag`] for the meaning of
+    /// the individual flags.
+    pub flags: u8,
+    /// The width of each element, in bits.
+    pub width: u8,
+    /// The number of elements in the node.
+    pub size: usize,
+    /// The number of bytes of payload following the node header, excluding
+    /// the padding to a multiple of 8 bytes.
+    pub payload_len: usize,
+    /// The offsets of the nodes referenced by this node, in element order.
+    /// Empty elements and tagged integers are skipped.
+    pub children: Vec<usize>,
+}
+
+/// Statistics about the nodes in a Realm file, as returned by
+/// [`Realm::count_nodes`](crate::Realm::count_nodes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeStats {
+    /// The number of nodes reachable from the top node.
+    pub node_count: usize,
+    /// The total number of bytes of payload of these nodes, excluding their
+    /// headers and padding.
+    pub payload_bytes: usize,
+}
+
+/// A single element of a [`RawNode`] that [has refs](RawNode::has_refs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawSlot {
+    /// The element is empty (zero).
+    Null,
+    /// The element is a reference to another node, at the given offset.
+    Ref(usize),
+    /// The element is an integer, with the tag bit already removed.
+    Tagged(u64),
+}
+
+impl<'a> RawNode<'a> {
+    pub(crate) fn new(realm: &'a Realm, offset: usize) -> crate::RealmResult<Self> {
+        if !offset.is_multiple_of(8) {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!("node offset 0x{offset:X} is not 8-byte aligned"),
+            });
+        }
+
+        let ref_ = RealmRef::new(offset);
+        let header = NodeHeader::parse(realm.try_slice(ref_, NodeHeader::SIZE)?)?;
+        let payload = realm.try_slice(ref_ + NodeHeader::SIZE, header.payload_len())?;
+
+        Ok(Self {
+            realm,
+            ref_,
+            header,
+            payload,
+        })
+    }
+
+    /// The offset of this node in the file.
+    pub fn offset(&self) -> usize {
+        self.ref_.to_offset()
+    }
+
+    /// Returns true if the node is an inner B+Tree node.
+    pub fn is_inner_bptree(&self) -> bool {
+        self.header.is_inner_bptree()
+    }
+
+    /// Returns true if the elements of this node are references to other
+    /// nodes (or tagged integers).
+    pub fn has_refs(&self) -> bool {
+        self.header.has_refs()
+    }
+
+    /// Returns true if the context flag is set. The meaning of this flag
+    /// depends on the type of node.
+    pub fn context_flag(&self) -> bool {
+        self.header.context_flag()
+    }
+
+    /// The raw flags byte of the node header.
+    pub fn flags(&self) -> u8 {
+        self.header.flags
+    }
+
+    /// The width of each element, in bits, or in bytes if the [width
+    /// scheme](Self::width_scheme) is 1.
+    pub fn width(&self) -> u8 {
+        self.header.width()
+    }
+
+    /// How the payload of this node is laid out: 0 for bit-packed elements of
+    /// [`width`](Self::width) bits, 1 for elements of [`width`](Self::width)
+    /// bytes (such as short strings), and 2 for [`size`](Self::size) raw bytes
+    /// (such as blobs).
+    pub fn width_scheme(&self) -> u8 {
+        self.header.width_scheme()
+    }
+
+    /// The number of elements in this node.
+    pub fn size(&self) -> usize {
+        self.header.size as usize
+    }
+
+    /// The raw payload of this node.
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Get the integer at the given index.
+    ///
+    /// Returns an error if the index is out of bounds, or the elements of this
+    /// node can't be read as integers, e.g. because it holds raw bytes.
+    pub fn get(&self, index: usize) -> crate::RealmResult<u64> {
+        if index >= self.size() {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "index {index} out of bounds for node at {:?} of size {}",
+                    self.ref_,
+                    self.size()
+                ),
+            });
+        }
+
+        self.header.read_element(self.payload, index)
+    }
+
+    /// Get the element at the given index, interpreted as a reference or a
+    /// tagged integer. See [`RawSlot`].
+    ///
+    /// Returns an error if the index is out of bounds.
+    pub fn slot(&self, index: usize) -> crate::RealmResult<RawSlot> {
+        Ok(match self.get(index)? {
+            0 => RawSlot::Null,
+            value if value & 1 == 0 => RawSlot::Ref(value as usize),
+            value => RawSlot::Tagged(value >> 1),
+        })
+    }
+
+    /// Get the node referenced by the element at the given index. Returns
+    /// `None` if the element is empty or a tagged integer.
+    ///
+    /// Returns an error if the index is out of bounds, or the referenced node
+    /// is invalid.
+    pub fn child(&self, index: usize) -> crate::RealmResult<Option<RawNode<'a>>> {
+        match self.slot(index)? {
+            RawSlot::Ref(offset) => Self::new(self.realm, offset).map(Some),
+            RawSlot::Null | RawSlot::Tagged(_) => Ok(None),
+        }
+    }
+
+    /// Get the offsets of all nodes referenced by this node. Returns an empty
+    /// list if this node [doesn't have refs](Self::has_refs).
+    pub fn child_offsets(&self) -> crate::RealmResult<Vec<usize>> {
+        if !self.has_refs() {
+            return Ok(vec![]);
+        }
+
+        let mut offsets = Vec::new();
+        for index in 0..self.size() {
+            if let RawSlot::Ref(offset) = self.slot(index)? {
+                offsets.push(offset);
+            }
+        }
+
+        Ok(offsets)
+    }
+}
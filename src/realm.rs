@@ -1,12 +1,21 @@
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::ops::Deref;
+#[cfg(any(test, feature = "test-utils"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::{fmt::Debug, path::Path};
 
 use byteorder::{ByteOrder, LittleEndian};
+use lru::LruCache;
+#[cfg(not(target_arch = "wasm32"))]
 use memmap2::Mmap;
 use tracing::instrument;
 
 use crate::array::{Array, RealmRef};
+use crate::node::{NodeStats, NodeVisit, RawNode};
 use crate::traits::Node;
+use crate::utils::read_array_value;
 use crate::{Group, RealmFileError};
 
 /// The header for a Realm file.
@@ -121,7 +130,14 @@ impl NodeHeader {
             });
         }
 
-        Ok(Self { flags, size })
+        let header = Self { flags, size };
+        if header.width_scheme() > 2 {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!("invalid width scheme {}", header.width_scheme()),
+            });
+        }
+
+        Ok(header)
     }
 
     /// Returns true if the node is an inner B+Tree node.
@@ -141,16 +157,53 @@ impl NodeHeader {
         self.flags & 0x20 != 0
     }
 
+    /// The width scheme determines how the payload is laid out:
+    ///
+    /// - 0: `size` elements of [`width`](Self::width) bits each, bit-packed.
+    /// - 1: `size` elements of [`width`](Self::width) bytes each.
+    /// - 2: `size` raw bytes, e.g. for blobs.
     #[inline]
-    fn width_scheme(&self) -> u8 {
+    pub(crate) fn width_scheme(&self) -> u8 {
         (self.flags & 0x18) >> 3
     }
 
+    /// Read the element at the given index from the payload of this node, as
+    /// an unsigned integer, taking the [width scheme](Self::width_scheme) into
+    /// account.
+    ///
+    /// Returns an error if the elements of this node can't be read as
+    /// integers, i.e. for raw bytes, or elements wider than 8 bytes.
+    pub(crate) fn read_element(&self, payload: &[u8], index: usize) -> crate::RealmResult<u64> {
+        let width = self.width();
+        match self.width_scheme() {
+            0 => Ok(read_array_value(payload, width, index)),
+            1 if width == 0 => Ok(0),
+            1 if width <= 8 => {
+                let width = width as usize;
+                let offset = index * width;
+                Ok(LittleEndian::read_uint(
+                    &payload[offset..offset + width],
+                    width,
+                ))
+            }
+            scheme => Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "elements of a node with width scheme {scheme} and width {width} can't be read as integers"
+                ),
+            }),
+        }
+    }
+
     #[inline]
     pub(crate) fn width(&self) -> u8 {
         (1 << (self.flags & 0x07)) >> 1
     }
 
+    /// The number of bytes of payload following the header, i.e. the bytes
+    /// holding the elements. Nodes are padded to a multiple of 8 bytes in the
+    /// file, but the padding is not part of the payload, so this is not
+    /// rounded up: for blobs in particular, that would add the padding to the
+    /// value.
     pub(crate) fn payload_len(&self) -> usize {
         let width = self.width() as u32;
         let num_bytes = match self.width_scheme() {
@@ -168,16 +221,231 @@ impl NodeHeader {
             }
         };
 
-        // Ensure 8-byte alignment
-        // ((num_bytes + 7) & !7) as usize
         num_bytes as usize
     }
 }
 
+/// The bytes backing a [`Realm`]: either a memory-mapped file, or a buffer
+/// that was read into memory. Memory mapping is not available on WebAssembly.
+enum Storage {
+    #[cfg(not(target_arch = "wasm32"))]
+    Mmap(Mmap),
+    Memory(Vec<u8>),
+}
+
+impl Deref for Storage {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Storage::Mmap(mmap) => mmap,
+            Storage::Memory(bytes) => bytes,
+        }
+    }
+}
+
+/// Options for opening a [`Realm`], created using [`Realm::options`].
+///
+/// ```no_run
+/// use realm_db_reader::Realm;
+///
+/// let realm = Realm::options()
+///     .mmap(false)
+///     .open("my-database.realm")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RealmOptions {
+    mmap: bool,
+    sequential: bool,
+    header_cache: usize,
+    skip_unsupported: bool,
+}
+
+// On WebAssembly, all options default to `false`, which could be derived.
+#[cfg_attr(target_arch = "wasm32", allow(clippy::derivable_impls))]
+impl Default for RealmOptions {
+    fn default() -> Self {
+        Self {
+            mmap: cfg!(not(target_arch = "wasm32")),
+            sequential: false,
+            header_cache: 0,
+            skip_unsupported: false,
+        }
+    }
+}
+
+impl RealmOptions {
+    /// Whether to memory-map the file (the default), or read it into memory
+    /// entirely. Reading the file into memory can be preferable on network
+    /// file systems, or platforms where memory mapping is unreliable.
+    ///
+    /// On WebAssembly, where memory mapping is not available, files are always
+    /// read into memory, and this defaults to `false`.
+    pub fn mmap(mut self, mmap: bool) -> Self {
+        self.mmap = mmap;
+        self
+    }
+
+    /// Hint to the operating system that the file will be read sequentially,
+    /// e.g. because you intend to read every table in full. This only has an
+    /// effect when the file is memory-mapped, on Unix platforms.
+    pub fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+
+    /// Keep up to the given number of decoded node headers in memory, evicting
+    /// the least recently used ones. Reading a value parses the headers of
+    /// all B+Tree nodes on the way to it, so this saves parsing the same
+    /// inner nodes over and over when scanning large tables. The file is never
+    /// modified, so cached headers never need to be invalidated.
+    ///
+    /// Defaults to 0, which disables the cache.
+    pub fn header_cache(mut self, capacity: usize) -> Self {
+        self.header_cache = capacity;
+        self
+    }
+
+    /// Read columns of types this library doesn't support yet as
+    /// [`Value::Unsupported`](crate::Value::Unsupported) placeholders, rather
+    /// than failing to build any table that has such a column. The other
+    /// columns of these tables can then still be read, e.g. to dump as much of
+    /// a file as possible.
+    ///
+    /// Defaults to `false`. Columns of unknown types, which indicate a corrupt
+    /// file, are always an error.
+    pub fn skip_unsupported(mut self, skip_unsupported: bool) -> Self {
+        self.skip_unsupported = skip_unsupported;
+        self
+    }
+
+    /// Open a Realm database using these options. See [`Realm::open`].
+    #[instrument(level = "debug")]
+    pub fn open(&self, path: impl AsRef<Path> + Debug) -> crate::RealmResult<Realm> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.mmap {
+            let file = std::fs::File::open(path)?;
+            let mmap = unsafe { Mmap::map(&file)? };
+            #[cfg(unix)]
+            if self.sequential {
+                mmap.advise(memmap2::Advice::Sequential)?;
+            }
+
+            return Realm::from_storage(Storage::Mmap(mmap), self);
+        }
+
+        Realm::from_storage(Storage::Memory(std::fs::read(path)?), self)
+    }
+
+    /// Load a Realm database from the given bytes using these options. See
+    /// [`Realm::from_bytes`]. The options for reading the file are ignored.
+    pub fn from_bytes(&self, bytes: impl Into<Vec<u8>>) -> crate::RealmResult<Realm> {
+        Realm::from_storage(Storage::Memory(bytes.into()), self)
+    }
+}
+
 /// A reference to a Realm database.
+///
+/// # Closing the file
+///
+/// The file is kept open (and, by default, memory-mapped) for as long as the
+/// [`Realm`] is alive. Once turned into a [`Group`], the realm is shared by the
+/// group and everything obtained from it, such as tables and lazily-read
+/// values, so the file is only closed when the last of those is dropped. On
+/// Windows in particular, this means the file can't be moved or deleted until
+/// then.
+///
+/// Use [`Group::realm_reference_count`] to find out how many references are
+/// still alive, and [`Group::into_realm`] to get the realm back once they are
+/// all gone.
 pub struct Realm {
-    mmap: Mmap,
+    storage: Storage,
     pub(crate) hdr: Header,
+    /// Decoded node headers, see [`RealmOptions::header_cache`].
+    header_cache: Box<dyn CacheHeaders>,
+    /// See [`RealmOptions::skip_unsupported`].
+    skip_unsupported: bool,
+}
+
+/// Looks up node headers in a [`HeaderCache`].
+///
+/// The realm only holds its cache as a trait object. The cache is kept behind
+/// a mutex, and values such as [`LinkListRef`](crate::LinkListRef) hold the
+/// realm, so with a concrete type, `clippy::mutable_key_type` would reject
+/// every map keyed by [`Value`](crate::Value), including in code using this
+/// library. Cached headers never affect how values are hashed or compared.
+trait CacheHeaders: Send + Sync {
+    /// Get the cached header of the node at the given ref, or parse it using
+    /// `parse` and cache it.
+    fn get_or_parse(
+        &self,
+        ref_: RealmRef,
+        parse: &dyn Fn() -> crate::RealmResult<NodeHeader>,
+    ) -> crate::RealmResult<NodeHeader>;
+
+    /// The number of node headers parsed so far.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn parses(&self) -> usize;
+}
+
+struct HeaderCache {
+    /// The least recently used headers, or `None` if the cache is disabled.
+    headers: Option<Mutex<LruCache<RealmRef, NodeHeader>>>,
+    /// The number of node headers parsed, for checking the cache.
+    #[cfg(any(test, feature = "test-utils"))]
+    parses: AtomicUsize,
+}
+
+impl HeaderCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            headers: NonZeroUsize::new(capacity)
+                .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            #[cfg(any(test, feature = "test-utils"))]
+            parses: AtomicUsize::new(0),
+        }
+    }
+
+    fn parse(
+        &self,
+        parse: &dyn Fn() -> crate::RealmResult<NodeHeader>,
+    ) -> crate::RealmResult<NodeHeader> {
+        #[cfg(any(test, feature = "test-utils"))]
+        self.parses.fetch_add(1, Ordering::Relaxed);
+
+        parse()
+    }
+}
+
+impl CacheHeaders for HeaderCache {
+    fn get_or_parse(
+        &self,
+        ref_: RealmRef,
+        parse: &dyn Fn() -> crate::RealmResult<NodeHeader>,
+    ) -> crate::RealmResult<NodeHeader> {
+        let Some(headers) = &self.headers else {
+            return self.parse(parse);
+        };
+
+        // The cache only ever holds correctly parsed headers, so it's still
+        // usable if another thread panicked while holding the lock.
+        let mut headers = headers.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(header) = headers.get(&ref_) {
+            return Ok(*header);
+        }
+
+        let header = self.parse(parse)?;
+        headers.put(ref_, header);
+
+        Ok(header)
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    fn parses(&self) -> usize {
+        self.parses.load(Ordering::Relaxed)
+    }
 }
 
 impl Debug for Realm {
@@ -187,23 +455,35 @@ impl Debug for Realm {
 }
 
 impl Realm {
-    /// Open a Realm database.
+    /// Open a Realm database. The file is memory-mapped; use
+    /// [`Realm::options`] to change how the file is read.
     ///
     /// Returns an error if:
     /// - The header is invalid.
-    /// - The file is encrypted.
     /// - The file format version is not supported.
+    ///
+    /// Encrypted files can be opened, so they can be detected using
+    /// [`Realm::is_encrypted`], but reading their data is not supported.
     #[instrument(level = "debug")]
     pub fn open(path: impl AsRef<Path> + Debug) -> crate::RealmResult<Self> {
-        let file = std::fs::File::open(path)?;
-        let mmap = unsafe { Mmap::map(&file)? };
-        let hdr = Header::parse(&mmap[..Header::SIZE])?;
+        RealmOptions::default().open(path)
+    }
 
-        if hdr.is_encrypted() {
-            return Err(RealmFileError::Unsupported {
-                reason: "Encrypted Realm files are not supported".to_string(),
-            });
-        }
+    /// Get the [`RealmOptions`] to customize how a Realm database is opened.
+    pub fn options() -> RealmOptions {
+        RealmOptions::default()
+    }
+
+    /// Load a Realm database from the given bytes, e.g. a file that was
+    /// already read into memory.
+    ///
+    /// Returns an error in the same cases as [`Realm::open`].
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> crate::RealmResult<Self> {
+        RealmOptions::default().from_bytes(bytes)
+    }
+
+    fn from_storage(storage: Storage, options: &RealmOptions) -> crate::RealmResult<Self> {
+        let hdr = Header::parse(&storage)?;
 
         if hdr.file_format_version() != (9, 9) {
             return Err(RealmFileError::Unsupported {
@@ -215,15 +495,45 @@ impl Realm {
             });
         }
 
-        Ok(Realm { mmap, hdr })
+        Ok(Realm {
+            storage,
+            hdr,
+            header_cache: Box::new(HeaderCache::new(options.header_cache)),
+            skip_unsupported: options.skip_unsupported,
+        })
+    }
+
+    /// Whether the file is encrypted, according to its header. The data in an
+    /// encrypted file can't be read, so [`into_group`](Self::into_group)
+    /// returns an error for these files.
+    pub fn is_encrypted(&self) -> bool {
+        self.hdr.is_encrypted()
+    }
+
+    /// Whether columns of unsupported types are read as placeholders, see
+    /// [`RealmOptions::skip_unsupported`].
+    pub(crate) fn skip_unsupported(&self) -> bool {
+        self.skip_unsupported
     }
 
     pub(crate) fn slice(&self, ref_: RealmRef, len: usize) -> &[u8] {
         let o = ref_.to_offset();
-        if o + len > self.mmap.len() {
+        if o + len > self.storage.len() {
             panic!("offset 0x{o:X} outside file");
         }
-        &self.mmap[o..o + len]
+        &self.storage[o..o + len]
+    }
+
+    /// Like [`slice`](Self::slice), but returns an error instead of panicking
+    /// if the requested range is outside the file.
+    pub(crate) fn try_slice(&self, ref_: RealmRef, len: usize) -> crate::RealmResult<&[u8]> {
+        let o = ref_.to_offset();
+        match o.checked_add(len) {
+            Some(end) if end <= self.storage.len() => Ok(&self.storage[o..end]),
+            _ => Err(RealmFileError::InvalidRealmFile {
+                reason: format!("offset 0x{o:X} outside file"),
+            }),
+        }
     }
 
     pub(crate) fn payload(&self, ref_: RealmRef, payload_len: usize) -> &[u8] {
@@ -232,20 +542,155 @@ impl Realm {
     }
 
     pub(crate) fn header(&self, ref_: RealmRef) -> crate::RealmResult<NodeHeader> {
-        let bytes = self.slice(ref_, NodeHeader::SIZE);
-        NodeHeader::parse(bytes)
+        self.header_cache.get_or_parse(ref_, &|| {
+            NodeHeader::parse(self.slice(ref_, NodeHeader::SIZE))
+        })
+    }
+
+    /// The number of node headers parsed so far, for measuring the effect of
+    /// [`RealmOptions::header_cache`].
+    #[cfg(any(test, feature = "test-utils"))]
+    #[doc(hidden)]
+    pub fn header_parses(&self) -> usize {
+        self.header_cache.parses()
     }
 
     pub(crate) fn top_ref(&self) -> RealmRef {
         self.hdr.current_top_ref()
     }
 
+    /// Get the raw node at the given offset in the file. This is intended for
+    /// reading data that isn't supported by the rest of this library. See
+    /// [`RawNode`] for details.
+    ///
+    /// Returns an error if the offset is not 8-byte aligned, or does not point
+    /// to a valid node.
+    pub fn node_at(&self, ref_offset: usize) -> crate::RealmResult<RawNode<'_>> {
+        RawNode::new(self, ref_offset)
+    }
+
+    /// Get the raw node the current top ref points to. This is the root node
+    /// of the [`Group`].
+    pub fn top_node(&self) -> crate::RealmResult<RawNode<'_>> {
+        RawNode::new(self, self.top_ref().to_offset())
+    }
+
+    /// The offset of the top node in the file, i.e. the currently active top
+    /// ref from the file header. A value of 0 means the file holds no data.
+    pub fn top_ref_offset(&self) -> usize {
+        self.top_ref().to_offset()
+    }
+
+    /// Walk the tree of nodes in this file, starting at the [top
+    /// node](Self::top_node), calling `visitor` once for every node in
+    /// depth-first order, parents before their children.
+    ///
+    /// Nodes referenced more than once are only visited the first time they
+    /// are encountered, so a corrupted file with a cycle in it will not cause
+    /// an endless loop.
+    ///
+    /// Returns an error if any of the nodes is invalid. Nodes visited before
+    /// the invalid one have already been passed to `visitor`.
+    #[instrument(level = "debug", skip(self, visitor))]
+    pub fn walk_nodes(&self, visitor: &mut impl FnMut(NodeVisit)) -> crate::RealmResult<()> {
+        let top_ref = self.top_ref_offset();
+        if top_ref == 0 {
+            return Ok(());
+        }
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![(top_ref, 0)];
+        while let Some((offset, depth)) = stack.pop() {
+            if !visited.insert(offset) {
+                continue;
+            }
+
+            let node = self.node_at(offset)?;
+            let children = node.child_offsets()?;
+            stack.extend(children.iter().rev().map(|child| (*child, depth + 1)));
+
+            visitor(NodeVisit {
+                offset,
+                depth,
+                flags: node.flags(),
+                width: node.width(),
+                size: node.size(),
+                payload_len: node.payload().len(),
+                children,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The size of the file in bytes, including any space that is not in use.
+    pub fn file_size(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Count the nodes reachable from the [top node](Self::top_node), along
+    /// with the total size of their payloads, using
+    /// [`walk_nodes`](Self::walk_nodes). Comparing these to the
+    /// [file size](Self::file_size) gives an idea of how much of the file is
+    /// unused, e.g. because it holds data of older versions that has not been
+    /// compacted yet.
+    ///
+    /// Returns an error if any of the nodes is invalid.
+    pub fn count_nodes(&self) -> crate::RealmResult<NodeStats> {
+        let mut stats = NodeStats::default();
+        self.walk_nodes(&mut |visit| {
+            stats.node_count += 1;
+            stats.payload_bytes += visit.payload_len;
+        })?;
+
+        Ok(stats)
+    }
+
     /// Create a reference to the [`Group`] in this Realm database. The
     /// [`Group`] is the main entrypoint for interacting with the tables.
+    ///
+    /// If the file holds no data yet, i.e. its [top ref](Self::top_ref_offset)
+    /// is 0, the group has no tables.
     pub fn into_group(self) -> crate::RealmResult<Group> {
         let ref_ = self.top_ref();
+
+        self.into_group_at(ref_.to_offset())
+    }
+
+    /// Create a reference to the [`Group`] whose top node is at the given
+    /// offset, rather than the one the file header points to. This is useful
+    /// for recovering data from a file whose current top ref is damaged, when
+    /// the offset of an earlier, intact version of the group is known.
+    ///
+    /// An offset of 0 results in a group without any tables, like for a file
+    /// that holds no data yet.
+    ///
+    /// Returns an error if the file is [encrypted](Self::is_encrypted), or if
+    /// the offset is not 8-byte aligned, is outside the file, or does not
+    /// point to a node that can be the top of a group.
+    #[instrument(level = "debug")]
+    pub fn into_group_at(self, top_ref_offset: usize) -> crate::RealmResult<Group> {
+        if self.is_encrypted() {
+            return Err(RealmFileError::Unsupported {
+                reason: "file is encrypted, and encrypted Realm files are not supported"
+                    .to_string(),
+            });
+        }
+
+        // Offset 0 is the file header, so no node can be stored there.
+        if top_ref_offset == 0 {
+            return Ok(Group::empty(Arc::new(self)));
+        }
+
+        let node = self.node_at(top_ref_offset)?;
+        if !node.has_refs() || node.size() < 2 {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!("node at 0x{top_ref_offset:X} is not the top of a group"),
+            });
+        }
+
         let realm = Arc::new(self);
-        let array = Array::from_ref(Arc::clone(&realm), ref_)?;
+        let array = Array::from_ref(Arc::clone(&realm), RealmRef::new(top_ref_offset))?;
 
         Group::build(array)
     }
@@ -301,6 +746,34 @@ impl RealmNode {
 #[cfg(test)]
 mod tests {
     use crate::realm::NodeHeader;
+    use crate::test_utils::RealmBuilder;
+    use crate::{NodeStats, Realm, RealmFileError, TableError, Value};
+
+    #[test]
+    #[ignore = "writes a sparse file larger than 4 GiB"]
+    fn test_refs_beyond_4_gib() {
+        let mut builder = RealmBuilder::with_offset(1 << 32);
+        let values = builder.integers(&[1, u64::MAX, 3]);
+        let table = builder.table(&[0], &["value"], &[0], &[], &[values]);
+        let top = builder.group(&[("class_Numbers", table)]);
+        assert!(values > u32::MAX as u64);
+
+        let path = std::env::temp_dir().join(format!("large-{}.realm", std::process::id()));
+        builder.write(top, &path).unwrap();
+        let read = || {
+            let realm = Realm::open(&path).unwrap();
+            let size = realm.node_at(values as usize).unwrap().size();
+            let table = realm.into_group().unwrap().get_table(0).unwrap();
+
+            (size, table.column_values(0).unwrap())
+        };
+        let result = std::panic::catch_unwind(read);
+        std::fs::remove_file(&path).unwrap();
+
+        let (size, values) = result.unwrap();
+        assert_eq!(size, 3);
+        assert_eq!(values, [Value::Int(1), Value::Int(-1), Value::Int(3)]);
+    }
 
     #[test]
     fn test_node_header() {
@@ -347,4 +820,162 @@ mod tests {
         // 32 bits -> 4 bytes -> *10 = 40 -> align to 8
         assert_eq!(header.payload_len(), 40);
     }
+
+    #[test]
+    fn test_read_element_width_schemes() {
+        // Width scheme 0: bit-packed, 4 bits per element.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x03, 0, 0, 2]).unwrap();
+        assert_eq!(header.read_element(&[0x21], 1).unwrap(), 2);
+
+        // Width scheme 1: 2 bytes per element.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x0A, 0, 0, 2]).unwrap();
+        assert_eq!(header.width_scheme(), 1);
+        assert_eq!(header.read_element(&[1, 0, 2, 1], 1).unwrap(), 0x0102);
+
+        // Width scheme 1 with elements too wide to be integers.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x0D, 0, 0, 1]).unwrap();
+        assert!(header.read_element(&[0; 16], 0).is_err());
+
+        // Width scheme 2: raw bytes.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x10, 0, 0, 3]).unwrap();
+        assert!(header.read_element(b"abc", 0).is_err());
+
+        // Width scheme 3 does not exist.
+        assert!(NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x18, 0, 0, 3]).is_err());
+    }
+
+    #[test]
+    fn test_payload_len() {
+        // Width scheme 0: 9 elements of 1 bit take 2 bytes.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x01, 0, 0, 9]).unwrap();
+        assert_eq!(header.payload_len(), 2);
+
+        // Width scheme 1: 3 elements of 2 bytes.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x0A, 0, 0, 3]).unwrap();
+        assert_eq!(header.payload_len(), 6);
+
+        // Width scheme 2: the size is the number of bytes, without padding,
+        // up to the largest size a header can hold.
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x10, 0, 0, 13]).unwrap();
+        assert_eq!(header.payload_len(), 13);
+        let header = NodeHeader::parse(&[0x41, 0x41, 0x41, 0x41, 0x10, 0xFF, 0xFF, 0xFF]).unwrap();
+        assert_eq!(header.payload_len(), 0xFF_FFFF);
+    }
+
+    #[test]
+    fn test_empty_realm() {
+        let mut buf = vec![0; 24];
+        buf[16..20].copy_from_slice(b"T-DB");
+        buf[20..22].copy_from_slice(&[9, 9]);
+
+        let realm = Realm::from_bytes(buf).unwrap();
+        assert_eq!(realm.top_ref_offset(), 0);
+
+        let group = realm.into_group().unwrap();
+        assert_eq!(group.table_count(), 0);
+        assert!(group.get_table_names().is_empty());
+        assert!(matches!(
+            group.get_table(0),
+            Err(TableError::TableNotFound { .. })
+        ));
+        assert!(group.into_realm().is_some());
+    }
+
+    #[test]
+    fn test_walk_nodes() {
+        let mut buf = vec![0; 24];
+        buf[16..20].copy_from_slice(b"T-DB");
+        buf[20..22].copy_from_slice(&[9, 9]);
+        // 24: leaf with a single 8-bit element.
+        buf.extend_from_slice(&[
+            0x41, 0x41, 0x41, 0x41, 0x04, 0, 0, 1, 42, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        // 40: node with refs, pointing at the leaf twice, with a tagged value
+        // and an empty element in between.
+        buf.extend_from_slice(&[0x41, 0x41, 0x41, 0x41, 0x46, 0, 0, 4]);
+        for value in [24u32, 7, 0, 24] {
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+        // 64: top node, pointing at the node above.
+        buf.extend_from_slice(&[
+            0x41, 0x41, 0x41, 0x41, 0x44, 0, 0, 1, 40, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        buf[0] = 64;
+
+        let realm = Realm::from_bytes(buf).unwrap();
+        assert_eq!(realm.top_ref_offset(), 64);
+
+        let mut visits = Vec::new();
+        realm.walk_nodes(&mut |visit| visits.push(visit)).unwrap();
+
+        assert_eq!(
+            visits
+                .iter()
+                .map(|visit| (visit.offset, visit.depth, visit.size))
+                .collect::<Vec<_>>(),
+            vec![(64, 0, 1), (40, 1, 4), (24, 2, 1)]
+        );
+        assert_eq!(visits[0].children, vec![40]);
+        assert_eq!(visits[1].children, vec![24, 24]);
+        assert_eq!(visits[1].width, 32);
+        assert_eq!(visits[1].flags, 0x46);
+        assert!(visits[2].children.is_empty());
+        assert_eq!(visits[1].payload_len, 16);
+
+        // The leaf is only counted once, even though it is referenced twice.
+        assert_eq!(realm.file_size(), 80);
+        assert_eq!(
+            realm.count_nodes().unwrap(),
+            NodeStats {
+                node_count: 3,
+                payload_bytes: 1 + 16 + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_into_group_at() {
+        let mut builder = RealmBuilder::new();
+        let old_data = builder.integers(&[1]);
+        let old_table = builder.table(&[0], &["value"], &[0], &[], &[old_data]);
+        let old_top = builder.group(&[("class_Old", old_table)]);
+        let new_top = builder.group(&[]);
+        let leaf = builder.integers(&[1]);
+        let realm = builder.build(new_top);
+
+        let realm_bytes = realm.storage.to_vec();
+        let group = realm.into_group_at(old_top as usize).unwrap();
+        assert_eq!(group.get_table_names(), ["class_Old"]);
+
+        for (offset, expected) in [
+            (old_top as usize + 4, "not 8-byte aligned"),
+            (realm_bytes.len() + 8, "outside file"),
+            (leaf as usize, "not the top of a group"),
+        ] {
+            let realm = Realm::from_bytes(realm_bytes.clone()).unwrap();
+            let error = realm.into_group_at(offset).unwrap_err();
+            assert!(error.to_string().contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_is_encrypted() {
+        let mut builder = RealmBuilder::new();
+        let top = builder.group(&[]);
+        builder.file_flags(0x80);
+        let realm = builder.build(top);
+        assert!(realm.is_encrypted());
+
+        let error = realm.into_group().unwrap_err();
+        assert!(
+            matches!(&error, RealmFileError::Unsupported { reason } if reason.contains("encrypted")),
+            "{error:?}"
+        );
+
+        let mut builder = RealmBuilder::new();
+        let top = builder.group(&[]);
+        let realm = builder.build(top);
+        assert!(!realm.is_encrypted());
+        assert!(realm.into_group().is_ok());
+    }
 }
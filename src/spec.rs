@@ -2,29 +2,48 @@ use crate::array::FromU64;
 
 /// The type of value contained in a column of a Realm table.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
-#[allow(unused)]
 pub(crate) enum ColumnType {
-    Int = 0,
-    Bool = 1,
-    String = 2,
-    OldStringEnum = 3, // double refs
-    Binary = 4,
-    Table = 5,
-    OldMixed = 6,
-    OldDateTime = 7,
-    Timestamp = 8,
-    Float = 9,
-    Double = 10,
-    Reserved4 = 11, // Decimal
-    Link = 12,
-    LinkList = 13,
-    BackLink = 14,
+    Int,
+    Bool,
+    String,
+    OldStringEnum, // double refs
+    Binary,
+    Table,
+    OldMixed,
+    OldDateTime,
+    Timestamp,
+    Float,
+    Double,
+    Reserved4, // Decimal
+    Link,
+    LinkList,
+    BackLink,
+    /// A column type that doesn't exist in the file format, holding the value
+    /// from the file. Only files of a single format version are read, so this
+    /// means the file is corrupt.
+    Unknown(u64),
 }
 
 impl FromU64 for ColumnType {
     fn from_u64(value: u64) -> Self {
-        unsafe { std::mem::transmute(value as u8) }
+        match value {
+            0 => ColumnType::Int,
+            1 => ColumnType::Bool,
+            2 => ColumnType::String,
+            3 => ColumnType::OldStringEnum,
+            4 => ColumnType::Binary,
+            5 => ColumnType::Table,
+            6 => ColumnType::OldMixed,
+            7 => ColumnType::OldDateTime,
+            8 => ColumnType::Timestamp,
+            9 => ColumnType::Float,
+            10 => ColumnType::Double,
+            11 => ColumnType::Reserved4,
+            12 => ColumnType::Link,
+            13 => ColumnType::LinkList,
+            14 => ColumnType::BackLink,
+            _ => ColumnType::Unknown(value),
+        }
     }
 }
 
@@ -44,3 +63,63 @@ impl ColumnType {
         }
     }
 }
+
+/// The kind of value stored in a column, as reported by
+/// [`Column::column_type`](crate::Column::column_type) and
+/// [`Table::schema`](crate::Table::schema).
+///
+/// This is the public counterpart of the column types used in the Realm file
+/// itself. Some kinds are only used by newer versions of the file format, and
+/// are never reported for the files this library can read; they are included
+/// so that matching on this enum does not need to change when support for
+/// those versions is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ColumnKind {
+    /// Integer values, see [`Value::Int`](crate::Value::Int).
+    Int,
+    /// Boolean values, see [`Value::Bool`](crate::Value::Bool).
+    Bool,
+    /// String values, see [`Value::String`](crate::Value::String).
+    String,
+    /// Binary blobs, see [`Value::Binary`](crate::Value::Binary).
+    Binary,
+    /// Subtables, see [`Value::Table`](crate::Value::Table).
+    Table,
+    /// Values of mixed types.
+    Mixed,
+    /// Timestamps, see [`Value::Timestamp`](crate::Value::Timestamp).
+    Timestamp,
+    /// Single-precision floating-point values, see
+    /// [`Value::Float`](crate::Value::Float).
+    Float,
+    /// Double-precision floating-point values, see
+    /// [`Value::Double`](crate::Value::Double).
+    Double,
+    /// Decimal values.
+    Decimal,
+    /// Object IDs. Not used by file format 9.9.
+    ObjectId,
+    /// UUIDs. Not used by file format 9.9.
+    Uuid,
+    /// Links to a single row, see [`Value::Link`](crate::Value::Link).
+    Link,
+    /// Lists of links, see [`Value::LinkList`](crate::Value::LinkList).
+    LinkList,
+    /// Backlinks, see [`Value::BackLink`](crate::Value::BackLink).
+    BackLink,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_type_from_u64() {
+        assert_eq!(ColumnType::from_u64(0), ColumnType::Int);
+        assert_eq!(ColumnType::from_u64(11), ColumnType::Reserved4);
+        assert_eq!(ColumnType::from_u64(14), ColumnType::BackLink);
+        assert_eq!(ColumnType::from_u64(15), ColumnType::Unknown(15));
+        assert_eq!(ColumnType::from_u64(256), ColumnType::Unknown(256));
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int64Array, StringArray,
+    TimestampNanosecondArray, UInt64Array,
+};
+use arrow::datatypes::{Field, Schema};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use tracing::instrument;
+
+use crate::RealmFileError;
+use crate::column::Column;
+use crate::spec::ColumnKind;
+use crate::table::Table;
+use crate::value::Value;
+
+impl Table {
+    /// Read all rows of this table into an Arrow [`RecordBatch`], with one
+    /// Arrow column per table column, e.g. to hand the table to Polars or
+    /// DataFusion. Columns are read sequentially, as with
+    /// [`column_values`](Self::column_values).
+    ///
+    /// Columns are mapped as follows:
+    ///
+    /// - Integers to `Int64`, booleans to `Boolean`, and strings to `Utf8`.
+    /// - Floats to `Float32`, and doubles to `Float64`.
+    /// - Timestamps to `Timestamp(Nanosecond, "UTC")`.
+    /// - Binary values to `Binary`.
+    /// - Links to `UInt64`, holding the number of the row in the target table.
+    ///
+    /// Link list and subtable columns can't be represented as a single Arrow
+    /// array of scalars, so they are left out entirely. Backlinks are left out
+    /// as well, as their columns are unnamed, and so are columns of
+    /// [unsupported](crate::RealmOptions::skip_unsupported) types.
+    ///
+    /// Returns [`RealmFileError::Unsupported`] if a timestamp is out of the
+    /// range that nanoseconds since the Unix epoch can represent (roughly the
+    /// years 1677 to 2262).
+    ///
+    /// This method is only available with the `arrow` feature.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn to_record_batch(&self) -> crate::RealmResult<RecordBatch> {
+        let mut fields = Vec::new();
+        let mut arrays = Vec::new();
+        for column in self.header.get_columns() {
+            let Some(name) = column.name() else {
+                continue;
+            };
+            let Some(array) = arrow_array(column.as_ref(), name)? else {
+                continue;
+            };
+
+            let nullable = column.nullable() || column.column_type() == ColumnKind::Link;
+            fields.push(Field::new(name, array.data_type().clone(), nullable));
+            arrays.push(array);
+        }
+
+        // The row count is needed for tables without any columns Arrow can
+        // represent.
+        let options = RecordBatchOptions::new().with_row_count(Some(self.row_count()?));
+
+        RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options).map_err(
+            |error| RealmFileError::InvalidRealmFile {
+                reason: format!("failed to build record batch: {error}"),
+            },
+        )
+    }
+}
+
+/// Read all values of the given column into an Arrow array, or `None` if the
+/// column can't be represented as one. See [`Table::to_record_batch`].
+fn arrow_array(column: &dyn Column, name: &str) -> crate::RealmResult<Option<ArrayRef>> {
+    let values = column.get_all()?;
+    if values
+        .iter()
+        .any(|value| matches!(value, Value::Unsupported { .. }))
+    {
+        return Ok(None);
+    }
+
+    let array: ArrayRef = match column.column_type() {
+        ColumnKind::Int => Arc::new(Int64Array::from_iter(values.iter().map(
+            |value| match value {
+                Value::Int(value) => Some(*value),
+                _ => None,
+            },
+        ))),
+        ColumnKind::Bool => Arc::new(BooleanArray::from_iter(values.iter().map(
+            |value| match value {
+                Value::Bool(value) => Some(*value),
+                _ => None,
+            },
+        ))),
+        ColumnKind::String => Arc::new(StringArray::from_iter(values.iter().map(
+            |value| match value {
+                Value::String(value) | Value::OldStringEnum(value) => Some(value.as_str()),
+                _ => None,
+            },
+        ))),
+        ColumnKind::Binary => Arc::new(BinaryArray::from_iter(values.iter().map(
+            |value| match value {
+                Value::Binary(value) => Some(value.as_slice()),
+                _ => None,
+            },
+        ))),
+        ColumnKind::Float => Arc::new(Float32Array::from_iter(values.iter().map(
+            |value| match value {
+                Value::Float(value) => Some(*value),
+                _ => None,
+            },
+        ))),
+        ColumnKind::Double => Arc::new(Float64Array::from_iter(values.iter().map(
+            |value| match value {
+                Value::Double(value) => Some(*value),
+                _ => None,
+            },
+        ))),
+        ColumnKind::Timestamp => {
+            let nanos = values
+                .iter()
+                .map(|value| match value {
+                    Value::Timestamp(value) => value
+                        .timestamp_nanos_opt()
+                        .map(Some)
+                        .ok_or_else(|| RealmFileError::Unsupported {
+                            reason: format!(
+                                "timestamp {value} in column '{name}' can't be represented in nanoseconds"
+                            ),
+                        }),
+                    _ => Ok(None),
+                })
+                .collect::<crate::RealmResult<Vec<_>>>()?;
+
+            Arc::new(TimestampNanosecondArray::from(nanos).with_timezone("UTC"))
+        }
+        ColumnKind::Link => Arc::new(UInt64Array::from_iter(values.iter().map(
+            |value| match value {
+                Value::Link(link) => Some(link.row_number as u64),
+                _ => None,
+            },
+        ))),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(array))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::{DataType, Int64Type, TimeUnit, TimestampNanosecondType};
+
+    use crate::test_utils::{RealmBuilder, tagged};
+
+    #[test]
+    fn test_to_record_batch() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.nullable_short_strings(&[Some("alice"), None]);
+        let ages = builder.integers(&[31, 42]);
+        let seconds = builder.integers(&[u64::MAX, 1_700_000_000, u64::MAX]);
+        let nanoseconds = builder.integers(&[5, 0]);
+        let born = builder.refs(&[seconds, nanoseconds]);
+        let friends = builder.refs(&[0, 0]);
+        let table = builder.table(
+            &[2, 0, 8, 13],
+            &["name", "age", "born", "friends"],
+            &[16, 0, 16, 0],
+            &[tagged(0)],
+            &[names, ages, born, friends],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let batch = table.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let schema = batch.schema();
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("name", DataType::Utf8),
+                ("age", DataType::Int64),
+                (
+                    "born",
+                    DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+                ),
+            ]
+        );
+
+        let names = batch.column(0).as_string::<i32>();
+        assert_eq!(names.value(0), "alice");
+        assert!(names.is_null(1));
+        assert_eq!(
+            batch.column(1).as_primitive::<Int64Type>().values(),
+            &[31, 42]
+        );
+        let born = batch.column(2).as_primitive::<TimestampNanosecondType>();
+        assert_eq!(born.value(0), 1_700_000_000_000_000_005);
+        assert!(born.is_null(1));
+    }
+}
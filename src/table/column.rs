@@ -2,8 +2,15 @@ use std::fmt::Debug;
 
 use crate::array::FromU64;
 
-#[derive(Copy, Clone)]
-pub(crate) struct ColumnAttributes(u64);
+/// The attributes of a column, as returned by
+/// [`Column::attributes`](crate::Column::attributes).
+///
+/// Some attributes are only used by newer versions of the file format than the
+/// one supported by this library, and are never set for the files it can read.
+/// They are included so that code inspecting attributes does not need to change
+/// when support for those versions is added.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnAttributes(u64);
 
 impl ColumnAttributes {
     const INDEXED: u64 = 1 << 0;
@@ -11,30 +18,87 @@ impl ColumnAttributes {
     const RESERVED: u64 = 1 << 2;
     const STRONG_LINKS: u64 = 1 << 3;
     const NULLABLE: u64 = 1 << 4;
+    const LIST: u64 = 1 << 5;
+    const DICTIONARY: u64 = 1 << 6;
+    const SET: u64 = 1 << 7;
+    const FULLTEXT_INDEXED: u64 = 1 << 8;
 
     pub(crate) fn new(attributes: u64) -> Self {
         Self(attributes)
     }
 
-    pub(crate) fn is_indexed(&self) -> bool {
+    /// The raw attribute bits, as stored in the file.
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Whether the column has a search index.
+    pub fn is_indexed(&self) -> bool {
         self.0 & Self::INDEXED != 0
     }
 
-    pub(crate) fn is_unique(&self) -> bool {
+    /// Whether the values in the column must be unique.
+    pub fn is_unique(&self) -> bool {
         self.0 & Self::UNIQUE != 0
     }
 
-    pub(crate) fn is_reserved(&self) -> bool {
+    /// Whether the reserved bit is set. This bit has no meaning.
+    pub fn is_reserved(&self) -> bool {
         self.0 & Self::RESERVED != 0
     }
 
-    pub(crate) fn is_strong_links(&self) -> bool {
+    /// Whether the links in this (link or link list) column are strong, i.e.
+    /// the target rows are deleted along with the origin row.
+    pub fn is_strong_links(&self) -> bool {
         self.0 & Self::STRONG_LINKS != 0
     }
 
-    pub(crate) fn is_nullable(&self) -> bool {
+    /// Whether the column may hold null values.
+    pub fn is_nullable(&self) -> bool {
         self.0 & Self::NULLABLE != 0
     }
+
+    /// Whether the column holds a list of values. Not used by file format
+    /// 9.9, which uses subtables for lists instead.
+    pub fn is_list(&self) -> bool {
+        self.0 & Self::LIST != 0
+    }
+
+    /// Whether the column holds a dictionary. Not used by file format 9.9.
+    pub fn is_dictionary(&self) -> bool {
+        self.0 & Self::DICTIONARY != 0
+    }
+
+    /// Whether the column holds a set. Not used by file format 9.9.
+    pub fn is_set(&self) -> bool {
+        self.0 & Self::SET != 0
+    }
+
+    /// Whether the column holds a collection: a list, dictionary or set.
+    pub fn is_collection(&self) -> bool {
+        self.is_list() || self.is_dictionary() || self.is_set()
+    }
+
+    /// Whether the column has a full-text search index. Not used by file
+    /// format 9.9.
+    pub fn is_fulltext_indexed(&self) -> bool {
+        self.0 & Self::FULLTEXT_INDEXED != 0
+    }
+
+    /// Whether the column has an index of either kind, which is stored after
+    /// the column data in the table.
+    pub(crate) fn has_index(&self) -> bool {
+        self.is_indexed() || self.is_fulltext_indexed()
+    }
+
+    /// The number of entries a column with these attributes takes up in the
+    /// data array of its table: one for the column data, and one for its index,
+    /// if any. This holds for columns of every type in file format 9.9,
+    /// including subtables (which are also used for lists), links, link lists
+    /// and backlinks; their extra data lives in the spec of the table instead.
+    pub(crate) fn data_array_entries(&self) -> usize {
+        1 + usize::from(self.has_index())
+    }
 }
 
 impl Debug for ColumnAttributes {
@@ -55,6 +119,18 @@ impl Debug for ColumnAttributes {
         if self.is_nullable() {
             s.field("nullable", &true);
         }
+        if self.is_list() {
+            s.field("list", &true);
+        }
+        if self.is_dictionary() {
+            s.field("dictionary", &true);
+        }
+        if self.is_set() {
+            s.field("set", &true);
+        }
+        if self.is_fulltext_indexed() {
+            s.field("fulltext_indexed", &true);
+        }
         s.finish()
     }
 }
@@ -64,3 +140,25 @@ impl FromU64 for ColumnAttributes {
         Self::new(attributes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_attributes() {
+        let attributes = ColumnAttributes::from_u64(0b1_0001_0001);
+        assert!(attributes.is_indexed());
+        assert!(attributes.is_nullable());
+        assert!(attributes.is_fulltext_indexed());
+        assert!(!attributes.is_unique());
+        assert!(!attributes.is_collection());
+        assert_eq!(attributes.bits(), 0b1_0001_0001);
+
+        let attributes = ColumnAttributes::from_u64(0b0100_0000);
+        assert!(attributes.is_dictionary());
+        assert!(attributes.is_collection());
+        assert!(!attributes.is_list());
+        assert!(!attributes.is_set());
+    }
+}
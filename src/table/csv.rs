@@ -0,0 +1,109 @@
+use std::io::Write;
+
+use tracing::instrument;
+
+use crate::spec::ColumnKind;
+use crate::table::Table;
+use crate::table::render::render;
+
+impl Table {
+    /// Write all rows of this table to `writer` in CSV format. The first line
+    /// contains the column names, followed by one line per row.
+    ///
+    /// Values are rendered like [`Row::to_string_map`](crate::Row::to_string_map)
+    /// does, e.g. links as `table:row`, and null values are written as empty
+    /// cells.
+    ///
+    /// Subtable columns can't be represented in a single cell, so they are
+    /// left out entirely. Backlinks are left out as well, as their columns are
+    /// unnamed.
+    ///
+    /// This method is only available with the `csv` feature.
+    #[instrument(level = "debug", skip(self, writer), fields(header = ?self.header))]
+    pub fn to_csv<W: Write>(&self, writer: W) -> crate::RealmResult<()> {
+        let columns = self
+            .header
+            .get_columns()
+            .iter()
+            .filter(|column| column.column_type() != ColumnKind::Table)
+            .filter_map(|column| column.name().map(|name| (name, column)))
+            .collect::<Vec<_>>();
+
+        let mut writer = csv::Writer::from_writer(writer);
+        writer
+            .write_record(columns.iter().map(|(name, _)| name))
+            .map_err(std::io::Error::from)?;
+
+        for row_number in 0..self.row_count()? {
+            let mut record = Vec::with_capacity(columns.len());
+            for (_, column) in &columns {
+                record.push(
+                    render(&column.get(row_number)?)
+                        .into_text()
+                        .unwrap_or_default(),
+                );
+            }
+
+            writer.write_record(&record).map_err(std::io::Error::from)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{RealmBuilder, tagged};
+
+    #[test]
+    fn test_to_csv() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.nullable_short_strings(&[Some("Smith, Jane"), None]);
+        // Binary columns aren't supported, so the binary value is stored in a
+        // mixed column, followed by a null subtable, which reads as null.
+        let types = builder.integers(&[4, 5]);
+        let values = builder.refs(&[tagged(0), 0]);
+        let ends = builder.integers(&[2]);
+        let blob = builder.blob(&[0xca, 0xfe]);
+        let blobs = builder.refs(&[ends, blob]);
+        let data = builder.refs(&[types, values, blobs]);
+        // Nullable seconds, with 7 as the null value.
+        let seconds = builder.integers(&[7, 1_700_000_000, 7]);
+        let nanoseconds = builder.integers(&[0, 0]);
+        let at = builder.refs(&[seconds, nanoseconds]);
+        // Single links are stored as row number + 1, or 0 for null.
+        let next = builder.integers(&[2, 0]);
+        let first_links = builder.integers(&[1, 0]);
+        let links = builder.refs(&[first_links, 0]);
+        let tag_spec = builder.table_spec(&[2], &["tag"], &[0], &[]);
+        let first_tags = builder.short_strings(&["a"]);
+        let first_tags = builder.refs(&[first_tags]);
+        let tags = builder.refs(&[first_tags, 0]);
+        let table = builder.table(
+            &[2, 6, 8, 12, 13, 5],
+            &["name", "data", "at", "next", "links", "tags"],
+            &[16, 16, 16, 16, 0, 0],
+            &[tagged(0), tagged(0), tag_spec],
+            &[names, data, at, next, links, tags],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let mut csv = Vec::new();
+        table.to_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "name,data,at,next,links\n\
+             \"Smith, Jane\",cafe,2023-11-14T22:13:20+00:00,0:1,0:1;0:0\n\
+             ,,,,\n"
+        );
+    }
+}
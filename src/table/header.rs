@@ -8,10 +8,11 @@ use crate::array::{Array, ArrayStringShort, FromU64, IntegerArray, RefOrTaggedVa
 use crate::column::{
     Column, create_backlink_column, create_bool_column, create_bool_null_column,
     create_double_column, create_float_column, create_int_column, create_int_null_column,
-    create_link_column, create_linklist_column, create_string_column, create_subtable_column,
-    create_timestamp_column,
+    create_link_column, create_linklist_column, create_old_mixed_column, create_string_column,
+    create_string_enum_column, create_subtable_column, create_timestamp_column,
+    create_unsupported_column,
 };
-use crate::spec::ColumnType;
+use crate::spec::{ColumnKind, ColumnType};
 use crate::table::column::ColumnAttributes;
 use crate::traits::ArrayLike;
 
@@ -21,6 +22,7 @@ use crate::traits::ArrayLike;
 #[derive(Debug)]
 pub(crate) struct TableHeader {
     columns: Vec<Box<dyn Column>>,
+    link_targets: Vec<Option<usize>>,
 }
 
 impl TableHeader {
@@ -31,6 +33,8 @@ impl TableHeader {
         mut column_names: Vec<String>,
         column_attributes: Vec<ColumnAttributes>,
         sub_spec_array: Option<Array>,
+        enum_keys_array: Option<Array>,
+        depth: usize,
     ) -> crate::RealmResult<Self> {
         // NOTE: The same does not apply for column names, as backlinks don't have a name.
         assert_eq!(
@@ -40,15 +44,26 @@ impl TableHeader {
         );
 
         let mut columns = Vec::with_capacity(column_types.len());
+        let mut link_targets = Vec::with_capacity(column_types.len());
         let mut data_array_index = 0;
         let mut sub_spec_index = 0;
+        let mut enum_keys_index = 0;
 
         // Reverse the column names so we can do a low-cost pop for each column that has a name.
         column_names.reverse();
 
-        for (i, column_type) in column_types.into_iter().enumerate() {
+        for (i, column_type) in column_types.iter().copied().enumerate() {
             let attributes = column_attributes[i];
-            let data_ref = data_array.get_ref(data_array_index).ok_or_else(|| {
+            // Collections are stored differently by newer file formats, and
+            // reading them as regular columns would misalign all columns
+            // after them.
+            if attributes.is_collection() {
+                return Err(RealmFileError::Unsupported {
+                    reason: format!("column {i} is a collection, with attributes {attributes:?}"),
+                });
+            }
+
+            let data_ref = data_array.get_ref(data_array_index)?.ok_or_else(|| {
                 RealmFileError::InvalidRealmFile {
                     reason: format!("failed to find data entry for column {i}"),
                 }
@@ -58,8 +73,8 @@ impl TableHeader {
                 "column type {i}: {column_type:?} has data array index {data_array_index} with ref {data_ref:?}"
             );
 
-            let index_ref = if attributes.is_indexed() {
-                Some(data_array.get_ref(data_array_index + 1).ok_or_else(|| {
+            let index_ref = if attributes.has_index() {
+                Some(data_array.get_ref(data_array_index + 1)?.ok_or_else(|| {
                     RealmFileError::InvalidRealmFile {
                         reason: format!("failed to find index entry for column {i}"),
                     }
@@ -68,6 +83,7 @@ impl TableHeader {
                 None
             };
 
+            let mut link_target = None;
             let column = match column_type {
                 ColumnType::Int => {
                     if attributes.is_nullable() {
@@ -114,16 +130,79 @@ impl TableHeader {
                     attributes,
                     column_names.pop().unwrap(),
                 )?,
-                ColumnType::OldStringEnum => todo!("Implement OldStringEnum column creation"),
-                ColumnType::Binary => todo!("Implement Binary column creation"),
+                ColumnType::OldMixed => create_old_mixed_column(
+                    Arc::clone(&data_array.node.realm),
+                    data_ref,
+                    attributes,
+                    column_names.pop().unwrap(),
+                    depth + 1,
+                )?,
+                ColumnType::OldStringEnum => {
+                    // The keys of enumerated string columns are stored in the
+                    // spec, one entry for each such column.
+                    let keys_ref = enum_keys_array
+                        .as_ref()
+                        .filter(|array| enum_keys_index < array.size())
+                        .map(|array| array.get_ref(enum_keys_index))
+                        .transpose()?
+                        .flatten()
+                        .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                            reason: format!("failed to find enum keys for column {i}"),
+                        })?;
+                    enum_keys_index += 1;
+
+                    create_string_enum_column(
+                        Arc::clone(&data_array.node.realm),
+                        data_ref,
+                        keys_ref,
+                        index_ref,
+                        attributes,
+                        column_names.pop().unwrap(),
+                    )?
+                }
+                ColumnType::Binary | ColumnType::OldDateTime | ColumnType::Reserved4
+                    if data_array.node.realm.skip_unsupported() =>
+                {
+                    let kind = match column_type {
+                        ColumnType::Binary => ColumnKind::Binary,
+                        ColumnType::OldDateTime => ColumnKind::Timestamp,
+                        _ => ColumnKind::Decimal,
+                    };
+
+                    create_unsupported_column(
+                        Arc::clone(&data_array.node.realm),
+                        data_ref,
+                        kind,
+                        attributes,
+                        column_names.pop().unwrap(),
+                    )?
+                }
+                ColumnType::Binary | ColumnType::OldDateTime | ColumnType::Reserved4 => {
+                    return Err(RealmFileError::Unsupported {
+                        reason: format!(
+                            "column '{}' has unsupported type {column_type:?}",
+                            column_names.pop().unwrap_or_default()
+                        ),
+                    });
+                }
+                ColumnType::Unknown(value) => {
+                    return Err(RealmFileError::InvalidRealmFile {
+                        reason: format!(
+                            "column '{}' has unknown type {value}",
+                            column_names.pop().unwrap_or_default()
+                        ),
+                    });
+                }
                 ColumnType::Table => {
                     let other_table_header_ref = sub_spec_array
                         .as_ref()
                         .ok_or_else(|| RealmFileError::InvalidRealmFile {
                             reason: "Expected sub-spec array for table column".to_string(),
                         })?
-                        .get_ref(sub_spec_index)
-                        .unwrap();
+                        .get_ref(sub_spec_index)?
+                        .ok_or_else(|| RealmFileError::InvalidRealmFile {
+                            reason: format!("failed to find the spec of subtable column {i}"),
+                        })?;
                     let name = column_names.pop().unwrap();
 
                     create_subtable_column(
@@ -132,10 +211,9 @@ impl TableHeader {
                         data_ref,
                         attributes,
                         name,
+                        depth + 1,
                     )?
                 }
-                ColumnType::OldMixed => todo!("Implement OldMixed column creation"),
-                ColumnType::OldDateTime => todo!("Implement OldDateTime column creation"),
                 ColumnType::Timestamp => create_timestamp_column(
                     Arc::clone(&data_array.node.realm),
                     data_ref,
@@ -155,7 +233,6 @@ impl TableHeader {
                     attributes,
                     column_names.pop().unwrap(),
                 )?,
-                ColumnType::Reserved4 => todo!("Implement Reserved4 column creation"),
                 ColumnType::Link => {
                     let target_table_index = Self::get_sub_spec_index_value(
                         sub_spec_array.as_ref().ok_or_else(|| {
@@ -165,6 +242,7 @@ impl TableHeader {
                         })?,
                         sub_spec_index,
                     )?;
+                    link_target = Some(target_table_index);
 
                     create_link_column(
                         Arc::clone(&data_array.node.realm),
@@ -183,6 +261,7 @@ impl TableHeader {
                             })?,
                         sub_spec_index,
                     )?;
+                    link_target = Some(target_table_index);
 
                     create_linklist_column(
                         Arc::clone(&data_array.node.realm),
@@ -203,6 +282,7 @@ impl TableHeader {
                         Self::get_sub_spec_index_value(sub_spec_array, sub_spec_index)?;
                     let target_table_column_index =
                         Self::get_sub_spec_index_value(sub_spec_array, sub_spec_index + 1)?;
+                    link_target = Some(target_table_index);
                     create_backlink_column(
                         Arc::clone(&data_array.node.realm),
                         data_ref,
@@ -216,28 +296,28 @@ impl TableHeader {
             tracing::info!("Created column {column:?}");
 
             columns.push(column);
+            link_targets.push(link_target);
 
-            data_array_index += 1;
-            if attributes.is_indexed() {
-                // Indexed columns have an additional data array, so we need to increment the data
-                // index. In other words, for column with data index N, with attribute is_indexed,
-                // there's an index entry at N+1 in the data array.
-                data_array_index += 1;
-            }
+            // For a column with data index N with an index, the index is at
+            // N+1 in the data array.
+            data_array_index += attributes.data_array_entries();
 
             if column_type.has_sub_spec() {
                 sub_spec_index += column_type.sub_spec_entries_count();
             }
         }
 
-        Ok(Self { columns })
+        Ok(Self {
+            columns,
+            link_targets,
+        })
     }
 
     fn get_sub_spec_index_value(
         sub_spec_array: &Array,
         sub_spec_index: usize,
     ) -> crate::RealmResult<usize> {
-        match sub_spec_array.get_ref_or_tagged_value(sub_spec_index) {
+        match sub_spec_array.get_ref_or_tagged_value(sub_spec_index)? {
             Some(RefOrTaggedValue::Ref(_)) => Err(RealmFileError::InvalidRealmFile {
                 reason: "Expected tagged integer for link column".to_string(),
             }),
@@ -261,15 +341,26 @@ impl TableHeader {
     pub(crate) fn get_column(&self, index: usize) -> Option<&dyn Column> {
         self.columns.get(index).map(|c| c.as_ref())
     }
+
+    /// Get, for each column, the number of the table it links to (for link and
+    /// link list columns) or the table the links originate from (for backlink
+    /// columns).
+    pub(crate) fn get_link_targets(&self) -> &[Option<usize>] {
+        &self.link_targets
+    }
 }
 
 impl TableHeader {
     #[instrument(level = "debug")]
-    pub(crate) fn build(header_array: &Array, data_array: &Array) -> crate::RealmResult<Self> {
+    pub(crate) fn build(
+        header_array: &Array,
+        data_array: &Array,
+        depth: usize,
+    ) -> crate::RealmResult<Self> {
         let column_types = {
             let array: IntegerArray = header_array.get_node(0)?.unwrap();
             array
-                .get_integers()
+                .get_integers()?
                 .into_iter()
                 .map(ColumnType::from_u64)
                 .collect::<Vec<_>>()
@@ -287,7 +378,7 @@ impl TableHeader {
         let column_attributes = {
             let array: IntegerArray = header_array.get_node(2)?.unwrap();
             array
-                .get_integers()
+                .get_integers()?
                 .into_iter()
                 .map(ColumnAttributes::from_u64)
                 .collect::<Vec<_>>()
@@ -301,12 +392,20 @@ impl TableHeader {
             None
         };
 
+        let enum_keys_array = if header_array.node.header.size > 4 {
+            header_array.get_node(4)?
+        } else {
+            None
+        };
+
         Self::from_parts(
             data_array,
             column_types,
             column_names,
             column_attributes,
             sub_spec_array,
+            enum_keys_array,
+            depth,
         )
     }
 }
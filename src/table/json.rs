@@ -0,0 +1,99 @@
+use serde_json::{Map, Number, json};
+
+use crate::table::Row;
+use crate::table::render::{Rendered, render};
+use crate::value::{Link, Value};
+
+impl Row<'_> {
+    /// Convert every field of this row to a JSON value, keyed by column name,
+    /// e.g. to quickly dump a row without defining a model for it. Values are
+    /// rendered like [`to_string_map`](Self::to_string_map) does, except for
+    /// the values JSON has its own types for:
+    ///
+    /// - Integers and booleans map to their JSON counterparts.
+    /// - Floating-point values become numbers, or `null` if they are not
+    ///   finite.
+    /// - Links become objects with the `table` and `row` numbers they point
+    ///   to, and link lists become arrays of such objects.
+    /// - Subtables become arrays of objects, one for each row. Subtables that
+    ///   were not read (see [`Value::TableLazy`]) become `null`.
+    ///
+    /// Null values and values of unsupported types become `null`. Backlinks
+    /// are not included, as their columns are unnamed. Duplicate column names
+    /// get a suffix, see [`unique_entries`](Row::unique_entries).
+    ///
+    /// This method is only available with the `json` feature.
+    pub fn to_json_map(&self) -> Map<String, serde_json::Value> {
+        self.unique_entries()
+            .map(|(name, value)| (name.into_owned(), json_value(value)))
+            .collect()
+    }
+}
+
+/// Convert a single value to JSON. See [`Row::to_json_map`].
+fn json_value(value: &Value) -> serde_json::Value {
+    match render(value) {
+        Rendered::Int(value) => json!(value),
+        Rendered::Bool(value) => json!(value),
+        Rendered::Float(value) => Number::from_f64(f64::from(value)).into(),
+        Rendered::Double(value) => Number::from_f64(value).into(),
+        Rendered::Text(value) => json!(value),
+        Rendered::Link(link) => json_link(&link),
+        Rendered::LinkList(links) => links.iter().map(json_link).collect(),
+        Rendered::Table(rows) => rows.iter().map(Row::to_json_map).collect(),
+        Rendered::Null => serde_json::Value::Null,
+    }
+}
+
+fn json_link(link: &Link) -> serde_json::Value {
+    json!({
+        "table": link.target_table_number,
+        "row": link.row_number,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use serde_json::json;
+
+    use crate::{Link, Row, Value};
+
+    #[test]
+    fn test_to_json_map() {
+        let tag = Row::new(vec!["red".into()], vec!["name".into()]);
+        let row = Row::new(
+            vec![
+                1.into(),
+                Value::Double(f64::NAN),
+                Value::Binary(vec![0xca, 0xfe]),
+                Value::Timestamp(DateTime::UNIX_EPOCH),
+                Value::Link(Link::new(2, 5)),
+                Value::Table(vec![tag.into_owned()]),
+                Value::None,
+            ],
+            vec![
+                "id".into(),
+                "score".into(),
+                "data".into(),
+                "created".into(),
+                "owner".into(),
+                "tags".into(),
+                "note".into(),
+            ],
+        );
+
+        assert_eq!(
+            serde_json::Value::Object(row.to_json_map()),
+            json!({
+                "id": 1,
+                "score": null,
+                "data": "cafe",
+                "created": "1970-01-01T00:00:00+00:00",
+                "owner": { "table": 2, "row": 5 },
+                "tags": [{ "name": "red" }],
+                "note": null,
+            })
+        );
+    }
+}
@@ -1,17 +1,34 @@
+#[cfg(feature = "arrow")]
+mod arrow;
 mod column;
+#[cfg(feature = "csv")]
+mod csv;
 mod header;
+#[cfg(feature = "json")]
+mod json;
+mod render;
 mod row;
+mod schema;
 
+use chrono::{DateTime, Utc};
 use tracing::{debug, instrument};
 
 use crate::RealmFileError;
 use crate::array::Array;
 use crate::column::Column;
-use crate::error::TableError;
-pub(crate) use crate::table::column::ColumnAttributes;
+use crate::error::{ResolveError, TableError, ValueError};
+use crate::index::{Index, IndexEntry};
+use crate::spec::ColumnKind;
+pub use crate::table::column::ColumnAttributes;
 use crate::table::header::TableHeader;
 pub use crate::table::row::Row;
-use crate::value::Value;
+pub use crate::table::schema::{
+    ColumnSchema, DatabaseSchema, DatabaseTableSchema, SchemaChange, TableSchema,
+};
+use crate::value::{LinkListRef, SubtableRef, Value};
+
+/// The maximum number of levels subtables may be nested.
+const MAX_SUBTABLE_DEPTH: usize = 64;
 
 /// A view into a single Realm database table.
 #[derive(Debug)]
@@ -28,19 +45,34 @@ impl Table {
         let header_array = array.get_node(0)?.unwrap();
         let data_array = array.get_node(1)?.unwrap();
 
-        Self::build_from(&header_array, data_array, table_number)
+        Self::build_from(&header_array, data_array, table_number, 0)
     }
 
     /// Construct a new table instance, from the given Realm arrays for the
     /// header and data. This is used primarily by subtables, as their header
     /// and data arrays are in disjointed locations compared to regular tables.
+    ///
+    /// The depth is the number of subtables this table is nested in, which is
+    /// 0 for regular tables. Returns an error if the depth exceeds
+    /// [`MAX_SUBTABLE_DEPTH`], which can only happen in malformed files where a
+    /// subtable refers back to one of its ancestors.
     #[instrument(level = "debug")]
     pub(crate) fn build_from(
         header_array: &Array,
         data_array: Array,
         table_number: usize,
+        depth: usize,
     ) -> crate::RealmResult<Self> {
-        let header = TableHeader::build(header_array, &data_array)?;
+        if depth > MAX_SUBTABLE_DEPTH {
+            return Err(RealmFileError::InvalidRealmFile {
+                reason: format!(
+                    "subtables are nested more than {MAX_SUBTABLE_DEPTH} levels deep at {:?}",
+                    data_array.node.ref_
+                ),
+            });
+        }
+
+        let header = TableHeader::build(header_array, &data_array, depth)?;
 
         let result = Self {
             header,
@@ -56,7 +88,9 @@ impl Table {
     /// Get the number of the table, starting with 0, within the
     /// [`Group`](`crate::group::Group`).
     ///
-    /// Subtables have a table number of [`usize::MAX`].
+    /// Subtables have a table number of [`usize::MAX`]. [`Link`](crate::Link)s
+    /// in the rows of a subtable still refer to their target by its number in
+    /// the group, so they can be resolved like links in top-level tables.
     pub fn get_table_number(&self) -> usize {
         self.table_number
     }
@@ -73,22 +107,93 @@ impl Table {
         self.header.get_column(column_number)
     }
 
-    /// Get the number of rows in the table.
+    /// Get the specification for the column with the given name. If several
+    /// columns have the same name, this returns the first of them.
+    ///
+    /// Returns `None` if there is no column with the given name.
+    pub fn get_column_by_name(&self, name: &str) -> Option<&dyn Column> {
+        self.header
+            .get_columns()
+            .iter()
+            .find(|column| column.name() == Some(name))
+            .map(|column| column.as_ref())
+    }
+
+    /// Get the number (starting with 0) of the column with the given name, as
+    /// used by e.g. [`get_column_spec`](Self::get_column_spec) and
+    /// [`column_values`](Self::column_values). If several columns have the
+    /// same name, this returns the number of the first of them.
+    ///
+    /// Returns `None` if there is no column with the given name.
+    pub fn column_number_of(&self, name: &str) -> Option<usize> {
+        self.header
+            .get_columns()
+            .iter()
+            .position(|column| column.name() == Some(name))
+    }
+
+    /// Get a description of the columns in this table. See [`TableSchema`].
+    pub fn schema(&self) -> TableSchema {
+        let columns = self
+            .header
+            .get_columns()
+            .iter()
+            .zip(self.header.get_link_targets())
+            .map(|(column, link_target)| ColumnSchema {
+                name: column.name().map(str::to_string),
+                kind: column.column_type(),
+                nullable: column.nullable(),
+                indexed: column.is_indexed(),
+                link_target: *link_target,
+            })
+            .collect();
+
+        TableSchema { columns }
+    }
+
+    /// Get a description of the columns in this table that hold user data,
+    /// like [`schema`](Self::schema), but without backlink columns, which are
+    /// maintained by Realm. This is, for example, what's needed to generate a
+    /// model for the table. Every column returned has a name.
+    pub fn user_columns(&self) -> Vec<ColumnSchema> {
+        self.schema()
+            .columns
+            .into_iter()
+            .filter(|column| column.name.is_some() && column.kind != ColumnKind::BackLink)
+            .collect()
+    }
+
+    /// Get the number of rows in the table. A table without any columns has
+    /// no rows.
     #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
     pub fn row_count(&self) -> crate::RealmResult<usize> {
-        let first_column =
-            self.header
-                .get_column(0)
-                .ok_or_else(|| RealmFileError::InvalidRealmFile {
-                    reason: "No column at index 0: can't load row count".to_string(),
-                })?;
-        first_column.count()
+        match self.header.get_column(0) {
+            Some(first_column) => first_column.count(),
+            None => Ok(0),
+        }
     }
 
-    /// Get the row with the given number (starting with 0).
+    /// Get the row with the given number (starting with 0). Subtables and link
+    /// lists are read in their entirety; see [`get_row_lazy`](Self::get_row_lazy)
+    /// to defer reading them.
     #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
     pub fn get_row<'a>(&'a self, row_number: usize) -> crate::RealmResult<Row<'a>> {
-        let values = self.load_row(row_number)?;
+        self.build_row(row_number, false)
+    }
+
+    /// Get the row with the given number (starting with 0), like
+    /// [`get_row`](Self::get_row), but without reading subtables and link
+    /// lists up front. The row holds [`Value::TableLazy`] and
+    /// [`Value::LinkListLazy`] values for them instead, see
+    /// [`Column::get_lazy`]. Use [`Row::subtable`] to read a subtable when
+    /// needed.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_row_lazy<'a>(&'a self, row_number: usize) -> crate::RealmResult<Row<'a>> {
+        self.build_row(row_number, true)
+    }
+
+    fn build_row(&self, row_number: usize, lazy: bool) -> crate::RealmResult<Row<'_>> {
+        let values = self.load_row(row_number, lazy)?;
 
         Ok(Row::new(
             values,
@@ -103,17 +208,92 @@ impl Table {
 
     /// Load the values for the row with the given number (starting with 0).
     #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
-    fn load_row(&self, row_number: usize) -> crate::RealmResult<Vec<Value>> {
+    fn load_row(&self, row_number: usize, lazy: bool) -> crate::RealmResult<Vec<Value>> {
         let column_count = self.header.column_count();
         let mut values = Vec::with_capacity(column_count);
         for column_number in 0..column_count {
             tracing::info!("loading column {column_number} for row {row_number}");
-            values.push(self.load_column(column_number, row_number)?);
+            values.push(self.load_column(column_number, row_number, lazy)?);
         }
 
         Ok(values)
     }
 
+    /// Get the value of a single column for the row with the given number
+    /// (starting with 0). Unlike [`get_row`](Self::get_row), this only reads
+    /// the requested column, which avoids decoding the other columns (such as
+    /// subtables) of wide tables.
+    ///
+    /// Returns an error if there is no column with the given name.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_cell(&self, row_number: usize, column_name: &str) -> crate::TableResult<Value> {
+        let column_spec = self.find_column(column_name)?;
+
+        Ok(column_spec.get(row_number)?)
+    }
+
+    /// Get the links in a link list column for the row with the given number,
+    /// without reading all of them up front. See [`LinkListRef`].
+    ///
+    /// Returns an error if there is no column with the given name, or if the
+    /// column is not a link list column.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_link_list(
+        &self,
+        row_number: usize,
+        column_name: &str,
+    ) -> crate::TableResult<LinkListRef> {
+        let column_spec = self.find_column(column_name)?;
+        let found = column_spec.column_type();
+        if found != ColumnKind::LinkList {
+            return Err(TableError::ColumnTypeMismatch {
+                name: column_name.to_string(),
+                expected: ColumnKind::LinkList,
+                found,
+            });
+        }
+
+        match column_spec.get_lazy(row_number)? {
+            Value::LinkListLazy(links) => Ok(links),
+            value => unreachable!("link list column returned {value:?}"),
+        }
+    }
+
+    /// Get the subtable in a subtable column for the row with the given
+    /// number, without reading its rows up front. See [`SubtableRef`].
+    ///
+    /// Returns an error if there is no column with the given name, or if the
+    /// column is not a subtable column.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_subtable(
+        &self,
+        row_number: usize,
+        column_name: &str,
+    ) -> crate::TableResult<SubtableRef> {
+        let column_spec = self.find_column(column_name)?;
+        let found = column_spec.column_type();
+        if found != ColumnKind::Table {
+            return Err(TableError::ColumnTypeMismatch {
+                name: column_name.to_string(),
+                expected: ColumnKind::Table,
+                found,
+            });
+        }
+
+        match column_spec.get_lazy(row_number)? {
+            Value::TableLazy(subtable) => Ok(subtable),
+            value => unreachable!("subtable column returned {value:?}"),
+        }
+    }
+
+    /// Find the column with the given name.
+    fn find_column(&self, column_name: &str) -> crate::TableResult<&dyn Column> {
+        self.get_column_by_name(column_name)
+            .ok_or_else(|| TableError::ColumnNotFound {
+                name: column_name.to_string(),
+            })
+    }
+
     /// Determine the row number for the given value in an indexed column.
     /// Note that if there are multiple rows with the same value, this function
     /// will return the first one.
@@ -127,15 +307,7 @@ impl Table {
         indexed_column_name: &str,
         value: &Value,
     ) -> crate::TableResult<Option<usize>> {
-        // Find the column index for the given column name
-        let column_spec = self
-            .header
-            .get_columns()
-            .iter()
-            .find(|col| col.name() == Some(indexed_column_name))
-            .ok_or_else(|| TableError::ColumnNotFound {
-                name: indexed_column_name.to_string(),
-            })?;
+        let column_spec = self.find_column(indexed_column_name)?;
 
         if !column_spec.is_indexed() {
             return Err(TableError::ColumnNotIndexed {
@@ -147,6 +319,225 @@ impl Table {
         Ok(result)
     }
 
+    /// Find the row numbers of many values in an indexed column at once, e.g.
+    /// to join two tables. This is like calling
+    /// [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column)
+    /// for every value, but looks up the column and its index only once.
+    ///
+    /// The result holds an entry for every value, in the same order, which is
+    /// `None` if the value is not found.
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed, and [`RealmFileError::Unsupported`] if any of
+    /// the values can't be looked up in an index, such as null values. The
+    /// values are checked before any of them is looked up.
+    #[instrument(level = "debug", skip(self, values), fields(header = ?self.header, count = values.len()))]
+    pub fn find_rows_from_indexed_column_batch(
+        &self,
+        indexed_column_name: &str,
+        values: &[Value],
+    ) -> crate::TableResult<Vec<Option<usize>>> {
+        let column_spec = self.find_indexed_column(indexed_column_name)?;
+        for value in values {
+            Index::check_lookup_value(value)?;
+        }
+
+        let mut result = Vec::with_capacity(values.len());
+        for value in values {
+            result.push(column_spec.get_row_number_by_index(value)?);
+        }
+
+        Ok(result)
+    }
+
+    /// Get the smallest non-null value in an indexed column, or `None` if it
+    /// has no non-null values. This uses the index, see
+    /// [`Column::index_min`].
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn column_min(&self, indexed_column_name: &str) -> crate::TableResult<Option<Value>> {
+        self.find_indexed_column(indexed_column_name)?.index_min()
+    }
+
+    /// Get the largest non-null value in an indexed column, or `None` if it
+    /// has no non-null values. This uses the index, see
+    /// [`Column::index_max`].
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn column_max(&self, indexed_column_name: &str) -> crate::TableResult<Option<Value>> {
+        self.find_indexed_column(indexed_column_name)?.index_max()
+    }
+
+    /// Get every entry in the index of an indexed column, in the order of
+    /// their keys. This is intended for debugging lookups that don't find the
+    /// expected rows, see [`IndexEntry`].
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn index_entries(&self, indexed_column_name: &str) -> crate::TableResult<Vec<IndexEntry>> {
+        self.find_indexed_column(indexed_column_name)?
+            .index_entries()
+    }
+
+    /// Find the column with the given name, which must be indexed.
+    fn find_indexed_column(&self, column_name: &str) -> crate::TableResult<&dyn Column> {
+        let column_spec = self.find_column(column_name)?;
+        if !column_spec.is_indexed() {
+            return Err(TableError::ColumnNotIndexed {
+                name: column_name.to_string(),
+            });
+        }
+
+        Ok(column_spec)
+    }
+
+    /// Check whether any row holds the given value in an indexed column. This
+    /// is like [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column),
+    /// for when the row itself is not needed.
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed, and [`RealmFileError::Unsupported`] if the value
+    /// can't be looked up in an index, such as null values.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn index_contains(
+        &self,
+        indexed_column_name: &str,
+        value: &Value,
+    ) -> crate::TableResult<bool> {
+        self.find_indexed_column(indexed_column_name)?
+            .index_contains(value)
+    }
+
+    /// Check the index of the given column against its values: for every
+    /// row, look up its value using
+    /// [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column),
+    /// and check that the row found holds the same value. This reads the
+    /// entire column, and is meant as an integrity check of the index.
+    ///
+    /// Returns the numbers of the rows whose value could not be found, or
+    /// whose value led to a row with a different value, in ascending order.
+    /// Rows holding null are skipped, as null values can't be looked up.
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column is not indexed.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn verify_index(&self, indexed_column_name: &str) -> crate::TableResult<Vec<usize>> {
+        let column_spec = self.find_indexed_column(indexed_column_name)?;
+
+        let values = column_spec.get_all()?;
+        let mut mismatches = Vec::new();
+        for (row_number, value) in values.iter().enumerate() {
+            if value.is_none() {
+                continue;
+            }
+
+            let found = column_spec.get_row_number_by_index(value)?;
+            if found.and_then(|found| values.get(found)) != Some(value) {
+                mismatches.push(row_number);
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Find the numbers of all rows containing the given word in a column
+    /// with a full-text index, in ascending order. The full-text index holds
+    /// the words of each value in lowercase, so the token is lowercased before
+    /// looking it up, making the search case-insensitive.
+    ///
+    /// Full-text indexes were introduced in a later file format than the one
+    /// supported by this library, so this only finds rows in files that were
+    /// written with such an index anyway.
+    ///
+    /// Returns an error if there is no column with the given name or if the
+    /// column does not have a full-text index.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn find_rows_by_token(
+        &self,
+        column_name: &str,
+        token: &str,
+    ) -> crate::TableResult<Vec<usize>> {
+        let column_spec = self.find_column(column_name)?;
+
+        if !column_spec.attributes().is_fulltext_indexed() {
+            return Err(TableError::ColumnNotFullTextIndexed {
+                name: column_name.to_string(),
+            });
+        }
+
+        column_spec.get_row_numbers_by_index(&Value::String(token.to_lowercase()))
+    }
+
+    /// Determine the row number for the given string in an indexed string
+    /// column. See
+    /// [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column).
+    ///
+    /// Returns an error if the column is not a string column.
+    pub fn find_by_str(
+        &self,
+        indexed_column_name: &str,
+        value: &str,
+    ) -> crate::TableResult<Option<usize>> {
+        self.find_row_number_of_kind(
+            indexed_column_name,
+            ColumnKind::String,
+            &Value::String(value.to_string()),
+        )
+    }
+
+    /// Determine the row number for the given integer in an indexed integer
+    /// column. See
+    /// [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column).
+    ///
+    /// Returns an error if the column is not an integer column.
+    pub fn find_by_int(
+        &self,
+        indexed_column_name: &str,
+        value: i64,
+    ) -> crate::TableResult<Option<usize>> {
+        self.find_row_number_of_kind(indexed_column_name, ColumnKind::Int, &Value::Int(value))
+    }
+
+    /// Determine the row number for the given timestamp in an indexed
+    /// timestamp column. See
+    /// [`find_row_number_from_indexed_column`](Self::find_row_number_from_indexed_column).
+    ///
+    /// Returns an error if the column is not a timestamp column.
+    pub fn find_by_timestamp(
+        &self,
+        indexed_column_name: &str,
+        value: DateTime<Utc>,
+    ) -> crate::TableResult<Option<usize>> {
+        self.find_row_number_of_kind(
+            indexed_column_name,
+            ColumnKind::Timestamp,
+            &Value::Timestamp(value),
+        )
+    }
+
+    fn find_row_number_of_kind(
+        &self,
+        indexed_column_name: &str,
+        expected: ColumnKind,
+        value: &Value,
+    ) -> crate::TableResult<Option<usize>> {
+        let found = self.find_column(indexed_column_name)?.column_type();
+        if found != expected {
+            return Err(TableError::ColumnTypeMismatch {
+                name: indexed_column_name.to_string(),
+                expected,
+                found,
+            });
+        }
+
+        self.find_row_number_from_indexed_column(indexed_column_name, value)
+    }
+
     /// Find and load the row with the given value in an indexed column.
     /// Note that if there are multiple rows with the same value, only the first one is returned.
     ///
@@ -182,16 +573,180 @@ impl Table {
         Ok(rows)
     }
 
+    /// Get all rows in the table, with only the columns with the given names,
+    /// in the given order. Unlike [`get_rows`](Self::get_rows), only the
+    /// requested columns are read, which avoids decoding the other columns
+    /// (such as subtables) of wide tables. See also [`get_cell`](Self::get_cell).
+    ///
+    /// The rows hold no backlinks, as backlink columns have no name.
+    ///
+    /// Returns an error if there is no column with one of the given names.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_rows_projected<'a>(
+        &'a self,
+        column_names: &[&str],
+    ) -> crate::TableResult<Vec<Row<'a>>> {
+        let columns = column_names
+            .iter()
+            .map(|name| self.find_column(name))
+            .collect::<crate::TableResult<Vec<_>>>()?;
+        let names = columns
+            .iter()
+            .filter_map(|column| column.name())
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        let row_count = self.row_count()?;
+        let mut rows = Vec::with_capacity(row_count);
+        for row_number in 0..row_count {
+            let values = columns
+                .iter()
+                .map(|column| column.get(row_number))
+                .collect::<crate::RealmResult<Vec<_>>>()?;
+
+            rows.push(Row::new(values, names.clone()));
+        }
+
+        Ok(rows)
+    }
+
+    /// Get (at most) `n` rows spread evenly across the table, e.g. to preview
+    /// a large table. The first row is always included, and the rows are
+    /// returned in order. If the table has `n` rows or fewer, all rows are
+    /// returned.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn sample(&self, n: usize) -> crate::RealmResult<Vec<Row<'_>>> {
+        let row_count = self.row_count()?;
+        if n >= row_count {
+            return self.get_rows();
+        }
+
+        (0..n).map(|i| self.get_row(i * row_count / n)).collect()
+    }
+
+    /// Iterate over the rows in the table, converting each of them to `T`,
+    /// e.g. a struct using [`realm_model`](crate::realm_model). Rows are read
+    /// one at a time, so unlike converting the result of
+    /// [`get_rows`](Self::get_rows), this doesn't load the whole table when
+    /// you stop early.
+    ///
+    /// Every item is either the converted row, or the error that occurred
+    /// reading or converting it: [`ResolveError::Table`] or
+    /// [`ResolveError::Value`] respectively.
+    ///
+    /// ```no_run
+    /// use realm_db_reader::{Realm, realm_model};
+    ///
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// realm_model!(Person => name);
+    ///
+    /// let group = Realm::open("example.realm").unwrap().into_group().unwrap();
+    /// let table = group.get_table_by_name("class_Person").unwrap();
+    /// for person in table.iter_models::<Person>() {
+    ///     println!("{}", person.unwrap().name);
+    /// }
+    /// ```
+    pub fn iter_models<'a, T>(&'a self) -> impl Iterator<Item = crate::ResolveResult<T>> + 'a
+    where
+        T: TryFrom<Row<'a>> + 'a,
+        ValueError: From<T::Error>,
+    {
+        // If the number of rows can't be read, the error is the only item.
+        let (row_count, error) = match self.row_count() {
+            Ok(row_count) => (row_count, None),
+            Err(error) => (0, Some(error)),
+        };
+
+        error
+            .map(|error| Err(TableError::from(error).into()))
+            .into_iter()
+            .chain((0..row_count).map(move |row_number| {
+                let row = self.get_row(row_number).map_err(TableError::from)?;
+
+                T::try_from(row).map_err(|error| ResolveError::Value(error.into()))
+            }))
+    }
+
+    /// Get all rows in the table, sorted by the values in the column with the
+    /// given name. See the [`Ord`] implementation of [`Value`] for how values
+    /// are ordered; in particular, null values are sorted last. Rows with equal
+    /// values keep their original order.
+    ///
+    /// Returns an error if there is no column with the given name.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn get_rows_sorted_by<'a>(&'a self, column_name: &str) -> crate::TableResult<Vec<Row<'a>>> {
+        // Make sure the column exists before loading every row.
+        self.find_column(column_name)?;
+
+        let mut rows = self.get_rows()?;
+        rows.sort_by(|a, b| a.get(column_name).cmp(&b.get(column_name)));
+
+        Ok(rows)
+    }
+
+    /// Get all rows in the table, consuming the table. Unlike
+    /// [`get_rows`](Self::get_rows), the returned rows own their column names,
+    /// so they are not tied to the lifetime of the table. See
+    /// [`Row::into_owned`].
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn into_rows(self) -> crate::RealmResult<Vec<Row<'static>>> {
+        Ok(self.get_rows()?.into_iter().map(Row::into_owned).collect())
+    }
+
+    /// Get the values of every row for the column with the given number
+    /// (starting with 0). The column is read sequentially, which is
+    /// considerably faster than [`get_rows`](Self::get_rows) when you only
+    /// need a single column.
+    ///
+    /// Panics if the column number is out of range.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn column_values(&self, column_number: usize) -> crate::RealmResult<Vec<Value>> {
+        let column_spec = self
+            .header
+            .get_column(column_number)
+            .unwrap_or_else(|| panic!("Invalid column number {column_number}"));
+
+        column_spec.get_all()
+    }
+
+    /// Check for every row whether the value in the column with the given
+    /// number (starting with 0) is null, in row order. This reads the column
+    /// sequentially, and mostly without decoding the values, which makes it
+    /// useful for e.g. checking that a column has no nulls.
+    ///
+    /// Panics if the column number is out of range.
+    #[instrument(level = "debug", skip(self), fields(header = ?self.header))]
+    pub fn column_nulls(&self, column_number: usize) -> crate::RealmResult<Vec<bool>> {
+        let column_spec = self
+            .header
+            .get_column(column_number)
+            .unwrap_or_else(|| panic!("Invalid column number {column_number}"));
+
+        column_spec.null_bitmap()
+    }
+
     /// Load the value at the specified column and row.
     ///
     /// Panics if the column or row number is out of range.
     #[instrument(level = "debug", skip(self))]
-    fn load_column(&self, column_number: usize, row_number: usize) -> crate::RealmResult<Value> {
+    fn load_column(
+        &self,
+        column_number: usize,
+        row_number: usize,
+        lazy: bool,
+    ) -> crate::RealmResult<Value> {
         let column_spec = self
             .header
             .get_column(column_number)
             .unwrap_or_else(|| panic!("Invalid column number {column_number}"));
-        let value = column_spec.get(row_number)?;
+        let value = if lazy {
+            column_spec.get_lazy(row_number)?
+        } else {
+            column_spec.get(row_number)?
+        };
 
         debug!(
             "Loaded column {column_number} at row {row_number}: {:?}",
@@ -201,3 +756,867 @@ impl Table {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::test_utils::{RealmBuilder, tagged};
+    use crate::{
+        ColumnKind, ColumnSchema, Group, Link, RealmFileError, ResolveError, TableError, Value,
+        ValueError, realm_model,
+    };
+
+    /// A group with a single table `class_Person`, with a string column
+    /// `name` and an integer column `age`.
+    fn people() -> Group {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let ages = builder.integers(&[31, 42]);
+        let table = builder.table(&[2, 0], &["name", "age"], &[0, 0], &[], &[names, ages]);
+        let top = builder.group(&[("class_Person", table)]);
+
+        builder.build(top).into_group().unwrap()
+    }
+
+    #[test]
+    fn test_get_cell() {
+        let table = people().get_table(0).unwrap();
+
+        assert_eq!(table.row_count().unwrap(), 2);
+        assert_eq!(table.get_cell(1, "name").unwrap(), Value::from("bob"));
+        assert_eq!(table.get_cell(0, "age").unwrap(), Value::Int(31));
+        assert!(matches!(
+            table.get_cell(0, "missing"),
+            Err(TableError::ColumnNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_rows_projected() {
+        let table = people().get_table(0).unwrap();
+
+        let rows = table.get_rows_projected(&["age", "name"]).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1].ordered_entries().collect::<Vec<_>>(),
+            [
+                (&"age".into(), &Value::Int(42)),
+                (&"name".into(), &Value::from("bob"))
+            ]
+        );
+
+        let rows = table.get_rows_projected(&["name"]).unwrap();
+        assert_eq!(rows[0].len(), 1);
+        assert_eq!(rows[0].get("name"), Some(&Value::from("alice")));
+        assert!(!rows[0].has_field("age"));
+
+        assert!(matches!(
+            table.get_rows_projected(&["name", "missing"]),
+            Err(TableError::ColumnNotFound { name }) if name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_get_column_by_name() {
+        let table = people().get_table(0).unwrap();
+
+        let column = table.get_column_by_name("age").unwrap();
+        assert_eq!(column.column_type(), ColumnKind::Int);
+        assert_eq!(table.column_number_of("age"), Some(1));
+        assert_eq!(table.column_number_of("name"), Some(0));
+        assert!(table.get_column_by_name("missing").is_none());
+        assert_eq!(table.column_number_of("missing"), None);
+    }
+
+    #[test]
+    fn test_get_subtable() {
+        let mut builder = RealmBuilder::new();
+        let subtable_spec = builder.table_spec(&[2], &["name"], &[0], &[]);
+        let names = builder.short_strings(&["red", "green"]);
+        let subtable = builder.refs(&[names]);
+        // The second row has a null subtable.
+        let subtables = builder.refs(&[subtable, 0]);
+        let table = builder.table(&[5], &["tags"], &[0], &[subtable_spec], &[subtables]);
+        let top = builder.group(&[("class_Post", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let tags = table.get_subtable(0, "tags").unwrap();
+        assert_eq!(tags.row_count().unwrap(), 2);
+        assert_eq!(
+            tags.get_row(1).unwrap().get("name"),
+            Some(&Value::from("green"))
+        );
+        let Value::Table(rows) = table.get_cell(0, "tags").unwrap() else {
+            panic!("subtable not loaded");
+        };
+        assert_eq!(tags.to_vec().unwrap(), rows);
+        assert_eq!(tags, table.get_subtable(0, "tags").unwrap());
+
+        let empty = table.get_subtable(1, "tags").unwrap();
+        assert!(empty.table().is_none());
+        assert_eq!(empty.row_count().unwrap(), 0);
+        assert!(empty.get_row(0).is_err());
+        assert_ne!(empty, tags);
+
+        assert!(matches!(
+            people().get_table(0).unwrap().get_subtable(0, "name"),
+            Err(TableError::ColumnTypeMismatch { .. })
+        ));
+
+        let row = table.get_row_lazy(0).unwrap();
+        assert_eq!(row.get("tags"), Some(&Value::TableLazy(tags)));
+        assert_eq!(row.subtable("tags").unwrap(), Some(rows.clone()));
+        assert_eq!(
+            table.get_row(0).unwrap().subtable("tags").unwrap(),
+            Some(rows)
+        );
+        assert_eq!(
+            table.get_row_lazy(1).unwrap().subtable("tags").unwrap(),
+            Some(Vec::new())
+        );
+        assert_eq!(row.subtable("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_data_array_alignment() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", tagged(1))]);
+        // A list of integers is stored as a subtable with a single column.
+        let scores_spec = builder.table_spec(&[0], &["!ARRAY_VALUE"], &[0], &[]);
+        let alice_scores = builder.integers(&[3, 5]);
+        let alice_scores = builder.refs(&[alice_scores]);
+        let scores = builder.refs(&[alice_scores, 0]);
+        let friends = builder.integers(&[2, 1]);
+        let ages = builder.integers(&[31, 42]);
+        let ages_index = builder.index_leaf(&[
+            (&31u32.to_le_bytes(), tagged(0)),
+            (&42u32.to_le_bytes(), tagged(1)),
+        ]);
+        let table = builder.table(
+            &[2, 5, 12, 0],
+            &["name", "scores", "friend", "age"],
+            &[1, 0, 16, 1],
+            &[scores_spec, tagged(0)],
+            &[names, names_index, scores, friends, ages, ages_index],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let row = table.get_row(0).unwrap();
+        assert_eq!(row.get("name"), Some(&Value::from("alice")));
+        assert_eq!(row.get_subtable_as::<i64>("scores").unwrap(), [3, 5]);
+        assert_eq!(row.get("friend"), Some(&Value::Link(Link::new(0, 1))));
+        assert_eq!(row.get("age"), Some(&Value::Int(31)));
+        let row = table.get_row(1).unwrap();
+        assert_eq!(row.get("friend"), Some(&Value::Link(Link::new(0, 0))));
+        assert_eq!(row.get("age"), Some(&Value::Int(42)));
+
+        assert_eq!(
+            table
+                .find_row_number_from_indexed_column("name", &"bob".into())
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            table
+                .find_row_number_from_indexed_column("age", &Value::Int(42))
+                .unwrap(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_collection_columns_are_unsupported() {
+        let mut builder = RealmBuilder::new();
+        let values = builder.integers(&[1]);
+        // The list attribute is only used by newer file formats.
+        let table = builder.table(&[0], &["values"], &[32], &[], &[values]);
+        let top = builder.group(&[("class_Numbers", table)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        assert!(matches!(
+            group.get_table(0),
+            Err(TableError::FileError(RealmFileError::Unsupported { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_column_min_max() {
+        let mut builder = RealmBuilder::new();
+        // The index orders integers by their least significant byte first, so
+        // 256 comes before 1.
+        let values = builder.integers(&[256, 1, 300, 1]);
+        let one_rows = builder.integers(&[1, 3]);
+        let values_index = builder.index_leaf(&[
+            (&256u32.to_le_bytes(), tagged(0)),
+            (&1u32.to_le_bytes(), one_rows),
+            (&300u32.to_le_bytes(), tagged(2)),
+        ]);
+        let empty = builder.integers(&[]);
+        let empty_index = builder.index_leaf(&[]);
+        let table = builder.table(
+            &[0, 0],
+            &["value", "empty"],
+            &[1, 1],
+            &[],
+            &[values, values_index, empty, empty_index],
+        );
+        let top = builder.group(&[("class_Numbers", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.column_min("value").unwrap(), Some(Value::Int(1)));
+        assert_eq!(table.column_max("value").unwrap(), Some(Value::Int(300)));
+        assert_eq!(table.column_min("empty").unwrap(), None);
+        assert_eq!(table.column_max("empty").unwrap(), None);
+        assert!(matches!(
+            people().get_table(0).unwrap().column_min("age"),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+
+        let entries = table.index_entries("value").unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.row_number)
+                .collect::<Vec<_>>(),
+            [0, 1, 3, 2]
+        );
+        assert_eq!(entries[1].keys, [u32::from_be_bytes(1u32.to_le_bytes())]);
+        assert_eq!(table.index_entries("empty").unwrap(), []);
+        assert!(matches!(
+            people().get_table(0).unwrap().index_entries("age"),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_column_names() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.short_strings(&["a"]);
+        let second = builder.short_strings(&["b"]);
+        let suffixed = builder.short_strings(&["c"]);
+        let unnamed = builder.short_strings(&["d"]);
+        let also_unnamed = builder.short_strings(&["e"]);
+        let table = builder.table(
+            &[2, 2, 2, 2, 2],
+            &["name", "name", "name_1", "", ""],
+            &[0, 0, 0, 0, 0],
+            &[],
+            &[first, second, suffixed, unnamed, also_unnamed],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let row = table.get_row(0).unwrap();
+        assert_eq!(row.len(), 5);
+        assert_eq!(row.get("name"), Some(&"a".into()));
+        assert_eq!(row.get_by_index(1), Some(&"b".into()));
+        assert_eq!(
+            row.unique_entries()
+                .map(|(name, value)| (name.into_owned(), value.clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("name".to_string(), "a".into()),
+                ("name_2".to_string(), "b".into()),
+                ("name_1".to_string(), "c".into()),
+                (String::new(), "d".into()),
+                ("_1".to_string(), "e".into()),
+            ]
+        );
+
+        let map = row.to_string_map();
+        assert_eq!(map.len(), 5);
+        assert_eq!(map["name_2"].as_deref(), Some("b"));
+        assert_eq!(map["_1"].as_deref(), Some("e"));
+    }
+
+    #[test]
+    fn test_user_columns() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", tagged(1))]);
+        let friends = builder.integers(&[2, 0]);
+        let backlinks = builder.refs(&[0, tagged(0)]);
+        let table = builder.table(
+            &[2, 12, 14],
+            &["name", "best_friend"],
+            &[1, 16, 0],
+            &[tagged(0), tagged(0), tagged(1)],
+            &[names, names_index, friends, backlinks],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.schema().columns.len(), 3);
+        assert_eq!(
+            table.user_columns(),
+            [
+                ColumnSchema {
+                    name: Some("name".to_string()),
+                    kind: ColumnKind::String,
+                    nullable: false,
+                    indexed: true,
+                    link_target: None,
+                },
+                ColumnSchema {
+                    name: Some("best_friend".to_string()),
+                    kind: ColumnKind::Link,
+                    nullable: true,
+                    indexed: false,
+                    link_target: Some(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_tables() {
+        let mut builder = RealmBuilder::new();
+        let empty = builder.integers(&[]);
+        let empty_refs = builder.refs(&[]);
+        // A leaf of long strings without any strings.
+        let empty_blobs = builder.refs(&[]);
+        let timestamps = builder.refs(&[empty, empty]);
+        let subtable_spec = builder.table_spec(&[0], &["value"], &[0], &[]);
+        let table = builder.table(
+            &[0, 0, 1, 1, 2, 2, 5, 8, 9, 10, 12, 13, 14],
+            &[
+                "int",
+                "int?",
+                "bool",
+                "bool?",
+                "string",
+                "blobs",
+                "table",
+                "timestamp",
+                "float",
+                "double",
+                "link",
+                "links",
+            ],
+            &[0, 16, 0, 16, 0, 16, 0, 16, 0, 0, 0, 0, 0],
+            &[subtable_spec, tagged(1), tagged(1), tagged(1), tagged(10)],
+            &[
+                empty,
+                empty,
+                empty,
+                empty,
+                empty,
+                empty_blobs,
+                empty_refs,
+                timestamps,
+                empty,
+                empty,
+                empty,
+                empty_refs,
+                empty_refs,
+            ],
+        );
+        let no_columns = builder.table(&[], &[], &[], &[], &[]);
+        let top = builder.group(&[("class_Empty", table), ("class_NoColumns", no_columns)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        for table_number in 0..group.table_count() {
+            let table = group.get_table(table_number).unwrap();
+            assert_eq!(table.row_count().unwrap(), 0);
+            assert!(table.get_rows().unwrap().is_empty());
+            for column_number in 0..table.get_column_specs().len() {
+                assert!(table.column_values(column_number).unwrap().is_empty());
+                assert!(table.column_nulls(column_number).unwrap().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported_column_types() {
+        let mut builder = RealmBuilder::new();
+        let data = builder.integers(&[1]);
+        let table = builder.table(&[11], &["value"], &[0], &[], &[data]);
+        let top = builder.group(&[("class_Value", table)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let error = group.get_table(0).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                TableError::FileError(RealmFileError::Unsupported { reason })
+                    if reason.contains("'value'")
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn test_skip_unsupported_column_types() {
+        let mut builder = RealmBuilder::new();
+        builder.skip_unsupported();
+        let decimals = builder.integers(&[1, 2]);
+        let ends = builder.integers(&[1, 3]);
+        let blob = builder.blob(&[1, 2, 3]);
+        let blobs = builder.refs(&[ends, blob]);
+        let ids = builder.integers(&[7, 8]);
+        let table = builder.table(
+            &[11, 4, 0],
+            &["price", "data", "id"],
+            &[0, 0, 0],
+            &[],
+            &[decimals, blobs, ids],
+        );
+        let top = builder.group(&[("class_Value", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.row_count().unwrap(), 2);
+        let row = table.get_row(1).unwrap();
+        assert_eq!(
+            row.get("price"),
+            Some(&Value::Unsupported {
+                kind: ColumnKind::Decimal
+            })
+        );
+        assert_eq!(
+            row.get("data"),
+            Some(&Value::Unsupported {
+                kind: ColumnKind::Binary
+            })
+        );
+        assert_eq!(row.get("id"), Some(&Value::Int(8)));
+        assert_eq!(table.schema().columns[1].kind, ColumnKind::Binary);
+    }
+
+    #[test]
+    fn test_unknown_column_types() {
+        let mut builder = RealmBuilder::new();
+        let data = builder.integers(&[1]);
+        let table = builder.table(
+            &[0, 42, u64::MAX],
+            &["first", "second", "third"],
+            &[0, 0, 0],
+            &[],
+            &[data, data, data],
+        );
+        let top = builder.group(&[("class_Value", table)]);
+        let group = builder.build(top).into_group().unwrap();
+
+        let error = group.get_table(0).unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                TableError::FileError(RealmFileError::InvalidRealmFile { reason })
+                    if reason.contains("'second'") && reason.contains("42")
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut builder = RealmBuilder::new();
+        let values = builder.integers(&(0..10).collect::<Vec<_>>());
+        let table = builder.table(&[0], &["value"], &[0], &[], &[values]);
+        let top = builder.group(&[("class_Numbers", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let sample = |n| {
+            table
+                .sample(n)
+                .unwrap()
+                .iter()
+                .map(|row| row.get("value").cloned().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sample(3), [0, 3, 6].map(Value::Int));
+        assert_eq!(sample(5), [0, 2, 4, 6, 8].map(Value::Int));
+        assert_eq!(sample(20).len(), 10);
+        assert!(sample(0).is_empty());
+    }
+
+    #[test]
+    fn test_iter_models() {
+        #[derive(Debug, PartialEq)]
+        struct Person {
+            name: String,
+            age: i64,
+        }
+
+        realm_model!(Person => name, age);
+
+        let table = people().get_table(0).unwrap();
+        let mut people = table.iter_models::<Person>();
+        assert_eq!(
+            people.next().unwrap().unwrap(),
+            Person {
+                name: "alice".to_string(),
+                age: 31
+            }
+        );
+        assert_eq!(people.next().unwrap().unwrap().name, "bob");
+        assert!(people.next().is_none());
+
+        #[derive(Debug)]
+        #[allow(unused)]
+        struct Misnamed {
+            surname: String,
+        }
+
+        realm_model!(Misnamed => surname);
+
+        let errors = table.iter_models::<Misnamed>().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            Err(ResolveError::Value(ValueError::MissingField { .. }))
+        ));
+        let message = errors[0].as_ref().unwrap_err().to_string();
+        assert!(message.starts_with("Failed to convert row: "), "{message}");
+    }
+
+    #[test]
+    fn test_column_nulls() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.nullable_short_strings(&[Some("alice"), None, Some(""), None]);
+        // Nullable integers, with 9 as the null value, split over two leaves.
+        let first_ages = builder.integers(&[9, 31, 9]);
+        let second_ages = builder.integers(&[9, 9, 42]);
+        let ages = builder.inner_node(tagged(2), &[first_ages, second_ages], 4);
+        let counts = builder.integers(&[1, 2, 3, 4]);
+        let table = builder.table(
+            &[2, 0, 0],
+            &["name", "age", "count"],
+            &[16, 16, 0],
+            &[],
+            &[names, ages, counts],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.column_nulls(0).unwrap(), [false, true, false, true]);
+        assert_eq!(table.column_nulls(1).unwrap(), [false, true, true, false]);
+        assert_eq!(table.column_nulls(2).unwrap(), [false; 4]);
+    }
+
+    #[test]
+    fn test_index_contains() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["bob", "alice", "bob"]);
+        let bob_rows = builder.integers(&[0, 2]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(1)), (b"bob\0", bob_rows)]);
+        let table = builder.table(&[2], &["name"], &[1], &[], &[names, names_index]);
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert!(table.index_contains("name", &Value::from("alice")).unwrap());
+        assert!(table.index_contains("name", &Value::from("bob")).unwrap());
+        assert!(!table.index_contains("name", &Value::from("carl")).unwrap());
+        for value in [Value::None, Value::Double(1.5)] {
+            assert!(matches!(
+                table.index_contains("name", &value),
+                Err(TableError::FileError(RealmFileError::Unsupported { .. }))
+            ));
+        }
+        assert!(matches!(
+            people()
+                .get_table(0)
+                .unwrap()
+                .index_contains("name", &Value::from("bob")),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_rows_from_indexed_column_batch() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["bob", "alice", "bob"]);
+        let bob_rows = builder.integers(&[0, 2]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(1)), (b"bob\0", bob_rows)]);
+        let table = builder.table(&[2], &["name"], &[1], &[], &[names, names_index]);
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(
+            table
+                .find_rows_from_indexed_column_batch(
+                    "name",
+                    &["bob".into(), "carl".into(), "alice".into(), "bob".into()]
+                )
+                .unwrap(),
+            [Some(0), None, Some(1), Some(0)]
+        );
+        assert_eq!(
+            table
+                .find_rows_from_indexed_column_batch("name", &[])
+                .unwrap(),
+            []
+        );
+        for value in [Value::None, Value::Double(1.5)] {
+            assert!(matches!(
+                table.find_rows_from_indexed_column_batch("name", &["bob".into(), value]),
+                Err(TableError::FileError(RealmFileError::Unsupported { .. }))
+            ));
+        }
+        assert!(matches!(
+            table.find_rows_from_indexed_column_batch("age", &[]),
+            Err(TableError::ColumnNotFound { .. })
+        ));
+        assert!(matches!(
+            people()
+                .get_table(0)
+                .unwrap()
+                .find_rows_from_indexed_column_batch("name", &["bob".into()]),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_index() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["bob", "alice", "bob", "carl"]);
+        let bob_rows = builder.integers(&[0, 2]);
+        // "alice" points to the wrong row, and "carl" is missing.
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", bob_rows)]);
+        let table = builder.table(&[2], &["name"], &[1], &[], &[names, names_index]);
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.verify_index("name").unwrap(), vec![1, 3]);
+        assert!(matches!(
+            people().get_table(0).unwrap().verify_index("name"),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_index_with_null() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.nullable_short_strings(&[Some("bob"), None]);
+        // Nulls are stored with the key of the empty string.
+        let names_index = builder.index_leaf(&[(b"\0\0\0\0", tagged(1)), (b"bob\0", tagged(0))]);
+        let table = builder.table(&[2], &["name"], &[17], &[], &[names, names_index]);
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.verify_index("name").unwrap(), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_find_rows_by_token() {
+        let mut builder = RealmBuilder::new();
+        let texts = builder.short_strings(&["Hello world", "world peace", "hello there"]);
+        let hello_rows = builder.integers(&[0, 2]);
+        let world_rows = builder.integers(&[0, 1]);
+        let texts_index = builder.index_leaf(&[
+            (b"hell", hello_rows),
+            (b"peac", tagged(1)),
+            (b"ther", tagged(2)),
+            (b"worl", world_rows),
+        ]);
+        let table = builder.table(&[2], &["text"], &[0x100], &[], &[texts, texts_index]);
+        let top = builder.group(&[("class_Note", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(
+            table.find_rows_by_token("text", "hello").unwrap(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            table.find_rows_by_token("text", "World").unwrap(),
+            vec![0, 1]
+        );
+        assert_eq!(table.find_rows_by_token("text", "peace").unwrap(), vec![1]);
+        assert!(
+            table
+                .find_rows_by_token("text", "other")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            people()
+                .get_table(0)
+                .unwrap()
+                .find_rows_by_token("name", "bob"),
+            Err(TableError::ColumnNotFullTextIndexed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_by_wrong_type() {
+        let table = people().get_table(0).unwrap();
+
+        assert!(matches!(
+            table.find_by_str("age", "31"),
+            Err(TableError::ColumnTypeMismatch {
+                expected: ColumnKind::String,
+                found: ColumnKind::Int,
+                ..
+            })
+        ));
+        assert!(matches!(
+            table.find_by_str("name", "alice"),
+            Err(TableError::ColumnNotIndexed { .. })
+        ));
+        assert!(matches!(
+            table.get_link_list(0, "name"),
+            Err(TableError::ColumnTypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cyclic_subtable() {
+        let mut builder = RealmBuilder::new();
+        let types = builder.integers(&[5]);
+        let names = builder.short_strings(&["children"]);
+        let attributes = builder.integers(&[0]);
+        // The spec of the subtable column is the spec of the table itself, and
+        // the subtable of the first row is the table itself. An array with a
+        // single ref takes 16 bytes.
+        let sub_spec = builder.next_ref();
+        assert_eq!(builder.refs(&[sub_spec + 16]), sub_spec);
+        let spec = builder.refs(&[types, names, attributes, sub_spec]);
+        assert_eq!(spec, sub_spec + 16);
+        let children = builder.next_ref();
+        assert_eq!(builder.refs(&[children + 16]), children);
+        let data = builder.refs(&[children]);
+        assert_eq!(data, children + 16);
+        let table = builder.refs(&[spec, data]);
+        let top = builder.group(&[("class_Node", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        let Err(RealmFileError::InvalidRealmFile { reason }) = table.get_rows() else {
+            panic!("expected reading a cyclic subtable to fail");
+        };
+        assert!(
+            reason.contains("nested more than 64 levels"),
+            "unexpected reason: {reason}"
+        );
+    }
+
+    #[test]
+    fn test_find_by_kind() {
+        let mut builder = RealmBuilder::new();
+        let names = builder.short_strings(&["alice", "bob"]);
+        let names_index = builder.index_leaf(&[(b"alic", tagged(0)), (b"bob\0", tagged(1))]);
+        let ages = builder.integers(&[31, 42]);
+        let ages_index = builder.index_leaf(&[
+            (&31u32.to_le_bytes(), tagged(0)),
+            (&42u32.to_le_bytes(), tagged(1)),
+        ]);
+        // Nullable seconds, with 7 as the null value. The index orders
+        // timestamps by the least significant byte of their seconds first.
+        let seconds = builder.integers(&[7, 1_700_000_000, 1_600_000_000]);
+        let nanoseconds = builder.integers(&[0, 0]);
+        let created = builder.refs(&[seconds, nanoseconds]);
+        let created_index = builder.index_leaf(&[
+            (&1_600_000_000u32.to_le_bytes(), tagged(1)),
+            (&1_700_000_000u32.to_le_bytes(), tagged(0)),
+        ]);
+        let table = builder.table(
+            &[2, 0, 8],
+            &["name", "age", "created"],
+            &[1, 1, 17],
+            &[],
+            &[names, names_index, ages, ages_index, created, created_index],
+        );
+        let top = builder.group(&[("class_Person", table)]);
+        let table = builder
+            .build(top)
+            .into_group()
+            .unwrap()
+            .get_table(0)
+            .unwrap();
+
+        assert_eq!(table.find_by_str("name", "bob").unwrap(), Some(1));
+        assert_eq!(table.find_by_str("name", "carl").unwrap(), None);
+        assert_eq!(table.find_by_int("age", 31).unwrap(), Some(0));
+        assert_eq!(table.find_by_int("age", 43).unwrap(), None);
+        let created = |seconds| DateTime::from_timestamp(seconds, 0).unwrap();
+        assert_eq!(
+            table
+                .find_by_timestamp("created", created(1_600_000_000))
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            table
+                .find_by_timestamp("created", created(1_700_000_000))
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            table
+                .find_by_timestamp("created", created(1_650_000_000))
+                .unwrap(),
+            None
+        );
+    }
+}
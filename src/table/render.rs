@@ -0,0 +1,80 @@
+//! Rendering of values for the exporters, [`Row::to_string_map`],
+//! [`Table::to_csv`](crate::Table::to_csv) and `Row::to_json_map`. Every
+//! exporter renders values the same way, see [`render`], and only differs in
+//! how it writes the rendered values.
+
+use crate::table::Row;
+use crate::value::{Link, Value};
+
+/// A value, as rendered by the exporters.
+pub(crate) enum Rendered<'a> {
+    /// Null values, subtables that were not read (see [`Value::TableLazy`]),
+    /// link lists that can't be read, backlinks, and values of unsupported
+    /// types.
+    Null,
+    Int(i64),
+    Bool(bool),
+    Float(f32),
+    Double(f64),
+    /// Strings, binary values as lowercase hex, and timestamps in RFC 3339
+    /// format.
+    Text(String),
+    Link(Link),
+    LinkList(Vec<Link>),
+    /// The rows of a subtable, which only JSON can hold.
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    Table(&'a [Row<'static>]),
+}
+
+/// Render a single value for the exporters.
+pub(crate) fn render(value: &Value) -> Rendered<'_> {
+    match value {
+        Value::Int(value) => Rendered::Int(*value),
+        Value::Bool(value) => Rendered::Bool(*value),
+        Value::String(value) | Value::OldStringEnum(value) => Rendered::Text(value.clone()),
+        Value::Binary(_) => Rendered::Text(value.binary_as_hex().unwrap_or_default()),
+        Value::Timestamp(value) => Rendered::Text(value.to_rfc3339()),
+        Value::Float(value) => Rendered::Float(*value),
+        Value::Double(value) => Rendered::Double(*value),
+        Value::Link(link) => Rendered::Link(link.clone()),
+        Value::LinkList(links) => Rendered::LinkList(links.clone()),
+        // The exporters can't fail, so link lists that can't be read are
+        // rendered like null values.
+        Value::LinkListLazy(links) => links.to_vec().map_or(Rendered::Null, Rendered::LinkList),
+        Value::Table(rows) => Rendered::Table(rows),
+        Value::TableLazy(_)
+        | Value::Unsupported { .. }
+        | Value::OldMixed
+        | Value::OldDateTime
+        | Value::Reserved4
+        | Value::BackLink(_)
+        | Value::None => Rendered::Null,
+    }
+}
+
+impl Rendered<'_> {
+    /// Render the value as text, for the formats without nested values. Links
+    /// are written as `table:row`, using the table and row numbers, and link
+    /// lists as a `;`-separated list of links.
+    ///
+    /// Returns `None` for null values and subtables, which can't be written
+    /// as text.
+    pub(crate) fn into_text(self) -> Option<String> {
+        match self {
+            Rendered::Int(value) => Some(value.to_string()),
+            Rendered::Bool(value) => Some(value.to_string()),
+            Rendered::Float(value) => Some(value.to_string()),
+            Rendered::Double(value) => Some(value.to_string()),
+            Rendered::Text(value) => Some(value),
+            Rendered::Link(link) => Some(link_text(&link)),
+            Rendered::LinkList(links) => {
+                Some(links.iter().map(link_text).collect::<Vec<_>>().join(";"))
+            }
+            Rendered::Table(_) | Rendered::Null => None,
+        }
+    }
+}
+
+fn link_text(link: &Link) -> String {
+    format!("{}:{}", link.target_table_number, link.row_number)
+}
@@ -1,14 +1,32 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::hash::{Hash, Hasher};
 
+use crate::error::ValueError;
+use crate::table::render::render;
 use crate::value::{Backlink, Value};
 
 /// A single row in a Realm table. This allows you to either extract [`Value`]s
 /// manually, or use [`realm_model!`](`crate::realm_model`) to convert them into
 /// your own structs.
+///
+/// Values are accessed by column name, but the rows also remember the order of
+/// the columns in the table, see [`ordered_entries`](Self::ordered_entries)
+/// and [`get_by_index`](Self::get_by_index). If a table has several columns
+/// with the same name, looking up a value by name finds the first of them,
+/// and [`unique_entries`](Self::unique_entries) gives every column a unique
+/// name.
 #[derive(Debug, Clone)]
 pub struct Row<'a> {
-    values: HashMap<Cow<'a, str>, Value>,
+    /// The values, in the order of the columns in the table. Values that were
+    /// [taken](Self::take) are `None`.
+    values: Vec<Option<Value>>,
     backlinks: Vec<Backlink>,
+    /// The column names, in the order of the columns in the table.
+    column_names: Vec<Cow<'a, str>>,
 }
 
 impl<'a> Row<'a> {
@@ -20,30 +38,164 @@ impl<'a> Row<'a> {
                     .expect("already matched the right value variant")
             })
             .collect();
-        let values = column_names
-            .into_iter()
-            .enumerate()
-            .rev()
-            .map(|(index, name)| (name, row.remove(index)))
-            .collect();
+        // Every column but the backlink columns has a name, so a mismatch is a
+        // bug in this crate rather than in the file.
+        assert_eq!(
+            row.len(),
+            column_names.len(),
+            "row has a different number of values than column names"
+        );
 
-        Self { values, backlinks }
+        Self {
+            values: row.into_iter().map(Some).collect(),
+            backlinks,
+            column_names,
+        }
     }
 
     /// Returns an iterator over the column names and values in this row.
     pub fn entries(&self) -> impl Iterator<Item = (&Cow<'a, str>, &Value)> {
-        self.values.iter()
+        self.ordered_entries()
+    }
+
+    /// Returns an iterator over the column names and values in this row, in
+    /// the order of the columns in the table. Unlike
+    /// [`entries`](Self::entries), the order is guaranteed, which makes this
+    /// suitable for exporting rows.
+    pub fn ordered_entries(&self) -> impl Iterator<Item = (&Cow<'a, str>, &Value)> {
+        self.column_names
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(name, value)| Some((name, value.as_ref()?)))
+    }
+
+    /// Like [`ordered_entries`](Self::ordered_entries), but with unique
+    /// column names, for tables with duplicate column names. The first column
+    /// with a name keeps it, and later ones get a suffix, starting at `_1`
+    /// (e.g. `name`, `name_1`, `name_2`). Suffixes that would clash with
+    /// another column are skipped. Empty names are treated like any other.
+    ///
+    /// This is what [`to_string_map`](Self::to_string_map) uses as keys, so
+    /// that no values are lost.
+    pub fn unique_entries(&self) -> impl Iterator<Item = (Cow<'a, str>, &Value)> {
+        let names = self
+            .ordered_entries()
+            .map(|(name, _)| name.as_ref())
+            .collect::<HashSet<_>>();
+        let mut used = HashSet::new();
+
+        self.ordered_entries().map(move |(name, value)| {
+            if used.insert(name.to_string()) {
+                return (name.clone(), value);
+            }
+
+            let unique = (1..)
+                .map(|n| format!("{name}_{n}"))
+                .find(|candidate| !names.contains(candidate.as_str()) && !used.contains(candidate))
+                .expect("there are always unused suffixes");
+            used.insert(unique.clone());
+
+            (unique.into(), value)
+        })
     }
 
     /// Returns an iterator over the values in this row.
     pub fn values(&self) -> impl Iterator<Item = &Value> {
-        self.values.values()
+        self.values.iter().flatten()
+    }
+
+    /// The number of values in this row, i.e. the number of items returned by
+    /// [`entries`](Self::entries). Backlinks are not counted, and neither are
+    /// values that were [taken](Self::take).
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    /// Returns `true` if this row has no values. See [`len`](Self::len).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The position of the first column with the given name that still has a
+    /// value.
+    fn position(&self, column_name: &str) -> Option<usize> {
+        self.column_names
+            .iter()
+            .zip(&self.values)
+            .position(|(name, value)| name == column_name && value.is_some())
     }
 
     /// Get the value of a column by its name. Returns `None` if the column does
     /// not exist.
     pub fn get(&self, column_name: &str) -> Option<&Value> {
-        self.values.get(column_name)
+        self.values[self.position(column_name)?].as_ref()
+    }
+
+    /// Get the value of a column by its position in the table, regardless of
+    /// its name. This is useful for tables with duplicate or empty column
+    /// names. Backlink columns are not counted, as their values are available
+    /// through [`backlinks`](Self::backlinks) instead.
+    ///
+    /// Returns `None` if the index is out of bounds, or the value was
+    /// [taken](Self::take_by_index).
+    pub fn get_by_index(&self, index: usize) -> Option<&Value> {
+        self.values.get(index)?.as_ref()
+    }
+
+    /// Get the value of a column by its name, converted to `T`. This uses the
+    /// same conversions as [`realm_model`](crate::realm_model), which makes it
+    /// convenient for reading a single field without defining a model.
+    ///
+    /// Returns [`ValueError::MissingField`] if the column does not exist, or
+    /// [`ValueError::FieldConversion`] if the value cannot be converted to
+    /// `T`.
+    pub fn try_get<T>(&self, column_name: &str) -> crate::ValueResult<T>
+    where
+        T: TryFrom<Value>,
+        ValueError: From<T::Error>,
+    {
+        let value = self
+            .get(column_name)
+            .ok_or_else(|| ValueError::MissingField {
+                field: column_name.to_string(),
+                target_type: type_name::<T>(),
+                remaining_fields: Box::new(self.clone().into_owned()),
+            })?;
+
+        T::try_from(value.clone()).map_err(|error| ValueError::FieldConversion {
+            field: column_name.to_string(),
+            target_type: type_name::<T>(),
+            source: Box::new(ValueError::from(error)),
+        })
+    }
+
+    /// Get the subtable in the column with the given name, converting each of
+    /// its rows to `T`, e.g. a struct using
+    /// [`realm_model`](crate::realm_model). Unlike taking the value and
+    /// converting it, this leaves the row intact, at the cost of cloning the
+    /// subtable rows.
+    ///
+    /// Returns the same errors as [`try_get`](Self::try_get).
+    pub fn get_subtable_as<T>(&self, column_name: &str) -> crate::ValueResult<Vec<T>>
+    where
+        T: TryFrom<Row<'static>>,
+        T::Error: Error + 'static,
+    {
+        self.try_get(column_name)
+    }
+
+    /// Get the rows of the subtable in the column with the given name, reading
+    /// them if the row holds a [`Value::TableLazy`], e.g. when it was read
+    /// using [`Table::get_row_lazy`](crate::Table::get_row_lazy).
+    ///
+    /// Returns `None` if the column does not exist or does not hold a
+    /// subtable.
+    pub fn subtable(&self, column_name: &str) -> crate::RealmResult<Option<Vec<Row<'static>>>> {
+        match self.get(column_name) {
+            Some(Value::Table(rows)) => Ok(Some(rows.clone())),
+            Some(Value::TableLazy(subtable)) => subtable.to_vec().map(Some),
+            _ => Ok(None),
+        }
     }
 
     /// Take the value of a column by its name. Returns `None` if the column
@@ -51,7 +203,17 @@ impl<'a> Row<'a> {
     /// row. It is used by [`realm_model`](crate::realm_model) to transfer the
     /// backlinks to your custom struct.
     pub fn take(&mut self, column_name: &str) -> Option<Value> {
-        self.values.remove(column_name)
+        let index = self.position(column_name)?;
+        self.values[index].take()
+    }
+
+    /// Take the value of a column by its position in the table, like
+    /// [`get_by_index`](Self::get_by_index). This method consumes the value,
+    /// removing it from the row. It is used by
+    /// [`realm_model`](crate::realm_model) for fields bound to a column
+    /// index.
+    pub fn take_by_index(&mut self, index: usize) -> Option<Value> {
+        self.values.get_mut(index)?.take()
     }
 
     /// Returns an iterator over the [`Backlink`]s in this row.
@@ -69,7 +231,137 @@ impl<'a> Row<'a> {
 
     /// Check if the row has a field with the given name.
     pub fn has_field(&self, key: &str) -> bool {
-        self.values.contains_key(key)
+        self.position(key).is_some()
+    }
+
+    /// Set the value of the field with the given name, replacing any existing
+    /// value. A new field is placed after the existing fields in
+    /// [`ordered_entries`](Self::ordered_entries).
+    ///
+    /// This is useful for building rows for export, e.g. to replace a
+    /// [`Link`](crate::Link) with the row it points to. See
+    /// [`Group::expand_links`](crate::Group::expand_links).
+    pub fn with_field(mut self, name: impl Into<Cow<'a, str>>, value: Value) -> Self {
+        let name = name.into();
+        match self.position(&name) {
+            Some(index) => self.values[index] = Some(value),
+            None => {
+                self.column_names.push(name);
+                self.values.push(Some(value));
+            }
+        }
+
+        self
+    }
+
+    /// Rename the field with the given name, e.g. to match the naming of the
+    /// system the row is exported to. Does nothing if the row has no field
+    /// with the name `from`. If there are several, only the first is renamed,
+    /// like [`get`](Self::get) only finds the first.
+    pub fn rename(&mut self, from: &str, to: impl Into<Cow<'a, str>>) {
+        if let Some(index) = self.position(from) {
+            self.column_names[index] = to.into();
+        }
+    }
+
+    /// Render every field of this row as a string, keyed by column name, e.g.
+    /// to quickly dump a row without defining a model for it. Values are
+    /// rendered as follows:
+    ///
+    /// - Integers, booleans and floating-point values are formatted as usual.
+    /// - Strings are used as they are.
+    /// - Binary values are rendered as lowercase hex.
+    /// - Timestamps are rendered in RFC 3339 format.
+    /// - Links are rendered as `table:row`, using the table and row numbers.
+    ///   Link lists are rendered as a `;`-separated list of links.
+    ///
+    /// Null values, subtables, and values of unsupported types are `None`.
+    /// Backlinks are not included, as their columns are unnamed. Duplicate
+    /// column names get a suffix, see [`unique_entries`](Self::unique_entries).
+    pub fn to_string_map(&self) -> HashMap<String, Option<String>> {
+        self.unique_entries()
+            .map(|(name, value)| (name.to_string(), render(value).into_text()))
+            .collect()
+    }
+
+    /// Rename all fields using the given function, which receives the current
+    /// name of each field. See [`rename`](Self::rename). Backlinks don't have
+    /// names, so they are left untouched.
+    pub fn rename_all(&mut self, f: impl Fn(&str) -> String) {
+        for name in &mut self.column_names {
+            *name = f(name).into();
+        }
+    }
+
+    /// Compare this row with another, e.g. the same row read from a different
+    /// snapshot of the realm, returning the fields that differ. Each entry
+    /// holds the column name, the value in this row, and the value in `other`,
+    /// where `None` means the row doesn't have the field.
+    ///
+    /// Fields are listed in the [column order](Self::ordered_entries) of this
+    /// row, followed by the fields only `other` has, in its column order.
+    /// Backlinks are not compared.
+    pub fn diff<'r>(
+        &'r self,
+        other: &'r Row<'_>,
+    ) -> Vec<(String, Option<&'r Value>, Option<&'r Value>)> {
+        let ours = self
+            .ordered_entries()
+            .map(|(name, value)| (name.as_ref(), Some(value), other.get(name)));
+        let theirs = other
+            .ordered_entries()
+            .filter(|(name, _)| !self.has_field(name))
+            .map(|(name, value)| (name.as_ref(), None, Some(value)));
+
+        ours.chain(theirs)
+            .filter(|(_, ours, theirs)| ours != theirs)
+            .map(|(name, ours, theirs)| (name.to_string(), ours, theirs))
+            .collect()
+    }
+}
+
+/// Rows are equal if they have the same values for the same column names, and
+/// the same backlinks. The order of the columns is not taken into account, but
+/// the order of the backlinks is: they are compared in the order of their
+/// columns in the table, which is the same for rows of the same table.
+impl PartialEq for Row<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sorted_entries() == other.sorted_entries() && self.backlinks == other.backlinks
+    }
+}
+
+impl Eq for Row<'_> {}
+
+impl Hash for Row<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_entries().hash(state);
+        self.backlinks.hash(state);
+    }
+}
+
+/// Rows are ordered by their values, in order of their column names, followed
+/// by their backlinks.
+impl Ord for Row<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sorted_entries()
+            .cmp(&other.sorted_entries())
+            .then_with(|| self.backlinks.cmp(&other.backlinks))
+    }
+}
+
+impl PartialOrd for Row<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Row<'a> {
+    /// The entries of this row, sorted by column name, so that equal rows
+    /// hash and compare equally regardless of the iteration order of the map.
+    fn sorted_entries(&self) -> Vec<(&Cow<'a, str>, &Value)> {
+        let mut entries = self.ordered_entries().collect::<Vec<_>>();
+        entries.sort_by_key(|(name, _)| *name);
+        entries
     }
 }
 
@@ -85,15 +377,195 @@ impl Row<'_> {
     /// manually. If you use [`realm_model!`](crate::realm_model), the column
     /// names are no longer used.
     pub fn into_owned(self) -> Row<'static> {
-        let values = self
-            .values
-            .into_iter()
-            .map(|(k, v)| (k.into_owned().into(), v))
-            .collect();
-
         Row {
-            values,
+            values: self.values,
             backlinks: self.backlinks,
+            column_names: self
+                .column_names
+                .into_iter()
+                .map(|name| name.into_owned().into())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Backlink, Link, Row, Value, ValueError, realm_model};
+
+    #[test]
+    fn test_diff() {
+        let before = Row::new(
+            vec!["id".into(), 1.into(), "removed".into()],
+            vec!["id".into(), "count".into(), "old".into()],
+        );
+        let after = Row::new(
+            vec!["id".into(), "added".into(), 2.into()],
+            vec!["id".into(), "new".into(), "count".into()],
+        );
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                (
+                    "count".to_string(),
+                    Some(&Value::Int(1)),
+                    Some(&Value::Int(2))
+                ),
+                ("old".to_string(), Some(&"removed".into()), None),
+                ("new".to_string(), None, Some(&"added".into())),
+            ]
+        );
+        assert!(before.diff(&before.clone()).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "row has a different number of values than column names")]
+    fn test_new_length_mismatch() {
+        Row::new(vec![1.into(), 2.into()], vec!["id".into()]);
+    }
+
+    #[test]
+    fn test_eq() {
+        let row = Row::new(
+            vec![1.into(), "name".into()],
+            vec!["id".into(), "name".into()],
+        );
+        let reordered = Row::new(
+            vec!["name".into(), 1.into()],
+            vec!["name".into(), "id".into()],
+        );
+        assert_eq!(row, reordered);
+
+        let mut taken = row.clone();
+        taken.take("name");
+        assert_ne!(row, taken);
+
+        let first = Value::BackLink(Backlink::new(1, 0, vec![3]));
+        let second = Value::BackLink(Backlink::new(2, 0, vec![4]));
+        let with_backlinks = |backlinks: [&Value; 2]| {
+            Row::new(
+                vec![1.into(), backlinks[0].clone(), backlinks[1].clone()],
+                vec!["id".into()],
+            )
+        };
+        assert_eq!(
+            with_backlinks([&first, &second]),
+            with_backlinks([&first, &second])
+        );
+        assert_ne!(
+            with_backlinks([&first, &second]),
+            with_backlinks([&second, &first])
+        );
+    }
+
+    #[test]
+    fn test_to_string_map() {
+        let row = Row::new(
+            vec![
+                1.into(),
+                Value::Bool(true),
+                Value::Binary(vec![0xca, 0xfe]),
+                Value::LinkList(vec![Link::new(2, 5), Link::new(2, 7)]),
+                Value::Table(Vec::new()),
+                Value::None,
+            ],
+            vec![
+                "id".into(),
+                "active".into(),
+                "data".into(),
+                "owners".into(),
+                "tags".into(),
+                "note".into(),
+            ],
+        );
+
+        let map = row.to_string_map();
+        assert_eq!(map.len(), 6);
+        assert_eq!(map["id"].as_deref(), Some("1"));
+        assert_eq!(map["active"].as_deref(), Some("true"));
+        assert_eq!(map["data"].as_deref(), Some("cafe"));
+        assert_eq!(map["owners"].as_deref(), Some("2:5;2:7"));
+        assert_eq!(map["tags"], None);
+        assert_eq!(map["note"], None);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut row = Row::new(
+            vec![
+                1.into(),
+                Value::BackLink(Backlink::new(1, 0, vec![3])),
+                "name".into(),
+            ],
+            vec!["id".into(), "name".into()],
+        );
+        assert_eq!(row.len(), 2);
+        assert!(!row.is_empty());
+
+        row.take("id");
+        row.take("name");
+        assert_eq!(row.len(), 0);
+        assert!(row.is_empty());
+    }
+
+    #[test]
+    fn test_rename() {
+        let backlink = Backlink::new(1, 0, vec![3]);
+        let mut row = Row::new(
+            vec![1.into(), Value::BackLink(backlink.clone()), "name".into()],
+            vec!["userId".into(), "displayName".into()],
+        );
+
+        row.rename("userId", "id");
+        row.rename("missing", "other");
+        assert_eq!(row.get("id"), Some(&Value::Int(1)));
+        assert!(!row.has_field("userId"));
+        assert!(!row.has_field("other"));
+
+        row.rename_all(|name| format!("user_{}", name.to_lowercase()));
+        let names = row
+            .ordered_entries()
+            .map(|(name, _)| name.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["user_id", "user_displayname"]);
+        assert_eq!(row.backlinks().collect::<Vec<_>>(), [&backlink]);
+    }
+
+    #[test]
+    fn test_get_subtable_as() {
+        #[derive(Debug, PartialEq)]
+        struct Item {
+            name: String,
         }
+
+        realm_model!(Item => name);
+
+        let items = vec![
+            Row::new(vec!["first".into()], vec!["name".into()]),
+            Row::new(vec!["second".into()], vec!["name".into()]),
+        ];
+        let row = Row::new(
+            vec!["parent".into(), items.into()],
+            vec!["id".into(), "items".into()],
+        );
+
+        let items: Vec<Item> = row.get_subtable_as("items").unwrap();
+        assert_eq!(
+            items,
+            vec![
+                Item {
+                    name: "first".to_string()
+                },
+                Item {
+                    name: "second".to_string()
+                }
+            ]
+        );
+        assert!(row.has_field("items"));
+        assert!(matches!(
+            row.get_subtable_as::<Item>("id"),
+            Err(ValueError::FieldConversion { .. })
+        ));
     }
 }
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+
+use crate::spec::ColumnKind;
+
+/// A description of all tables in a Realm file, as returned by
+/// [`Group::schema`](crate::Group::schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseSchema {
+    /// The tables in the file, in table order.
+    pub tables: Vec<DatabaseTableSchema>,
+}
+
+/// A description of a single table in a [`DatabaseSchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseTableSchema {
+    /// The name of the table.
+    pub name: String,
+    /// The columns of the table.
+    pub schema: TableSchema,
+    /// The name of the table each link or link list column points to, keyed
+    /// by column name.
+    pub link_targets: BTreeMap<String, String>,
+}
+
+impl DatabaseSchema {
+    /// Get the table with the given name, if any.
+    pub fn table(&self, name: &str) -> Option<&DatabaseTableSchema> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+}
+
+/// A description of the columns of a [`Table`](crate::Table), as returned by
+/// [`Table::schema`](crate::Table::schema).
+///
+/// Two schemas are equal if they have the same columns, in the same order.
+/// Use [`diff`](Self::diff) to find out how two schemas differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSchema {
+    /// The columns of the table, in column order.
+    pub columns: Vec<ColumnSchema>,
+}
+
+/// A description of a single column in a [`TableSchema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSchema {
+    /// The name of the column. All columns except backlinks are named.
+    pub name: Option<String>,
+    /// The kind of values stored in the column.
+    pub kind: ColumnKind,
+    /// Whether the column is nullable.
+    pub nullable: bool,
+    /// Whether the column is indexed.
+    pub indexed: bool,
+    /// For link and link list columns, the number of the table that is linked
+    /// to. For backlink columns, the number of the table the links originate
+    /// from.
+    pub link_target: Option<usize>,
+}
+
+/// A difference between two [`TableSchema`]s, as returned by
+/// [`TableSchema::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// The column only exists in the other schema.
+    Added(ColumnSchema),
+    /// The column only exists in this schema.
+    Removed(ColumnSchema),
+    /// The column exists in both schemas, but its kind or nullability
+    /// differs.
+    Retyped {
+        /// The column as it is in this schema.
+        from: ColumnSchema,
+        /// The column as it is in the other schema.
+        to: ColumnSchema,
+    },
+}
+
+impl TableSchema {
+    /// Get the column with the given name, if any.
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns
+            .iter()
+            .find(|column| column.name.as_deref() == Some(name))
+    }
+
+    /// Determine the changes needed to go from this schema to `other`.
+    ///
+    /// Columns are matched by name, so a column that moved to a different
+    /// position is not considered changed. Unnamed (backlink) columns are not
+    /// taken into account, as they can't be matched up reliably.
+    pub fn diff(&self, other: &TableSchema) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+
+        for column in self.named_columns() {
+            let name = column.name.as_deref().unwrap();
+            match other.column(name) {
+                None => changes.push(SchemaChange::Removed(column.clone())),
+                Some(other_column)
+                    if other_column.kind != column.kind
+                        || other_column.nullable != column.nullable =>
+                {
+                    changes.push(SchemaChange::Retyped {
+                        from: column.clone(),
+                        to: other_column.clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        for column in other.named_columns() {
+            let name = column.name.as_deref().unwrap();
+            if self.column(name).is_none() {
+                changes.push(SchemaChange::Added(column.clone()));
+            }
+        }
+
+        changes
+    }
+
+    fn named_columns(&self) -> impl Iterator<Item = &ColumnSchema> {
+        self.columns.iter().filter(|column| column.name.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, kind: ColumnKind, nullable: bool) -> ColumnSchema {
+        ColumnSchema {
+            name: Some(name.to_string()),
+            kind,
+            nullable,
+            indexed: false,
+            link_target: None,
+        }
+    }
+
+    #[test]
+    fn test_schema_diff() {
+        let old = TableSchema {
+            columns: vec![
+                column("id", ColumnKind::String, false),
+                column("count", ColumnKind::Int, false),
+                column("removed", ColumnKind::Bool, false),
+            ],
+        };
+        let new = TableSchema {
+            columns: vec![
+                column("count", ColumnKind::Int, true),
+                column("id", ColumnKind::String, false),
+                column("added", ColumnKind::Timestamp, true),
+            ],
+        };
+
+        assert_ne!(old, new);
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                SchemaChange::Retyped {
+                    from: column("count", ColumnKind::Int, false),
+                    to: column("count", ColumnKind::Int, true),
+                },
+                SchemaChange::Removed(column("removed", ColumnKind::Bool, false)),
+                SchemaChange::Added(column("added", ColumnKind::Timestamp, true)),
+            ]
+        );
+    }
+}
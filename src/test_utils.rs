@@ -0,0 +1,341 @@
+//! Helpers for building Realm files in memory, so tests don't depend on real
+//! `.realm` files.
+//!
+//! The benchmarks use these helpers as well, through the `test-utils` feature.
+//! They are not part of the public API.
+
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+#[cfg(test)]
+use std::sync::Arc;
+
+#[cfg(test)]
+use crate::array::RealmRef;
+use crate::realm::{Realm, RealmOptions};
+
+/// Node header flag: the node is an inner B+Tree node.
+pub const INNER_BPTREE: u8 = 0x80;
+/// Node header flag: the node's elements are refs (or tagged integers).
+pub const HAS_REFS: u8 = 0x40;
+/// Node header flag with a meaning specific to the node, e.g. marking a
+/// sub-index in a search index.
+const CONTEXT: u8 = 0x20;
+
+/// Width scheme and width bits for 32-bit wide integers.
+const WIDTH_32: u8 = 0x06;
+/// Width scheme and width bits for 64-bit wide integers.
+const WIDTH_64: u8 = 0x07;
+/// Width scheme bits for arrays whose width is the number of bytes per
+/// element, such as short strings.
+const WIDTH_SCHEME_BYTES: u8 = 0x08;
+/// Width scheme bits for arrays whose size is the number of bytes, such as
+/// blobs.
+const WIDTH_SCHEME_BLOB: u8 = 0x10;
+
+/// Tag an integer so it can be stored in a node with refs.
+pub fn tagged(value: u64) -> u64 {
+    (value << 1) | 1
+}
+
+/// Builds a Realm file in memory, one node at a time. Every method appending
+/// a node returns the ref of that node, which can then be stored in other
+/// nodes.
+///
+/// ```ignore
+/// let mut builder = RealmBuilder::new();
+/// let first = builder.integers(&[1, 2]);
+/// let second = builder.integers(&[3]);
+/// let root = builder.inner_node(tagged(2), &[first, second], 3);
+/// let realm = builder.build(root);
+/// ```
+pub struct RealmBuilder {
+    buf: Vec<u8>,
+    options: RealmOptions,
+    /// The number of bytes between the file header and the first node, see
+    /// [`with_offset`](Self::with_offset).
+    offset: u64,
+}
+
+impl Default for RealmBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RealmBuilder {
+    /// Start a new file, with a file header for format 9.9.
+    pub fn new() -> Self {
+        let mut buf = vec![0; 24];
+        buf[16..20].copy_from_slice(b"T-DB");
+        buf[20..22].copy_from_slice(&[9, 9]);
+
+        Self {
+            buf,
+            options: RealmOptions::default(),
+            offset: 0,
+        }
+    }
+
+    /// Start a new file whose nodes are placed the given number of bytes
+    /// (a multiple of 8) after the file header, e.g. to test refs beyond 4
+    /// GiB. Such files can only be written using [`write`](Self::write).
+    pub fn with_offset(offset: u64) -> Self {
+        assert!(offset.is_multiple_of(8), "offset must be a multiple of 8");
+
+        Self {
+            offset,
+            ..Self::new()
+        }
+    }
+
+    /// Append a node with the given header flags (including the width) and
+    /// size, followed by the payload, padded to 8 bytes.
+    pub fn node(&mut self, flags: u8, size: u32, payload: &[u8]) -> u64 {
+        let ref_ = self.next_ref();
+        self.buf.extend_from_slice(&[0x41, 0x41, 0x41, 0x41, flags]);
+        self.buf.extend_from_slice(&size.to_be_bytes()[1..]);
+        self.buf.extend_from_slice(payload);
+        self.buf.resize(self.buf.len().next_multiple_of(8), 0);
+
+        ref_
+    }
+
+    /// The ref the next node will be appended at, for building nodes that
+    /// refer to nodes appended after them, e.g. to test cycles.
+    pub fn next_ref(&self) -> u64 {
+        self.offset + self.buf.len() as u64
+    }
+
+    /// Append an array of 64-bit integers, with the given extra header flags.
+    pub fn integers_with_flags(&mut self, flags: u8, values: &[u64]) -> u64 {
+        let payload = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        self.node(flags | WIDTH_64, values.len() as u32, &payload)
+    }
+
+    /// Append an array of 64-bit integers.
+    pub fn integers(&mut self, values: &[u64]) -> u64 {
+        self.integers_with_flags(0, values)
+    }
+
+    /// Append an array of refs, which may also hold [tagged](tagged)
+    /// integers.
+    pub fn refs(&mut self, refs: &[u64]) -> u64 {
+        self.integers_with_flags(HAS_REFS, refs)
+    }
+
+    /// Append an inner B+Tree node. `first` is either the ref of an offsets
+    /// array (general form), or the [tagged](tagged) number of elements per
+    /// child (compact form).
+    pub fn inner_node(&mut self, first: u64, children: &[u64], total_size: u64) -> u64 {
+        let mut values = Vec::with_capacity(children.len() + 2);
+        values.push(first);
+        values.extend_from_slice(children);
+        values.push(tagged(total_size));
+
+        self.integers_with_flags(INNER_BPTREE | HAS_REFS, &values)
+    }
+
+    /// Append an array of short strings (at most 63 bytes each), where `None`
+    /// is a null string. The strings are written as given, even if they are
+    /// not valid UTF-8.
+    pub fn nullable_short_strings<S: AsRef<[u8]>>(&mut self, values: &[Option<S>]) -> u64 {
+        let width = values
+            .iter()
+            .map(|value| value.as_ref().map_or(0, |value| value.as_ref().len()) + 1)
+            .max()
+            .unwrap_or(1)
+            .next_power_of_two();
+
+        let mut payload = Vec::with_capacity(values.len() * width);
+        for value in values {
+            let mut element = vec![0u8; width];
+            match value {
+                Some(value) => {
+                    let value = value.as_ref();
+                    element[..value.len()].copy_from_slice(value);
+                    element[width - 1] = (width - 1 - value.len()) as u8;
+                }
+                None => element[width - 1] = width as u8,
+            }
+            payload.extend_from_slice(&element);
+        }
+
+        let width_ndx = width.trailing_zeros() as u8 + 1;
+        self.node(
+            WIDTH_SCHEME_BYTES | width_ndx,
+            values.len() as u32,
+            &payload,
+        )
+    }
+
+    /// Append an array of short strings (at most 63 bytes each).
+    pub fn short_strings<S: AsRef<[u8]>>(&mut self, values: &[S]) -> u64 {
+        let values = values.iter().map(Some).collect::<Vec<_>>();
+
+        self.nullable_short_strings(&values)
+    }
+
+    /// Append a blob node, holding the given bytes.
+    pub fn blob(&mut self, bytes: &[u8]) -> u64 {
+        self.node(WIDTH_SCHEME_BLOB, bytes.len() as u32, bytes)
+    }
+
+    /// Append an array of medium-sized strings, stored as a single blob with
+    /// an array of end offsets.
+    pub fn blob_strings<S: AsRef<[u8]>>(&mut self, values: &[S]) -> u64 {
+        let mut blob = Vec::new();
+        let mut ends = Vec::with_capacity(values.len());
+        for value in values {
+            blob.extend_from_slice(value.as_ref());
+            blob.push(0);
+            ends.push(blob.len() as u64);
+        }
+
+        let ends = self.integers(&ends);
+        let blob = self.blob(&blob);
+        self.refs(&[ends, blob])
+    }
+
+    /// Append a table: its spec (column types, names and attributes, and
+    /// optionally the sub-spec entries of link and subtable columns), and the
+    /// refs of the data of each column (followed by the ref of its index, for
+    /// indexed columns).
+    pub fn table(
+        &mut self,
+        column_types: &[u64],
+        column_names: &[&str],
+        column_attributes: &[u64],
+        sub_spec: &[u64],
+        data: &[u64],
+    ) -> u64 {
+        let header = self.table_spec(column_types, column_names, column_attributes, sub_spec);
+        let data = self.refs(data);
+
+        self.refs(&[header, data])
+    }
+
+    /// Append the spec of a table, as used for both top-level tables and
+    /// subtables.
+    pub fn table_spec(
+        &mut self,
+        column_types: &[u64],
+        column_names: &[&str],
+        column_attributes: &[u64],
+        sub_spec: &[u64],
+    ) -> u64 {
+        let column_types = self.integers(column_types);
+        let column_names = self.short_strings(column_names);
+        let column_attributes = self.integers(column_attributes);
+        if sub_spec.is_empty() {
+            return self.refs(&[column_types, column_names, column_attributes]);
+        }
+
+        let sub_spec = self.refs(sub_spec);
+        self.refs(&[column_types, column_names, column_attributes, sub_spec])
+    }
+
+    /// Append a search index with a single leaf, holding the given keys (the
+    /// first four bytes of each value, in ascending order) along with either
+    /// the [tagged](tagged) row number, or the ref of a list of row numbers.
+    pub fn index_leaf(&mut self, entries: &[(&[u8; 4], u64)]) -> u64 {
+        self.index_leaf_with_flags(0, entries)
+    }
+
+    /// Append a sub-index, for values sharing the same first four bytes, like
+    /// an [index leaf](Self::index_leaf) holding the next four bytes of each
+    /// value as keys.
+    pub fn sub_index_leaf(&mut self, entries: &[(&[u8; 4], u64)]) -> u64 {
+        self.index_leaf_with_flags(CONTEXT, entries)
+    }
+
+    fn index_leaf_with_flags(&mut self, flags: u8, entries: &[(&[u8; 4], u64)]) -> u64 {
+        let keys = entries
+            .iter()
+            .flat_map(|(key, _)| u32::from_be_bytes(**key).to_le_bytes())
+            .collect::<Vec<_>>();
+        let keys = self.node(WIDTH_32, entries.len() as u32, &keys);
+
+        let mut refs = vec![keys];
+        refs.extend(entries.iter().map(|(_, value)| *value));
+        self.integers_with_flags(flags | HAS_REFS, &refs)
+    }
+
+    /// Append the top array of a group holding the given named tables,
+    /// returning its ref.
+    pub fn group(&mut self, tables: &[(&str, u64)]) -> u64 {
+        let names = tables.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let table_names = self.short_strings(&names);
+        let table_refs = tables.iter().map(|(_, ref_)| *ref_).collect::<Vec<_>>();
+        let tables = self.refs(&table_refs);
+
+        self.refs(&[table_names, tables])
+    }
+
+    /// Set the flags byte of the file header, e.g. to mark the file as
+    /// encrypted.
+    pub fn file_flags(&mut self, flags: u8) {
+        self.buf[23] = flags;
+    }
+
+    /// Keep up to the given number of decoded node headers once the file is
+    /// built, see [`RealmOptions::header_cache`].
+    pub fn header_cache(&mut self, capacity: usize) {
+        self.options = self.options.clone().header_cache(capacity);
+    }
+
+    /// Read columns of unsupported types as placeholders once the file is
+    /// built, see [`RealmOptions::skip_unsupported`].
+    pub fn skip_unsupported(&mut self) {
+        self.options = self.options.clone().skip_unsupported(true);
+    }
+
+    /// Finish the file, using the given ref as the top ref, and write it to
+    /// the given path. The space between the file header and the first node
+    /// is left unwritten, so on most file systems the file is sparse.
+    pub fn write(mut self, top_ref: u64, path: &Path) -> std::io::Result<()> {
+        self.buf[0..8].copy_from_slice(&top_ref.to_le_bytes());
+
+        let mut file = File::create(path)?;
+        file.write_all(&self.buf[..24])?;
+        file.seek(SeekFrom::Start(self.offset + 24))?;
+        file.write_all(&self.buf[24..])
+    }
+
+    /// Finish the file, using the given ref as the top ref, and return its
+    /// bytes.
+    pub fn into_bytes(mut self, top_ref: u64) -> Vec<u8> {
+        assert_eq!(self.offset, 0, "files with an offset must be written");
+
+        self.buf[0..8].copy_from_slice(&top_ref.to_le_bytes());
+        self.buf
+    }
+
+    /// Finish the file, using the given ref as the top ref.
+    pub fn build(self, top_ref: u64) -> Realm {
+        let options = self.options.clone();
+
+        options.from_bytes(self.into_bytes(top_ref)).unwrap()
+    }
+
+    /// Finish the file without a top ref, returning the realm along with the
+    /// given ref, ready for constructing nodes.
+    #[cfg(test)]
+    pub(crate) fn build_with_ref(self, ref_: u64) -> (Arc<Realm>, RealmRef) {
+        (Arc::new(self.build(0)), RealmRef::new(ref_ as usize))
+    }
+}
+
+/// Create a Realm holding a single node with the given flags, size and
+/// payload, returning the realm and the ref of the node.
+#[cfg(test)]
+pub(crate) fn single_node(flags: u8, size: u32, payload: &[u8]) -> (Arc<Realm>, RealmRef) {
+    let mut builder = RealmBuilder::new();
+    let ref_ = builder.node(flags, size, payload);
+
+    builder.build_with_ref(ref_)
+}
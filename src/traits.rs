@@ -62,6 +62,11 @@ pub(crate) trait ArrayLike<T, Context = ()>: NodeWithContext<Context> + Debug {
     /// Check if the value at the given index is null.
     fn is_null(&self, index: usize) -> crate::RealmResult<bool>;
 
+    /// Check for every value in the array whether it is null.
+    fn null_bitmap(&self) -> crate::RealmResult<Vec<bool>> {
+        (0..self.size()).map(|i| self.is_null(i)).collect()
+    }
+
     /// Get the size of the array, indicating the number of elements it contains.
     fn size(&self) -> usize;
 }
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::{array::RealmRef, realm::Realm};
+use crate::{RealmFileError, array::RealmRef, realm::Realm};
 
 /// Read a value from a Realm node payload. The width here corresponds to the
 /// width value read from the Realm node header.
+///
+/// A width of 0 means every element in the array is 0, so the payload is
+/// empty and is not read at all.
 pub(crate) fn read_array_value(payload: &[u8], width: u8, index: usize) -> u64 {
     match width {
         0 => 0,
@@ -40,6 +43,18 @@ pub(crate) fn read_array_value(payload: &[u8], width: u8, index: usize) -> u64 {
     }
 }
 
+/// Check that the given index is within the bounds of an array holding `size`
+/// elements, so that reading it does not panic.
+pub(crate) fn check_index(index: usize, size: usize) -> crate::RealmResult<()> {
+    if index >= size {
+        return Err(RealmFileError::InvalidRealmFile {
+            reason: format!("index {index} out of bounds for array of size {size}"),
+        });
+    }
+
+    Ok(())
+}
+
 /// Find the index of the child node that contains the specified
 /// element index. Element index zero corresponds to the first element
 /// of the first leaf node contained in the subtree corresponding with
@@ -73,7 +88,7 @@ fn find_bptree_child(
     first_value: u64,
     index: usize,
 ) -> crate::RealmResult<(usize, usize)> {
-    if first_value % 2 != 0 {
+    if !first_value.is_multiple_of(2) {
         // Case 1/2: No offsets array (compact form)
         let elems_per_child = (first_value / 2) as usize;
         let child_ndx = index / elems_per_child;
@@ -104,20 +119,19 @@ pub(crate) fn find_bptree_child_in_payload(
     Ok((child_ref, index_in_child))
 }
 
-/// Converts a byte vector to a string, assuming it is null-terminated.
-pub(crate) fn string_from_bytes(mut bytes: Vec<u8>) -> String {
-    assert!(
-        !bytes.is_empty(),
-        "string cannot be empty (should have a trailing \\0"
-    );
-    assert!(
-        bytes[bytes.len() - 1] == 0,
-        "string must end with a \\0 byte"
-    );
-
-    bytes.pop();
+/// Converts a byte vector to a string, stripping its trailing null byte.
+/// Invalid UTF-8 is replaced with U+FFFD, as files may be corrupt.
+///
+/// Returns an error if the bytes are not null-terminated.
+pub(crate) fn string_from_bytes(mut bytes: Vec<u8>) -> crate::RealmResult<String> {
+    if bytes.pop() != Some(0) {
+        return Err(RealmFileError::InvalidRealmFile {
+            reason: "string is not null-terminated".to_string(),
+        });
+    }
 
-    unsafe { String::from_utf8_unchecked(bytes) }
+    Ok(String::from_utf8(bytes)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned()))
 }
 
 /// Lower/upper bound in sorted sequence
@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+
+use crate::value::Value;
+
+/// Values are equal if they are of the same variant, and hold equal data.
+///
+/// Floating-point values ([`Value::Float`] and [`Value::Double`]) are compared
+/// by their bit patterns, rather than using IEEE 754 equality. This makes the
+/// comparison reflexive, so `Value` can be used as a key in a `HashMap`, but it
+/// means that `NaN` is equal to itself (if the bits match), and that `0.0` and
+/// `-0.0` are not equal.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::OldStringEnum(a), Value::OldStringEnum(b)) => a == b,
+            (Value::Binary(a), Value::Binary(b)) => a == b,
+            (Value::Table(a), Value::Table(b)) => a == b,
+            (Value::Timestamp(a), Value::Timestamp(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+            (Value::Double(a), Value::Double(b)) => a.to_bits() == b.to_bits(),
+            (Value::Link(a), Value::Link(b)) => a == b,
+            (Value::LinkList(a), Value::LinkList(b)) => a == b,
+            (Value::LinkListLazy(a), Value::LinkListLazy(b)) => a == b,
+            (Value::TableLazy(a), Value::TableLazy(b)) => a == b,
+            (Value::Unsupported { kind: a }, Value::Unsupported { kind: b }) => a == b,
+            (Value::BackLink(a), Value::BackLink(b)) => a == b,
+            (Value::OldMixed, Value::OldMixed)
+            | (Value::OldDateTime, Value::OldDateTime)
+            | (Value::Reserved4, Value::Reserved4)
+            | (Value::None, Value::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+
+        match self {
+            Value::Int(value) => value.hash(state),
+            Value::Bool(value) => value.hash(state),
+            Value::String(value) | Value::OldStringEnum(value) => value.hash(state),
+            Value::Binary(value) => value.hash(state),
+            Value::Table(rows) => rows.hash(state),
+            Value::Timestamp(value) => value.hash(state),
+            Value::Float(value) => value.to_bits().hash(state),
+            Value::Double(value) => value.to_bits().hash(state),
+            Value::Link(link) => link.hash(state),
+            Value::LinkList(links) => links.hash(state),
+            Value::LinkListLazy(links) => links.hash(state),
+            Value::TableLazy(subtable) => subtable.hash(state),
+            Value::Unsupported { kind } => kind.hash(state),
+            Value::BackLink(backlink) => backlink.hash(state),
+            Value::OldMixed | Value::OldDateTime | Value::Reserved4 | Value::None => {}
+        }
+    }
+}
+
+/// Values of the same variant are compared naturally: numbers by value,
+/// strings and binary blobs lexicographically, timestamps chronologically, and
+/// so on. Values of different variants are ordered by the order in which the
+/// variants are declared in [`Value`], so for example every [`Value::Int`] sorts
+/// before every [`Value::String`], and [`Value::None`] sorts last.
+///
+/// Floating-point values are compared using [`f64::total_cmp`], which orders
+/// `-0.0` before `0.0`, and `NaN` after all other numbers. This is consistent
+/// with the bitwise equality used for [`PartialEq`].
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::OldStringEnum(a), Value::OldStringEnum(b)) => a.cmp(b),
+            (Value::Binary(a), Value::Binary(b)) => a.cmp(b),
+            (Value::Table(a), Value::Table(b)) => a.cmp(b),
+            (Value::Timestamp(a), Value::Timestamp(b)) => a.cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+            (Value::Double(a), Value::Double(b)) => a.total_cmp(b),
+            (Value::Link(a), Value::Link(b)) => a.cmp(b),
+            (Value::LinkList(a), Value::LinkList(b)) => a.cmp(b),
+            (Value::LinkListLazy(a), Value::LinkListLazy(b)) => a.cmp(b),
+            (Value::TableLazy(a), Value::TableLazy(b)) => a.cmp(b),
+            (Value::Unsupported { kind: a }, Value::Unsupported { kind: b }) => a.cmp(b),
+            (Value::BackLink(a), Value::BackLink(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Value {
+    /// The position of this value's variant in the declaration of [`Value`],
+    /// used to order values of different variants.
+    fn rank(&self) -> u8 {
+        match self {
+            Value::Int(_) => 0,
+            Value::Bool(_) => 1,
+            Value::String(_) => 2,
+            Value::OldStringEnum(_) => 3,
+            Value::Binary(_) => 4,
+            Value::Table(_) => 5,
+            Value::OldMixed => 6,
+            Value::OldDateTime => 7,
+            Value::Timestamp(_) => 8,
+            Value::Float(_) => 9,
+            Value::Double(_) => 10,
+            Value::Reserved4 => 11,
+            Value::Link(_) => 12,
+            Value::LinkList(_) => 13,
+            Value::LinkListLazy(_) => 14,
+            Value::TableLazy(_) => 15,
+            Value::Unsupported { .. } => 16,
+            Value::BackLink(_) => 17,
+            Value::None => 18,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::value::Value;
+
+    #[test]
+    fn test_value_as_map_key() {
+        let mut map = HashMap::new();
+        map.insert(Value::String("a".to_string()), 1);
+        map.insert(Value::Int(1), 2);
+        map.insert(Value::Double(f64::NAN), 3);
+        map.insert(Value::None, 4);
+
+        assert_eq!(map.get(&Value::String("a".to_string())), Some(&1));
+        assert_eq!(map.get(&Value::Int(1)), Some(&2));
+        assert_eq!(map.get(&Value::Double(f64::NAN)), Some(&3));
+        assert_eq!(map.get(&Value::None), Some(&4));
+        assert_eq!(map.get(&Value::Float(1.0)), None);
+        assert_ne!(Value::Double(0.0), Value::Double(-0.0));
+    }
+
+    #[test]
+    fn test_value_ordering() {
+        let mut values = vec![
+            Value::None,
+            Value::String("b".to_string()),
+            Value::Double(f64::NAN),
+            Value::Int(3),
+            Value::String("a".to_string()),
+            Value::Double(-1.5),
+            Value::Int(-7),
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            vec![
+                Value::Int(-7),
+                Value::Int(3),
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+                Value::Double(-1.5),
+                Value::Double(f64::NAN),
+                Value::None,
+            ]
+        );
+    }
+}
@@ -41,7 +41,7 @@ impl From<f32> for Value {
 
 impl From<f64> for Value {
     fn from(value: f64) -> Self {
-        Value::Float(value as f32)
+        Value::Double(value)
     }
 }
 
@@ -1,20 +1,25 @@
 use std::any::type_name;
 use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 
 use crate::error::ValueError;
 use crate::table::Row;
-use crate::value::{ARRAY_VALUE_KEY, Backlink, Link, Value};
+use crate::value::{ARRAY_VALUE_KEY, Backlink, Link, TimestampMillis, Value};
 
 macro_rules! value_try_into {
     (Option<$target:ty>, $source:ident) => {
+        value_try_into!(Option<$target>, $source, ::core::convert::identity);
+    };
+
+    (Option<$target:ty>, $source:ident, $convert:expr) => {
         impl TryFrom<Value> for Option<$target> {
             type Error = ValueError;
 
             fn try_from(value: Value) -> Result<Self, Self::Error> {
                 match value {
-                    Value::$source(val) => Ok(Some(val)),
+                    Value::$source(val) => Ok(Some($convert(val))),
                     Value::None => Ok(None),
                     value => Err(ValueError::UnexpectedType {
                         expected: stringify!($target),
@@ -26,12 +31,16 @@ macro_rules! value_try_into {
     };
 
     ($target:ty, $source:ident) => {
+        value_try_into!($target, $source, ::core::convert::identity);
+    };
+
+    ($target:ty, $source:ident, $convert:expr) => {
         impl TryFrom<Value> for $target {
             type Error = ValueError;
 
             fn try_from(value: Value) -> Result<Self, Self::Error> {
                 match value {
-                    Value::$source(val) => Ok(val),
+                    Value::$source(val) => Ok($convert(val)),
                     value => Err(ValueError::UnexpectedType {
                         expected: stringify!($target),
                         found: value,
@@ -40,36 +49,128 @@ macro_rules! value_try_into {
             }
         }
 
+        value_try_into!(@row $target);
+    };
+
+    (@row $target:ty) => {
         impl<'a> TryFrom<Row<'a>> for $target {
             type Error = ValueError;
 
-            fn try_from(mut value: Row<'a>) -> Result<Self, Self::Error> {
-                let Some(value) = value.take(ARRAY_VALUE_KEY) else {
-                    return Err(ValueError::ExpectedArrayRow {
-                        field: ARRAY_VALUE_KEY,
-                        found: value.into_owned(),
-                    });
-                };
-
-                value.try_into()
+            fn try_from(value: Row<'a>) -> Result<Self, Self::Error> {
+                take_array_value(value)?.try_into()
             }
         }
     };
 }
 
+/// Take the value out of a row of a subtable holding a list of scalars. See
+/// [`ValueError::ExpectedArrayRow`].
+fn take_array_value(mut row: Row<'_>) -> crate::ValueResult<Value> {
+    row.take(ARRAY_VALUE_KEY)
+        .ok_or_else(|| ValueError::ExpectedArrayRow {
+            field: ARRAY_VALUE_KEY,
+            found: Box::new(row.into_owned()),
+        })
+}
+
+/// Implement conversions from integer values into other integer types, which
+/// fail with [`ValueError::IntOutOfRange`] if the value doesn't fit.
+macro_rules! int_try_into {
+    ($($target:ty),+) => {
+        $(
+            impl TryFrom<Value> for $target {
+                type Error = ValueError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::Int(val) => {
+                            <$target>::try_from(val).map_err(|_| ValueError::IntOutOfRange {
+                                value: val,
+                                target: stringify!($target),
+                            })
+                        }
+                        value => Err(ValueError::UnexpectedType {
+                            expected: stringify!($target),
+                            found: value,
+                        }),
+                    }
+                }
+            }
+
+            impl TryFrom<Value> for Option<$target> {
+                type Error = ValueError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::None => Ok(None),
+                        value => value.try_into().map(Some),
+                    }
+                }
+            }
+
+            value_try_into!(@row $target);
+        )+
+    };
+}
+
 value_try_into!(String, String);
 value_try_into!(Option<String>, String);
 value_try_into!(i64, Int);
 value_try_into!(Option<i64>, Int);
+int_try_into!(i32, u32, u64, usize);
 value_try_into!(bool, Bool);
 value_try_into!(f32, Float);
 value_try_into!(f64, Double);
 value_try_into!(DateTime<Utc>, Timestamp);
 value_try_into!(Option<DateTime<Utc>>, Timestamp);
+value_try_into!(NaiveDateTime, Timestamp, |val: DateTime<Utc>| val
+    .naive_utc());
+value_try_into!(Option<NaiveDateTime>, Timestamp, |val: DateTime<Utc>| val
+    .naive_utc());
+value_try_into!(TimestampMillis, Timestamp, TimestampMillis::from);
+value_try_into!(Option<TimestampMillis>, Timestamp, TimestampMillis::from);
 value_try_into!(Backlink, BackLink);
 value_try_into!(Link, Link);
 value_try_into!(Option<Link>, Link);
 
+/// Converts a timestamp into a [`SystemTime`], through its offset from the Unix
+/// epoch. Timestamps before the epoch are rejected, as not every platform can
+/// represent them.
+impl TryFrom<Value> for SystemTime {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Timestamp(val) => val
+                .signed_duration_since(DateTime::UNIX_EPOCH)
+                .to_std()
+                .ok()
+                .and_then(|duration| UNIX_EPOCH.checked_add(duration))
+                .ok_or(ValueError::TimestampOutOfRange {
+                    value: val,
+                    target: "SystemTime",
+                }),
+            value => Err(ValueError::UnexpectedType {
+                expected: "SystemTime",
+                found: value,
+            }),
+        }
+    }
+}
+
+impl TryFrom<Value> for Option<SystemTime> {
+    type Error = ValueError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::None => Ok(None),
+            value => value.try_into().map(Some),
+        }
+    }
+}
+
+value_try_into!(@row SystemTime);
+
 impl<'a, T> TryFrom<Value> for Vec<T>
 where
     T: TryFrom<Row<'a>>,
@@ -94,3 +195,167 @@ where
         }
     }
 }
+
+impl Value {
+    /// Convert a subtable holding a list of scalars, such as a `Vec<String>`
+    /// field of a model, into a `Vec<T>`. Realm stores such lists as
+    /// subtables with a single column, whose value is extracted from every
+    /// row. This is what [`realm_model!`](crate::realm_model) does for these
+    /// fields, for when you read the value yourself.
+    ///
+    /// Returns [`ValueError::ExpectedTable`] if the value is not a
+    /// [`Table`](Self::Table); read lazy subtables first, e.g. using
+    /// [`Row::subtable`]. Returns [`ValueError::VecConversionError`] if a row
+    /// does not hold a list element, or the element cannot be converted to
+    /// `T`.
+    pub fn into_scalar_vec<T>(self) -> crate::ValueResult<Vec<T>>
+    where
+        T: TryFrom<Value>,
+        ValueError: From<T::Error>,
+    {
+        let Value::Table(rows) = self else {
+            return Err(ValueError::ExpectedTable { found: self });
+        };
+
+        rows.into_iter()
+            .map(|row| {
+                let result = take_array_value(row)
+                    .and_then(|value| T::try_from(value).map_err(ValueError::from));
+
+                result.map_err(|error| ValueError::VecConversionError {
+                    element_type: type_name::<T>(),
+                    source: Box::new(error),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use chrono::DateTime;
+
+    use crate::Row;
+    use crate::error::ValueError;
+    use crate::value::{ARRAY_VALUE_KEY, Value};
+
+    #[test]
+    fn test_int_conversions() {
+        assert_eq!(u32::try_from(Value::Int(42)).unwrap(), 42);
+        assert_eq!(i32::try_from(Value::Int(-42)).unwrap(), -42);
+        assert_eq!(
+            u64::try_from(Value::Int(i64::MAX)).unwrap(),
+            i64::MAX as u64
+        );
+        assert_eq!(usize::try_from(Value::Int(7)).unwrap(), 7);
+        assert_eq!(Option::<u32>::try_from(Value::None).unwrap(), None);
+        assert_eq!(Option::<u32>::try_from(Value::Int(1)).unwrap(), Some(1));
+
+        assert!(matches!(
+            u64::try_from(Value::Int(-1)),
+            Err(ValueError::IntOutOfRange {
+                value: -1,
+                target: "u64"
+            })
+        ));
+        assert!(matches!(
+            Option::<i32>::try_from(Value::Int(i64::from(i32::MAX) + 1)),
+            Err(ValueError::IntOutOfRange { target: "i32", .. })
+        ));
+        assert!(matches!(
+            u32::try_from(Value::Bool(true)),
+            Err(ValueError::UnexpectedType {
+                expected: "u32",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_system_time_conversions() {
+        let epoch = Value::Timestamp(DateTime::UNIX_EPOCH);
+        assert_eq!(SystemTime::try_from(epoch).unwrap(), UNIX_EPOCH);
+
+        let after = Value::Timestamp(DateTime::from_timestamp(1_700_000_000, 5_000_000).unwrap());
+        assert_eq!(
+            SystemTime::try_from(after.clone()).unwrap(),
+            UNIX_EPOCH + Duration::new(1_700_000_000, 5_000_000)
+        );
+        assert_eq!(
+            Option::<SystemTime>::try_from(after).unwrap(),
+            Some(UNIX_EPOCH + Duration::new(1_700_000_000, 5_000_000))
+        );
+        assert_eq!(Option::<SystemTime>::try_from(Value::None).unwrap(), None);
+
+        let before = DateTime::from_timestamp_millis(-1).unwrap();
+        assert!(matches!(
+            SystemTime::try_from(Value::Timestamp(before)),
+            Err(ValueError::TimestampOutOfRange {
+                value,
+                target: "SystemTime"
+            }) if value == before
+        ));
+        assert!(matches!(
+            SystemTime::try_from(Value::Int(0)),
+            Err(ValueError::UnexpectedType {
+                expected: "SystemTime",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_into_scalar_vec() {
+        let list = |values: Vec<Value>| {
+            Value::Table(
+                values
+                    .into_iter()
+                    .map(|value| Row::new(vec![value], vec![ARRAY_VALUE_KEY.into()]))
+                    .collect(),
+            )
+        };
+
+        assert_eq!(
+            list(vec!["a".into(), "b".into()])
+                .into_scalar_vec::<String>()
+                .unwrap(),
+            ["a", "b"]
+        );
+        assert_eq!(
+            list(vec![1.into(), Value::None])
+                .into_scalar_vec::<Option<u32>>()
+                .unwrap(),
+            [Some(1), None]
+        );
+        assert!(
+            Value::Table(Vec::new())
+                .into_scalar_vec::<i64>()
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(matches!(
+            list(vec![(-1).into()]).into_scalar_vec::<u32>(),
+            Err(ValueError::VecConversionError {
+                element_type: "u32",
+                ..
+            })
+        ));
+        let other_column = Value::Table(vec![Row::new(vec![1.into()], vec!["value".into()])]);
+        let Err(ValueError::VecConversionError { source, .. }) =
+            other_column.into_scalar_vec::<i64>()
+        else {
+            panic!("expected VecConversionError");
+        };
+        assert!(matches!(
+            source.downcast_ref(),
+            Some(ValueError::ExpectedArrayRow { .. })
+        ));
+        assert!(matches!(
+            Value::Int(1).into_scalar_vec::<i64>(),
+            Err(ValueError::ExpectedTable { .. })
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use crate::column::{BpTree, IntColumnType};
+use crate::value::Link;
+
+/// A list of links that is read from the Realm file on demand, rather than all
+/// at once. This is returned by [`Column::get_lazy`](crate::Column::get_lazy)
+/// and [`Table::get_link_list`](crate::Table::get_link_list), and is useful for
+/// link lists with many links, when only the number of links or a few of the
+/// links are needed.
+///
+/// Link list refs are compared by the links they contain, just like
+/// `Vec<Link>`. Links that can't be read compare equal to each other, and
+/// less than any other link.
+#[derive(Clone)]
+pub struct LinkListRef {
+    /// The row numbers, or `None` if the list is empty. Long lists are split
+    /// over multiple leaves, like any other column.
+    links: Option<BpTree<IntColumnType>>,
+    /// The number of links, read from the root of the B+Tree up front.
+    len: usize,
+    target_table_number: usize,
+}
+
+impl LinkListRef {
+    pub(crate) fn new(
+        links: Option<BpTree<IntColumnType>>,
+        target_table_number: usize,
+    ) -> crate::RealmResult<Self> {
+        let len = links.as_ref().map_or(Ok(0), BpTree::count)?;
+
+        Ok(Self {
+            links,
+            len,
+            target_table_number,
+        })
+    }
+
+    /// The table number of the target table, in the Realm
+    /// [`Group`](`crate::Group`).
+    pub fn target_table_number(&self) -> usize {
+        self.target_table_number
+    }
+
+    /// The number of links in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the list contains no links.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the link at the given index, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// Returns an error if the link can't be read from the file.
+    pub fn try_get(&self, index: usize) -> crate::RealmResult<Option<Link>> {
+        let Some(links) = &self.links else {
+            return Ok(None);
+        };
+        if index >= self.len {
+            return Ok(None);
+        }
+
+        let row_number = links.get(index)?;
+
+        Ok(Some(Link::new(
+            self.target_table_number,
+            row_number as usize,
+        )))
+    }
+
+    /// Get the link at the given index, or `None` if the index is out of
+    /// bounds, or the link can't be read. Use [`try_get`](Self::try_get) to
+    /// tell these apart.
+    pub fn get(&self, index: usize) -> Option<Link> {
+        self.try_get(index).ok().flatten()
+    }
+
+    /// Iterate over the links in the list. Every link is read from the file
+    /// when the iterator reaches it, which fails if the link can't be read.
+    pub fn iter(&self) -> impl Iterator<Item = crate::RealmResult<Link>> + '_ {
+        (0..self.len()).filter_map(|index| self.try_get(index).transpose())
+    }
+
+    /// Read all links in the list.
+    ///
+    /// Returns an error if any of the links can't be read from the file.
+    pub fn to_vec(&self) -> crate::RealmResult<Vec<Link>> {
+        self.iter().collect()
+    }
+
+    /// The links in the list, with `None` for links that can't be read, for
+    /// comparing and hashing lists, which can't fail.
+    fn links_or_none(&self) -> impl Iterator<Item = Option<Link>> + '_ {
+        self.iter().map(Result::ok)
+    }
+}
+
+impl Debug for LinkListRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkListRef")
+            .field("target_table_number", &self.target_table_number)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl PartialEq for LinkListRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.links_or_none().eq(other.links_or_none())
+    }
+}
+
+impl Eq for LinkListRef {}
+
+impl Hash for LinkListRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the same way as the equivalent `Vec<Link>`.
+        self.len().hash(state);
+        for link in self.links_or_none().flatten() {
+            link.hash(state);
+        }
+    }
+}
+
+impl Ord for LinkListRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.links_or_none().cmp(other.links_or_none())
+    }
+}
+
+impl PartialOrd for LinkListRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{RealmBuilder, single_node, tagged};
+    use crate::traits::Node;
+
+    #[test]
+    fn test_link_list_ref() {
+        let (realm, ref_) = single_node(0x04, 3, &[3, 5, 8]);
+        let links = LinkListRef::new(Some(BpTree::from_ref(realm, ref_).unwrap()), 2).unwrap();
+
+        assert_eq!(links.len(), 3);
+        assert!(!links.is_empty());
+        assert_eq!(links.get(1), Some(Link::new(2, 5)));
+        assert_eq!(links.get(3), None);
+        assert_eq!(links.try_get(1).unwrap(), Some(Link::new(2, 5)));
+        assert_eq!(links.try_get(3).unwrap(), None);
+        assert_eq!(
+            links.to_vec().unwrap(),
+            vec![Link::new(2, 3), Link::new(2, 5), Link::new(2, 8)]
+        );
+
+        let empty = LinkListRef::new(None, 2).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.get(0), None);
+        assert!(empty < links);
+        assert_eq!(links, links.clone());
+    }
+
+    #[test]
+    fn test_link_list_ref_with_inner_nodes() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.integers(&[3, 5]);
+        let second = builder.integers(&[8]);
+        let root = builder.inner_node(tagged(2), &[first, second], 3);
+        let (realm, root) = builder.build_with_ref(root);
+        let links = LinkListRef::new(Some(BpTree::from_ref(realm, root).unwrap()), 2).unwrap();
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links.get(2), Some(Link::new(2, 8)));
+        assert_eq!(links.get(3), None);
+        assert_eq!(
+            links.to_vec().unwrap(),
+            vec![Link::new(2, 3), Link::new(2, 5), Link::new(2, 8)]
+        );
+    }
+
+    #[test]
+    fn test_link_list_ref_errors() {
+        let mut builder = RealmBuilder::new();
+        let first = builder.integers(&[3, 5]);
+        // The second leaf can't be read as integers: its width is 16 bytes.
+        let second = builder.node(0x0D, 1, &[0; 16]);
+        let root = builder.inner_node(tagged(2), &[first, second], 3);
+        let (realm, root) = builder.build_with_ref(root);
+        let links = LinkListRef::new(Some(BpTree::from_ref(realm, root).unwrap()), 2).unwrap();
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links.try_get(1).unwrap(), Some(Link::new(2, 5)));
+        assert!(links.try_get(2).is_err());
+        assert_eq!(links.get(2), None);
+        assert!(links.to_vec().is_err());
+        assert_eq!(
+            links.iter().map(|link| link.is_ok()).collect::<Vec<_>>(),
+            [true, true, false]
+        );
+    }
+}
@@ -1,14 +1,29 @@
+use std::fmt::{Display, Formatter};
+
 use chrono::{DateTime, Utc};
 
+use crate::spec::ColumnKind;
 use crate::table::Row;
 
+mod cmp;
 mod from;
 mod into;
+mod link_list;
+mod subtable;
+
+pub use crate::value::link_list::LinkListRef;
+pub use crate::value::subtable::SubtableRef;
 
 pub(crate) const ARRAY_VALUE_KEY: &str = "!ARRAY_VALUE";
 
-// Should match [`crate::spec::ColumnType`]
+// Should match [`crate::ColumnKind`]
 /// A single value from a Realm database. Represents one row in one column.
+///
+/// Values implement [`Eq`] and [`Hash`], so they can be used as keys in a
+/// `HashMap`. Note that floating-point values are compared by their bit
+/// patterns for this purpose, so `NaN` is equal to itself, but `0.0` and `-0.0`
+/// are different. Values are also totally ordered, see the [`Ord`]
+/// implementation for details.
 #[derive(Debug, Clone)]
 pub enum Value {
     /// A signed integer value. Integers may be nullable in Realm, in which case
@@ -30,7 +45,8 @@ pub enum Value {
     /// entire table. In that case, upon loading the row, the subtable and all
     /// its rows are loaded.
     Table(Vec<Row<'static>>),
-    /// Currently unsupported.
+    /// Not produced when reading: values of (legacy) mixed columns are read
+    /// as the variant matching the type of each value.
     #[doc(hidden)]
     OldMixed,
     /// Currently unsupported.
@@ -52,6 +68,24 @@ pub enum Value {
     /// A list of links to rows in a given table. If a row has no links, this
     /// will be an empty list.
     LinkList(Vec<Link>),
+    /// A list of links to rows in a given table, which is read on demand. This
+    /// is only returned when explicitly requested, e.g. using
+    /// [`Column::get_lazy`](crate::Column::get_lazy). It is never equal to a
+    /// [`LinkList`](Self::LinkList), even if both contain the same links.
+    LinkListLazy(LinkListRef),
+    /// A subtable, which is read on demand. This is only returned when
+    /// explicitly requested, e.g. using
+    /// [`Column::get_lazy`](crate::Column::get_lazy). It is never equal to a
+    /// [`Table`](Self::Table), even if both contain the same rows.
+    TableLazy(SubtableRef),
+    /// A placeholder for a value in a column of a type this library can't
+    /// read yet. This is only returned for files opened with
+    /// [`RealmOptions::skip_unsupported`](crate::RealmOptions::skip_unsupported);
+    /// otherwise, building a table with such a column fails.
+    Unsupported {
+        /// The kind of the column holding the value.
+        kind: ColumnKind,
+    },
     /// A backlink. In cases where table A maintains a link (see [`Link`] or
     /// [`LinkList`](`Self::LinkList`)), table B maintains a backlink to table
     /// A. You can use this to navigate back to the parent row in a has-one
@@ -77,10 +111,70 @@ impl Value {
     pub fn is_none(&self) -> bool {
         matches!(self, Value::None)
     }
+
+    /// The number of milliseconds since the Unix epoch, if the value is a
+    /// [`Timestamp`](Self::Timestamp).
+    pub fn timestamp_millis(&self) -> Option<i64> {
+        match self {
+            Value::Timestamp(value) => Some(value.timestamp_millis()),
+            _ => None,
+        }
+    }
+
+    /// The bytes of the value as lowercase hex, if the value is
+    /// [`Binary`](Self::Binary).
+    pub fn binary_as_hex(&self) -> Option<String> {
+        match self {
+            Value::Binary(value) => Some(hex::encode(value)),
+            _ => None,
+        }
+    }
+
+    /// The bytes of the value as standard, padded base64, if the value is
+    /// [`Binary`](Self::Binary).
+    ///
+    /// This method is only available with the `base64` feature.
+    #[cfg(feature = "base64")]
+    pub fn binary_as_base64(&self) -> Option<String> {
+        use base64::Engine;
+
+        match self {
+            Value::Binary(value) => Some(base64::engine::general_purpose::STANDARD.encode(value)),
+            _ => None,
+        }
+    }
+}
+
+/// A timestamp, as the number of milliseconds since the Unix epoch. This can
+/// be used in a [`realm_model!`](crate::realm_model) to read a timestamp column
+/// into an integer, rather than a `chrono::DateTime<Utc>`.
+///
+/// ```rust
+/// use realm_db_reader::{realm_model, TimestampMillis};
+///
+/// struct MyStruct {
+///     created_at: TimestampMillis,
+///     updated_at: Option<TimestampMillis>,
+/// }
+///
+/// realm_model!(MyStruct => created_at, updated_at);
+/// ```
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TimestampMillis(pub i64);
+
+impl From<DateTime<Utc>> for TimestampMillis {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self(value.timestamp_millis())
+    }
 }
 
 /// A link to a single row in a given table.
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+///
+/// Links always refer to a top-level table by its number in the
+/// [`Group`](crate::Group), including links read from the rows of a subtable
+/// (see [`Value::Table`]). Such links can be resolved like any other, e.g.
+/// using [`Group::resolve_link`](crate::Group::resolve_link).
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Link {
     /// The table number of the target table, in the Realm
     /// [`Group`](`crate::Group`).
@@ -99,10 +193,42 @@ impl Link {
     }
 }
 
+/// Formats the link as `table#N -> row#M`. Use
+/// [`Group::format_link`](crate::Group::format_link) to include the name of the
+/// target table instead.
+impl Display for Link {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "table#{} -> row#{}",
+            self.target_table_number, self.row_number
+        )
+    }
+}
+
+/// A [`Link`] with the name of its target table, rather than its number, as
+/// returned by [`Group::describe_link`](crate::Group::describe_link). Table
+/// numbers only mean something within a single file, so this is the form to
+/// use when storing or exporting links.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ResolvedLink {
+    /// The name of the target table.
+    pub table_name: String,
+    /// The row number this link points to.
+    pub row_number: usize,
+}
+
+/// Formats the link as `table_name -> row#M`.
+impl Display for ResolvedLink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> row#{}", self.table_name, self.row_number)
+    }
+}
+
 /// A backlink to one or more rows in a given table. This is the opposite end of
 /// a [`Link`]. Note that [`row_numbers`](`Self::row_numbers`) is guaranteed to
 /// be non-empty. An empty backlink would be represented as [`Value::None`].
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Backlink {
     /// The table number of the origin table, in the Realm
     /// [`Group`](`crate::Group`).
@@ -128,3 +254,41 @@ impl Backlink {
         }
     }
 }
+
+/// Formats the backlink as `table#N.col#C <- rows[A, B]`.
+impl Display for Backlink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "table#{}.col#{} <- rows{:?}",
+            self.origin_table_number, self.origin_column_number, self.row_numbers
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_links() {
+        assert_eq!(Link::new(2, 5).to_string(), "table#2 -> row#5");
+        assert_eq!(
+            Backlink::new(1, 3, vec![4, 7]).to_string(),
+            "table#1.col#3 <- rows[4, 7]"
+        );
+    }
+
+    #[test]
+    fn test_binary_rendering() {
+        let value = Value::Binary(vec![0x00, 0xAB, 0x10, 0xFF]);
+        assert_eq!(value.binary_as_hex().as_deref(), Some("00ab10ff"));
+        assert_eq!(Value::from("00ab").binary_as_hex(), None);
+
+        #[cfg(feature = "base64")]
+        {
+            assert_eq!(value.binary_as_base64().as_deref(), Some("AKsQ/w=="));
+            assert_eq!(Value::None.binary_as_base64(), None);
+        }
+    }
+}
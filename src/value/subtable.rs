@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::array::RealmRef;
+use crate::table::{Row, Table};
+
+/// A subtable that is read from the Realm file on demand, rather than all at
+/// once. This is returned by [`Column::get_lazy`](crate::Column::get_lazy) and
+/// [`Table::get_subtable`](crate::Table::get_subtable), and is useful for large
+/// subtables, when only a few of their rows are needed, or when the rows should
+/// be read into different models.
+///
+/// Subtable refs are compared by the location of the subtable in the file, so
+/// they are equal if they refer to the same subtable, without reading any of
+/// their rows.
+#[derive(Clone)]
+pub struct SubtableRef {
+    /// The subtable, along with the ref of its data, or `None` if the subtable
+    /// is null.
+    table: Option<(RealmRef, Arc<Table>)>,
+}
+
+impl SubtableRef {
+    pub(crate) fn new(table: Option<(RealmRef, Table)>) -> Self {
+        Self {
+            table: table.map(|(ref_, table)| (ref_, Arc::new(table))),
+        }
+    }
+
+    /// The subtable, or `None` if it is null. Null subtables have no rows.
+    pub fn table(&self) -> Option<&Table> {
+        self.table.as_ref().map(|(_, table)| table.as_ref())
+    }
+
+    /// The number of rows in the subtable.
+    pub fn row_count(&self) -> crate::RealmResult<usize> {
+        self.table().map_or(Ok(0), Table::row_count)
+    }
+
+    /// Get the row with the given number (starting with 0). See
+    /// [`Table::get_row`].
+    ///
+    /// Returns an error if the subtable is null.
+    pub fn get_row(&self, row_number: usize) -> crate::RealmResult<Row<'_>> {
+        match self.table() {
+            Some(table) => table.get_row(row_number),
+            None => Err(crate::RealmFileError::InvalidRealmFile {
+                reason: format!("row {row_number} is out of bounds for a null subtable"),
+            }),
+        }
+    }
+
+    /// Read all rows of the subtable, as they would be held by a
+    /// [`Value::Table`](crate::Value::Table).
+    pub fn to_vec(&self) -> crate::RealmResult<Vec<Row<'static>>> {
+        let Some(table) = self.table() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(table.get_rows()?.into_iter().map(Row::into_owned).collect())
+    }
+
+    /// The location of the subtable in the file, used for comparisons.
+    fn location(&self) -> Option<RealmRef> {
+        self.table.as_ref().map(|(ref_, _)| *ref_)
+    }
+}
+
+impl Debug for SubtableRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubtableRef")
+            .field("location", &self.location())
+            .finish()
+    }
+}
+
+impl PartialEq for SubtableRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.location() == other.location()
+    }
+}
+
+impl Eq for SubtableRef {}
+
+impl Hash for SubtableRef {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.location().hash(state);
+    }
+}
+
+impl Ord for SubtableRef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.location()
+            .map(RealmRef::to_offset)
+            .cmp(&other.location().map(RealmRef::to_offset))
+    }
+}
+
+impl PartialOrd for SubtableRef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}